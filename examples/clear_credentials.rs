@@ -7,21 +7,21 @@
 //!
 //! Unlike the other examples in this crate, this one touches the *real* OS
 //! credential store, not a mock or a remote API -- running it has an actual,
-//! irreversible effect (whichever provider is currently configured will need
-//! its token re-entered in Preferences afterward). It asks for an explicit
+//! irreversible effect (every keyed provider will need its token re-entered
+//! in Preferences afterward). It asks for an explicit
 //! "yes" confirmation on stdin before deleting anything.
 //!
 //! ```sh
 //! cargo run --example clear_credentials
 //! ```
-use open_weather_wizard::config::AppConfig;
+use open_weather_wizard::config::{AppConfig, WeatherApiProvider};
 use std::io::{self, Write};
 
 fn main() {
     println!("Clear Stored Credentials");
     println!("=========================\n");
     println!(
-        "This will permanently delete the Weather Wizard API token stored in \
+        "This will permanently delete every Weather Wizard API token stored in \
          this OS's secure credential store (macOS Keychain / Windows Credential \
          Manager / Linux Secret Service). You'll need to re-enter it in \
          Preferences afterward.\n"
@@ -39,11 +39,17 @@ fn main() {
         return;
     }
 
-    // Deleting the token doesn't depend on any other config field --
+    // Deleting a token doesn't depend on any other config field --
     // AppConfig::default() is just a handle to call the method through.
     let config = AppConfig::default();
-    match config.delete_api_token() {
-        Ok(()) => println!("\n✅ Stored API token deleted."),
-        Err(e) => println!("\n⚠️  Failed to delete API token: {e}"),
+    println!();
+    for provider in [
+        WeatherApiProvider::OpenWeather,
+        WeatherApiProvider::GoogleWeather,
+    ] {
+        match config.delete_api_token(&provider) {
+            Ok(()) => println!("✅ Stored {provider} API token deleted."),
+            Err(e) => println!("⚠️  Failed to delete {provider} API token: {e}"),
+        }
     }
 }
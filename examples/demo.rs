@@ -37,13 +37,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             city: "San Francisco".to_string(),
             state: "CA".to_string(),
             country: "US".to_string(),
+            ..Default::default()
         },
     }];
 
-    config.set_api_token("demo_api_token_12345")?;
+    config.set_api_token(&config.weather_provider.clone(), "demo_api_token_12345")?;
     println!("   ✅ API token stored securely in the OS keychain");
 
-    let decoded_token = config.get_api_token()?;
+    let decoded_token = config.get_api_token(&config.weather_provider)?;
     println!("   ✅ API token decoded: {}", decoded_token);
 
     // Demo 2: Weather provider testing
@@ -55,6 +56,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         &WeatherApiProvider::GoogleWeather,
         None,
         config.language,
+        config.retry,
+        &config.http.client()?,
+        &config.endpoints,
     )?;
 
     let weather_result = provider.get_weather(&config.current_location()).await;
@@ -12,6 +12,9 @@
 //! GOOGLE_WEATHER_API_KEY=your-key-here cargo run --example google_weather_test
 //! ```
 use open_weather_wizard::config::{Language, LocationConfig, WeatherApiProvider};
+use open_weather_wizard::weather_api::endpoints::Endpoints;
+use open_weather_wizard::weather_api::http::HttpSettings;
+use open_weather_wizard::weather_api::retry::RetryPolicy;
 use open_weather_wizard::weather_api::weather_provider::WeatherProviderFactory;
 
 #[tokio::main]
@@ -23,6 +26,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         city: "Peoria".to_string(),
         state: "IL".to_string(),
         country: "US".to_string(),
+        ..Default::default()
     };
 
     // Never hardcode a real API key in source -- read it from the
@@ -46,6 +50,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         &WeatherApiProvider::GoogleWeather,
         Some(api_key),
         Language::English,
+        RetryPolicy::default(),
+        &HttpSettings::default().client()?,
+        &Endpoints::default(),
     )?;
 
     match provider.get_weather(&location).await {
@@ -35,6 +35,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             // alpha-2 country code -- "UK" isn't one (the correct code is
             // "GB") and silently returns zero results rather than an error.
             country: "GB".to_string(),
+            ..Default::default()
         },
     }];
 
@@ -58,6 +59,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         &WeatherApiProvider::OpenWeather,
         Some(api_key.to_string()),
         config.language,
+        config.retry,
+        &config.http.client()?,
+        &config.endpoints,
     )?;
 
     match provider.get_weather(&config.current_location()).await {
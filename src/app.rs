@@ -13,7 +13,7 @@ use iced::widget::Space;
 use iced::{Element, Size, Subscription, Task, Theme, window};
 
 use crate::config::{
-    AppConfig, ConfigManager, LocationConfig, ThemePreference, WeatherApiProvider,
+    AppConfig, ConfigManager, LocationConfig, SavedLocation, ThemePreference, WeatherApiProvider,
 };
 use crate::ui::temperature::{
     celsius_to_display, compass_direction, distance_to_display, distance_unit, format_local_time,
@@ -21,9 +21,12 @@ use crate::ui::temperature::{
 };
 use crate::ui::{about, icons, main_screen, preferences, transition};
 use crate::weather_api::alerts::{AlertSeverity, WeatherAlert};
-use crate::weather_api::forecast::ForecastResponse;
-use crate::weather_api::openweather_api::{ApiResponse, WeatherSymbol, get_weather_symbol};
-use crate::weather_api::weather_provider::WeatherProviderFactory;
+use crate::weather_api::cache::{self, CachingProvider, Entry, ResponseCache};
+use crate::weather_api::forecast::{ForecastResponse, HourlyForecast};
+use crate::weather_api::geocoding::GeocodeCandidate;
+use crate::weather_api::http::HttpSettings;
+use crate::weather_api::openweather_api::{ApiResponse, Coord, WeatherSymbol, get_weather_symbol};
+use crate::weather_api::weather_provider::{WeatherProvider, WeatherProviderFactory};
 use tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
 
 pub const DEFAULT_WINDOW_WIDTH: f32 = 720.0;
@@ -52,6 +55,25 @@ const AUTO_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
 /// free tier; `AUTO_REFRESH_INTERVAL`'s 30s would blow through it in about a
 /// day.
 const GOOGLE_WEATHER_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// The keyless providers' default refresh, when none is configured. Not a
+/// floor like Google's -- Open-Meteo's free tier allows 10,000 calls/day,
+/// which even a 30s refresh (2 calls each) stays under, and NWS and MET
+/// Norway publish no fixed limit (MET's responses are cached until their
+/// `Expires` regardless) -- but Open-Meteo's current conditions only update
+/// every 15 minutes and NWS's and MET's forecasts less often than that, so
+/// polling any faster by default just re-downloads the same numbers from a
+/// free, publicly-funded service.
+const KEYLESS_REFRESH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Shown data counts as stale once it's missed this many refreshes in a
+/// row -- see `AppState::is_stale`.
+const STALE_AFTER_REFRESHES: u32 = 3;
+/// ...but never sooner than this, so a 30s refresh interval doesn't flag a
+/// minute-long network blip.
+const MIN_STALE_AGE: Duration = Duration::from_secs(5 * 60);
+/// The longest auto-refresh waits between attempts while they keep failing
+/// -- or the refresh interval itself, if that's longer. See
+/// `backoff_interval`.
+const MAX_REFRESH_BACKOFF: Duration = Duration::from_secs(30 * 60);
 /// Drives redraws for the animated Lottie icons (~30fps); `icons::view`
 /// computes each frame from wall-clock time, so this tick carries no state of
 /// its own -- it exists purely to make iced re-invoke `view()` regularly.
@@ -117,18 +139,39 @@ pub struct AppState {
     pub config_manager: ConfigManager,
     pub weather: WeatherStatus,
     pub forecast: ForecastStatus,
+    /// The hourly strip's data -- `None` until the first hourly fetch for
+    /// this location succeeds. No status enum like `ForecastStatus`: it's
+    /// never shown while loading or on error, and a failed refresh keeps
+    /// the last good data (same as `alerts`).
+    pub hourly: Option<HourlyForecast>,
     pub alerts: Vec<WeatherAlert>,
+    /// Where every weather/forecast/alerts fetch saves its answer, and what
+    /// `show_cached` opens a location with before its first fetch answers.
+    pub cache: ResponseCache,
+    /// The one client every request goes through, built from
+    /// `config.http` at boot and rebuilt when Preferences changes it -- see
+    /// `weather_api::http`.
+    pub http_client: reqwest::Client,
     /// The OS's current light/dark preference, as of the last
     /// `detect_system_theme_task` poll -- only consulted by `theme()` when
     /// `config.theme_preference` (or the live Preferences draft) is
     /// `ThemePreference::System`. Defaults to `Theme::Light` until the
     /// first detection (fired at boot) resolves.
     pub system_theme: Theme,
-    /// When `weather` last transitioned to `Loaded`, for the "Updated Xs ago"
-    /// label. `main_screen` re-renders often enough (via `AnimationTick`,
-    /// already needed for the animated icons) that this stays fresh without
-    /// its own timer.
+    /// When `weather` last transitioned to `Loaded` -- or, restored from the
+    /// cache, when that data was fetched -- for the "Updated Xs ago" label.
+    /// `main_screen` re-renders often enough (via `AnimationTick`, already
+    /// needed for the animated icons) that this stays fresh without its own
+    /// timer.
     pub last_updated: Option<Instant>,
+    /// Weather fetches that have failed in a row since the last success --
+    /// reset by one, or by switching to another location, whose fetches
+    /// haven't failed yet. Forecast, hourly, and alerts failures don't
+    /// count: they're never surfaced on their own.
+    pub consecutive_failures: u32,
+    /// The most recent of those failures' message, for the stale banner --
+    /// `None` once a fetch succeeds.
+    pub last_error: Option<String>,
     /// Drives the per-value cross-fade when a tracked field's freshly
     /// fetched value differs from what was last displayed -- see
     /// `ui::transition`. Noted in `update()` on fetch success, read (never
@@ -159,12 +202,33 @@ pub struct AppState {
     tray_icon: Option<TrayIcon>,
 }
 
+impl AppState {
+    /// Whether the weather on screen is old *because* refreshing it keeps
+    /// failing: the last fetch failed, and the data is older than
+    /// `STALE_AFTER_REFRESHES` refresh intervals (at least `MIN_STALE_AGE`).
+    /// Data restored from the cache with no known age counts as old. Drives
+    /// `ui::main_screen`'s stale banner and the tray tooltip's "(stale)".
+    pub fn is_stale(&self) -> bool {
+        let interval = refresh_interval(
+            &self.config.weather_provider,
+            self.config.refresh_interval_secs,
+        );
+        let stale_age = (interval * STALE_AFTER_REFRESHES).max(MIN_STALE_AGE);
+        self.weather.data().is_some()
+            && self.consecutive_failures > 0
+            && self
+                .last_updated
+                .is_none_or(|updated| updated.elapsed() >= stale_age)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     RefreshRequested,
     Tick(#[allow(dead_code)] std::time::Instant),
     WeatherFetched(Result<ApiResponse, String>),
     ForecastFetched(Result<ForecastResponse, String>),
+    HourlyFetched(Result<HourlyForecast, String>),
     AlertsFetched(Result<Vec<WeatherAlert>, String>),
     /// Result of `detect_system_theme_task`, fired at boot and again on
     /// every `RefreshRequested`/`Tick` -- see that function's docs for why
@@ -185,6 +249,9 @@ pub enum Message {
     /// Applies to whatever Preferences window is currently open, if any --
     /// see `update`.
     LocationDetected(Result<LocationConfig, String>),
+    /// Result of `crate::weather_api::geocoding::search`, fired by
+    /// `Message::Preferences(preferences::Message::SearchLocationRequested)`.
+    LocationSearched(Result<Vec<GeocodeCandidate>, String>),
     /// Result of a single `get_weather()` call against the *currently-typed*
     /// provider/token/location, fired by
     /// `Message::Preferences(preferences::Message::TestConnectionRequested)`.
@@ -194,11 +261,13 @@ pub enum Message {
     ConnectionTested(Result<(), String>),
     /// Result of the async, off-UI-thread `AppConfig::get_api_token` read
     /// fired whenever a Preferences window opens (`OpenPreferences`, and
-    /// `boot`'s first-run path) -- see `preferences::State::from_config`'s
-    /// docs for why the read isn't done synchronously up front. Applies to
-    /// whatever Preferences window is currently open, if any; a no-op if
-    /// it's already been closed by the time this resolves.
-    ApiTokenLoaded(String),
+    /// `boot`'s first-run path) and whenever it switches to a keyed
+    /// provider it hasn't loaded a token for yet -- see
+    /// `preferences::State::from_config`'s docs for why the read isn't done
+    /// synchronously up front. Applies to whatever Preferences window is
+    /// currently open, if any; a no-op if it's already been closed by the
+    /// time this resolves.
+    ApiTokenLoaded(WeatherApiProvider, String),
     /// A location switcher pill was clicked (`ui::location_switcher`) --
     /// switches `config.current_location_index` and persists it
     /// immediately, independent of Preferences' Save/Cancel, then
@@ -208,6 +277,13 @@ pub enum Message {
     /// is in flight -- it belongs to a different place, so carrying it
     /// forward would misleadingly look current.
     LocationSwitched(usize),
+    /// A weather fetch for a location with no saved coordinates (or no
+    /// saved timezone) came back with the coordinates it resolved to
+    /// (`ApiResponse::coord`), and the timezone if it found one. Saved
+    /// onto every saved location still exactly equal to the one fetched --
+    /// none, if it was edited in the meantime -- so later fetches skip
+    /// geocoding (see `weather_api::geocoding::resolve`).
+    LocationResolved(LocationConfig, Coord),
 
     Preferences(preferences::Message),
 }
@@ -221,67 +297,182 @@ pub enum Message {
 /// `update()`'s own thread, freezing the whole UI (including button clicks)
 /// until that prompt is dismissed. Reading it inside the async block instead
 /// keeps it on iced's executor, off the UI thread.
-fn fetch_weather_task(config: &AppConfig) -> Task<Message> {
-    let provider_type = config.weather_provider.clone();
+///
+/// When the location has no saved coordinates yet, also reports the ones
+/// the provider resolved via `Message::LocationResolved` -- as it does a
+/// timezone looked up for saved coordinates that had none.
+fn fetch_weather_task(
+    config: &AppConfig,
+    cache: &ResponseCache,
+    client: &reqwest::Client,
+) -> Task<Message> {
+    let chain = config.provider_chain();
     let location = config.current_location();
+    let cache = cache.clone();
+    let unresolved =
+        (location.coordinates().is_none() || location.timezone.is_none()).then(|| location.clone());
     let config = config.clone();
+    let client = client.clone();
 
     Task::perform(
         async move {
-            let token = config.get_api_token().ok();
-            let provider =
-                WeatherProviderFactory::create_provider(&provider_type, token, config.language)?;
+            let provider = CachingProvider::new(
+                WeatherProviderFactory::create_chain(
+                    &chain,
+                    |provider| config.get_api_token(provider).ok(),
+                    config.language,
+                    config.retry,
+                    &client,
+                    &config.endpoints,
+                )?,
+                cache,
+            );
             provider
                 .get_weather(&location)
                 .await
-                .map_err(|e| format!("{:?}", e))
+                .map_err(|e| e.to_string())
         },
-        Message::WeatherFetched,
+        std::convert::identity,
     )
+    .then(move |result: Result<ApiResponse, String>| {
+        let resolved = unresolved
+            .clone()
+            .zip(
+                result
+                    .as_ref()
+                    .ok()
+                    .and_then(|weather| weather.coord.clone()),
+            )
+            .filter(|(location, coord)| {
+                location.coordinates().is_none() || coord.timezone.is_some()
+            });
+        let fetched = Task::done(Message::WeatherFetched(result));
+        match resolved {
+            Some((location, coord)) => Task::batch([
+                Task::done(Message::LocationResolved(location, coord)),
+                fetched,
+            ]),
+            None => fetched,
+        }
+    })
 }
 
 /// Builds a `Task` that fetches a forecast for the active provider/location.
 /// See `fetch_weather_task`'s docs for why the token is read inside the
 /// async block rather than before it.
-fn fetch_forecast_task(config: &AppConfig) -> Task<Message> {
-    let provider_type = config.weather_provider.clone();
+fn fetch_forecast_task(
+    config: &AppConfig,
+    cache: &ResponseCache,
+    client: &reqwest::Client,
+) -> Task<Message> {
+    let chain = config.provider_chain();
     let location = config.current_location();
+    let cache = cache.clone();
     let config = config.clone();
+    let client = client.clone();
 
     Task::perform(
         async move {
-            let token = config.get_api_token().ok();
-            let provider =
-                WeatherProviderFactory::create_provider(&provider_type, token, config.language)?;
+            let provider = CachingProvider::new(
+                WeatherProviderFactory::create_chain(
+                    &chain,
+                    |provider| config.get_api_token(provider).ok(),
+                    config.language,
+                    config.retry,
+                    &client,
+                    &config.endpoints,
+                )?,
+                cache,
+            );
             provider
                 .get_forecast(&location)
                 .await
-                .map_err(|e| format!("{:?}", e))
+                .map_err(|e| e.to_string())
         },
         Message::ForecastFetched,
     )
 }
 
+/// Builds a `Task` that fetches the hourly strip's forecast. See
+/// `fetch_weather_task`'s docs for why the token is read inside the async
+/// block rather than before it.
+fn fetch_hourly_task(config: &AppConfig, client: &reqwest::Client) -> Task<Message> {
+    let chain = config.provider_chain();
+    let location = config.current_location();
+    let config = config.clone();
+    let client = client.clone();
+
+    Task::perform(
+        async move {
+            let provider = WeatherProviderFactory::create_chain(
+                &chain,
+                |provider| config.get_api_token(provider).ok(),
+                config.language,
+                config.retry,
+                &client,
+                &config.endpoints,
+            )?;
+            provider
+                .get_hourly(&location)
+                .await
+                .map_err(|e| e.to_string())
+        },
+        Message::HourlyFetched,
+    )
+}
+
 /// Builds a `Task` that fetches active weather alerts.
-fn fetch_alerts_task(config: &AppConfig) -> Task<Message> {
-    let provider_type = config.weather_provider.clone();
+fn fetch_alerts_task(
+    config: &AppConfig,
+    cache: &ResponseCache,
+    client: &reqwest::Client,
+) -> Task<Message> {
+    let chain = config.provider_chain();
     let location = config.current_location();
+    let cache = cache.clone();
     let config = config.clone();
+    let client = client.clone();
 
     Task::perform(
         async move {
-            let token = config.get_api_token().ok();
-            let provider =
-                WeatherProviderFactory::create_provider(&provider_type, token, config.language)?;
+            let provider = CachingProvider::new(
+                WeatherProviderFactory::create_chain(
+                    &chain,
+                    |provider| config.get_api_token(provider).ok(),
+                    config.language,
+                    config.retry,
+                    &client,
+                    &config.endpoints,
+                )?,
+                cache,
+            );
             provider
                 .get_alerts(&location)
                 .await
-                .map_err(|e| format!("{:?}", e))
+                .map_err(|e| e.to_string())
         },
         Message::AlertsFetched,
     )
 }
 
+/// The shared client for `settings` (see `weather_api::http`). Preferences
+/// won't save settings it can't build, but a hand-edited config file can
+/// still name a missing CA bundle -- logged, and the defaults used, rather
+/// than failing every request.
+fn http_client(settings: &HttpSettings) -> reqwest::Client {
+    settings.client().unwrap_or_else(|e| {
+        log::warn!("{e} -- using the default network settings");
+        HttpSettings::default().client().unwrap_or_default()
+    })
+}
+
+/// A client for Preferences' *draft* network settings, so Detect, Search,
+/// and Verify API go through a proxy that's been typed in but not saved
+/// yet -- same "currently-typed" idea as the connection test's token.
+fn draft_http_client(prefs_state: &preferences::State) -> Result<reqwest::Client, String> {
+    prefs_state.http_settings()?.client()
+}
+
 /// Builds a `Task` that polls the OS's current light/dark preference off
 /// the UI thread. `dark_light::detect()` is a blocking call (on Linux, a
 /// D-Bus round trip to the XDG Desktop Portal, bounded by the crate's own
@@ -308,15 +499,18 @@ fn detect_system_theme_task() -> Task<Message> {
     )
 }
 
-/// Builds a `Task` that reads the API token off the UI thread and reports
-/// it back via `Message::ApiTokenLoaded` -- see
+/// Builds a `Task` that reads `provider`'s API token off the UI thread and
+/// reports it back via `Message::ApiTokenLoaded` -- see
 /// `preferences::State::from_config`'s docs for why Preferences opens with
 /// an empty token field rather than reading it synchronously up front.
-fn fetch_api_token_task(config: &AppConfig) -> Task<Message> {
+fn fetch_api_token_task(config: &AppConfig, provider: WeatherApiProvider) -> Task<Message> {
     let config = config.clone();
     Task::perform(
-        async move { config.get_api_token().unwrap_or_default() },
-        Message::ApiTokenLoaded,
+        async move {
+            let token = config.get_api_token(&provider).unwrap_or_default();
+            (provider, token)
+        },
+        |(provider, token)| Message::ApiTokenLoaded(provider, token),
     )
 }
 
@@ -345,7 +539,7 @@ fn bring_window_to_front(id: window::Id) -> Task<Message> {
     })
 }
 
-/// Drops any last-known-good weather/forecast/alerts data rather than
+/// Drops any last-known-good weather/forecast/hourly/alerts data rather than
 /// letting it carry forward through the next fetch's `Refreshing` state --
 /// for use whenever what's about to be fetched is for a *different place*
 /// than what's currently displayed (a location switch, or a Preferences
@@ -353,13 +547,47 @@ fn bring_window_to_front(id: window::Id) -> Task<Message> {
 /// data while the new fetch is in flight would misleadingly look like a
 /// same-place refresh instead of a different place's page still loading.
 /// Ordinary same-place refreshes (`Message::RefreshRequested`/`Tick`) don't
-/// call this -- they're the one case `Refreshing` exists for.
+/// call this -- they're the one case `Refreshing` exists for. The old
+/// place's failures go too, so the new one doesn't start out stale.
 fn discard_stale_location_data(state: &mut AppState) {
     state.weather = WeatherStatus::Loading;
     state.forecast = ForecastStatus::Loading;
+    state.hourly = None;
     state.alerts = vec![];
     state.selected_forecast_day = None;
     state.last_updated = None;
+    state.consecutive_failures = 0;
+    state.last_error = None;
+    sync_tray_display(state);
+}
+
+/// Shows whatever `state.cache` last saved for the current location, as
+/// `Refreshing` -- the fetch that's about to go out replaces it, or, if it
+/// fails (offline), leaves it showing rather than an error. Only for a
+/// screen with nothing of this location's on it yet: at boot, and right
+/// after `discard_stale_location_data`. "Updated Xm ago" counts from when
+/// the saved data was actually fetched.
+fn show_cached(state: &mut AppState) {
+    let location = state.config.current_location();
+    let use_fahrenheit = state.config.use_fahrenheit;
+    if let Some(cached) = state.cache.load::<ApiResponse>(&location, Entry::Weather) {
+        note_weather_transitions(&mut state.value_tracker, &cached.data, use_fahrenheit);
+        state.last_updated = Instant::now().checked_sub(cached.age());
+        state.weather = WeatherStatus::Refreshing(cached.data);
+    }
+    if let Some(cached) = state
+        .cache
+        .load::<ForecastResponse>(&location, Entry::Forecast)
+    {
+        note_forecast_transitions(&mut state.value_tracker, &cached.data, use_fahrenheit);
+        state.forecast = ForecastStatus::Refreshing(cached.data);
+    }
+    if let Some(cached) = state
+        .cache
+        .load::<Vec<WeatherAlert>>(&location, Entry::Alerts)
+    {
+        state.alerts = cache::unexpired(cached.data);
+    }
     sync_tray_display(state);
 }
 
@@ -475,7 +703,7 @@ fn build_tray_icon() -> Option<TrayIcon> {
 /// glyph (`⚠`) `ui/main_screen.rs::alerts_view` already uses to pick its
 /// danger styling, so "severe" means the same thing in the tray as it does
 /// in the main window.
-fn has_severe_alert(alerts: &[WeatherAlert]) -> bool {
+pub(crate) fn has_severe_alert(alerts: &[WeatherAlert]) -> bool {
     alerts.iter().any(|alert| {
         matches!(
             alert.severity,
@@ -489,18 +717,21 @@ fn has_severe_alert(alerts: &[WeatherAlert]) -> bool {
 /// into `state.tray_icon` and make the actual OS call) purely so the text
 /// itself is unit-testable without needing a real `TrayIcon`. Prefixed with
 /// a `⚠` badge whenever `alerts` contains an active Severe/Extreme alert,
-/// so something urgent is visible without opening the app.
-fn tray_tooltip_text(
+/// so something urgent is visible without opening the app, and suffixed
+/// "(stale)" when `stale` (see `AppState::is_stale`).
+pub(crate) fn tray_tooltip_text(
     weather: &WeatherStatus,
     use_fahrenheit: bool,
     alerts: &[WeatherAlert],
+    stale: bool,
 ) -> String {
     let badge = if has_severe_alert(alerts) { "⚠ " } else { "" };
+    let suffix = if stale { " (stale)" } else { "" };
     match weather {
         WeatherStatus::Loaded(response) | WeatherStatus::Refreshing(response) => {
             match response.weather.first() {
                 Some(condition) => format!(
-                    "{badge}Weather Wizard — {:.0}{} {}",
+                    "{badge}Weather Wizard — {:.0}{} {}{suffix}",
                     celsius_to_display(response.main.temp, use_fahrenheit),
                     unit_symbol(use_fahrenheit),
                     condition.description
@@ -521,7 +752,7 @@ fn tray_tooltip_text(
 /// title doesn't crowd the icon with nothing useful to say. Still gets the
 /// `⚠` severe-alert badge even while loading/erroring, since that's exactly
 /// when a user might otherwise have no reason to open the app and notice.
-fn tray_title_text(
+pub(crate) fn tray_title_text(
     weather: &WeatherStatus,
     use_fahrenheit: bool,
     alerts: &[WeatherAlert],
@@ -569,7 +800,12 @@ fn sync_tray_display(state: &AppState) {
     let Some(tray_icon) = &state.tray_icon else {
         return;
     };
-    let tooltip = tray_tooltip_text(&state.weather, state.config.use_fahrenheit, &state.alerts);
+    let tooltip = tray_tooltip_text(
+        &state.weather,
+        state.config.use_fahrenheit,
+        &state.alerts,
+        state.is_stale(),
+    );
     if let Err(e) = tray_icon.set_tooltip(Some(tooltip)) {
         log::warn!("Failed to update tray icon tooltip: {e}");
     }
@@ -609,6 +845,13 @@ pub fn boot() -> (AppState, Task<Message>) {
     let config_manager = ConfigManager::new().expect("Failed to create config manager");
     let is_first_run = !config_manager.config_exists();
     let config = config_manager.load_config();
+    let cache = ResponseCache::new(config_manager.cache_dir());
+    let client = http_client(&config.http);
+    // Hand-edited only, so nowhere to show this but the log; the requests
+    // themselves then fail as unreachable.
+    if let Err(e) = config.endpoints.validate() {
+        log::warn!("{e}");
+    }
 
     let (main_window, main_open_task) = window::open(window::Settings {
         size: Size::new(DEFAULT_WINDOW_WIDTH, DEFAULT_WINDOW_HEIGHT),
@@ -635,26 +878,35 @@ pub fn boot() -> (AppState, Task<Message>) {
         (
             Some(id),
             Some(prefs_state),
-            Task::batch([prefs_open_task.discard(), fetch_api_token_task(&config)]),
+            Task::batch([
+                prefs_open_task.discard(),
+                fetch_api_token_task(&config, config.weather_provider.clone()),
+            ]),
         )
     } else {
         (
             None,
             None,
             Task::batch([
-                fetch_weather_task(&config),
-                fetch_forecast_task(&config),
-                fetch_alerts_task(&config),
+                fetch_weather_task(&config, &cache, &client),
+                fetch_forecast_task(&config, &cache, &client),
+                fetch_hourly_task(&config, &client),
+                fetch_alerts_task(&config, &cache, &client),
             ]),
         )
     };
 
-    let state = AppState {
+    let mut state = AppState {
         weather: WeatherStatus::Loading,
         forecast: ForecastStatus::Loading,
+        hourly: None,
         alerts: vec![],
+        cache,
+        http_client: client,
         system_theme: Theme::Light,
         last_updated: None,
+        consecutive_failures: 0,
+        last_error: None,
         value_tracker: transition::ValueTracker::default(),
         selected_forecast_day: None,
         main_window,
@@ -666,6 +918,9 @@ pub fn boot() -> (AppState, Task<Message>) {
         config_manager,
         tray_icon: build_tray_icon(),
     };
+    if !is_first_run {
+        show_cached(&mut state);
+    }
 
     (
         state,
@@ -696,9 +951,10 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
                 other => other,
             };
             Task::batch([
-                fetch_weather_task(&state.config),
-                fetch_forecast_task(&state.config),
-                fetch_alerts_task(&state.config),
+                fetch_weather_task(&state.config, &state.cache, &state.http_client),
+                fetch_forecast_task(&state.config, &state.cache, &state.http_client),
+                fetch_hourly_task(&state.config, &state.http_client),
+                fetch_alerts_task(&state.config, &state.cache, &state.http_client),
                 detect_system_theme_task(),
             ])
         }
@@ -712,12 +968,29 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
                 log::warn!("Failed to persist location switch: {}", e);
             }
             discard_stale_location_data(state);
+            show_cached(state);
             Task::batch([
-                fetch_weather_task(&state.config),
-                fetch_forecast_task(&state.config),
-                fetch_alerts_task(&state.config),
+                fetch_weather_task(&state.config, &state.cache, &state.http_client),
+                fetch_forecast_task(&state.config, &state.cache, &state.http_client),
+                fetch_hourly_task(&state.config, &state.http_client),
+                fetch_alerts_task(&state.config, &state.cache, &state.http_client),
             ])
         }
+        Message::LocationResolved(location, coord) => {
+            let mut resolved_any = false;
+            for saved in &mut state.config.locations {
+                if saved.location == location {
+                    saved.location.lat = Some(coord.lat);
+                    saved.location.lon = Some(coord.lon);
+                    saved.location.timezone = coord.timezone.clone();
+                    resolved_any = true;
+                }
+            }
+            if resolved_any && let Err(e) = state.config_manager.save_config(&state.config) {
+                log::warn!("Failed to persist resolved coordinates: {}", e);
+            }
+            Task::none()
+        }
         Message::SystemThemeDetected(theme) => {
             state.system_theme = theme;
             Task::none()
@@ -730,13 +1003,18 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             );
             state.weather = WeatherStatus::Loaded(response);
             state.last_updated = Some(Instant::now());
+            state.consecutive_failures = 0;
+            state.last_error = None;
             sync_tray_display(state);
             Task::none()
         }
         Message::WeatherFetched(Err(error)) => {
+            state.consecutive_failures += 1;
+            state.last_error = Some(error.clone());
             // A failed background refresh shouldn't disrupt a screen that
             // already has good data -- only surface the error if we had
-            // nothing to show in the first place.
+            // nothing to show in the first place. Once it's been failing
+            // long enough, `AppState::is_stale` says so instead.
             state.weather = match std::mem::replace(&mut state.weather, WeatherStatus::Loading) {
                 WeatherStatus::Refreshing(data) => {
                     log::warn!("Background weather refresh failed, keeping last data: {error}");
@@ -782,6 +1060,14 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             };
             Task::none()
         }
+        Message::HourlyFetched(Ok(hourly)) => {
+            state.hourly = Some(hourly);
+            Task::none()
+        }
+        Message::HourlyFetched(Err(error)) => {
+            log::warn!("Hourly forecast fetch failed: {error}");
+            Task::none()
+        }
         Message::AlertsFetched(Ok(alerts)) => {
             state.alerts = alerts;
             sync_tray_display(state);
@@ -803,7 +1089,10 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             state.prefs_state = Some(preferences::State::from_config(&state.config));
             let (id, open_task) = window::open(preferences_window_settings());
             state.prefs_window = Some(id);
-            Task::batch([open_task.discard(), fetch_api_token_task(&state.config)])
+            Task::batch([
+                open_task.discard(),
+                fetch_api_token_task(&state.config, state.config.weather_provider.clone()),
+            ])
         }
         Message::OpenAbout => {
             if let Some(id) = state.about_window {
@@ -911,6 +1200,7 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
                 return Task::none();
             };
             let previous_location = state.config.current_location();
+            let previous_http = state.config.http.clone();
             if let Err(e) = prefs_state.apply_to(&mut state.config) {
                 // A keychain write failed (locked keychain, no Secret
                 // Service running, etc.) -- put the form state back and
@@ -925,6 +1215,9 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             } else {
                 log::info!("Configuration saved successfully");
             }
+            if state.config.http != previous_http {
+                state.http_client = http_client(&state.config.http);
+            }
             // Editing the active location's own fields (or removing it,
             // which falls back to a different entry -- see
             // `preferences::State::apply_to`) changes what "current
@@ -934,6 +1227,7 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             // `Refreshing` while the new place's fetch is in flight.
             if state.config.current_location() != previous_location {
                 discard_stale_location_data(state);
+                show_cached(state);
             }
             // Independent of location: `use_fahrenheit` might have just
             // changed with no location change at all, and the tooltip
@@ -969,8 +1263,10 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             };
             prefs_state.is_detecting_location = true;
             prefs_state.location_detection_error = None;
+            let client = draft_http_client(prefs_state);
+            let endpoints = state.config.endpoints.clone();
             Task::perform(
-                crate::geolocation::detect_location(),
+                async move { crate::geolocation::detect_location(client?, endpoints).await },
                 Message::LocationDetected,
             )
         }
@@ -992,9 +1288,19 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
                         .locations
                         .get_mut(prefs_state.selected_location_index)
                     {
+                        // A coordinate-only entry takes just the position.
+                        if entry.by_coordinates
+                            && let Some((lat, lon)) = location.coordinates()
+                        {
+                            entry.lat_input = lat.to_string();
+                            entry.lon_input = lon.to_string();
+                        }
                         entry.city = location.city;
                         entry.state = location.state;
                         entry.country = location.country;
+                        entry.lat = location.lat;
+                        entry.lon = location.lon;
+                        entry.timezone = location.timezone;
                     }
                 }
                 Err(e) => {
@@ -1007,14 +1313,85 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             }
             Task::none()
         }
-        Message::ApiTokenLoaded(token) => {
+        Message::Preferences(preferences::Message::SearchLocationRequested) => {
+            let Some(prefs_state) = state.prefs_state.as_mut() else {
+                return Task::none();
+            };
+            let Some(entry) = prefs_state
+                .locations
+                .get(prefs_state.selected_location_index)
+            else {
+                return Task::none();
+            };
+            let query =
+                LocationConfig::new(entry.city.clone(), String::new(), entry.country.clone());
+            prefs_state.reset_location_lookups();
+            prefs_state.is_searching_location = true;
+            let client = draft_http_client(prefs_state);
+            let endpoints = state.config.endpoints.clone();
+            Task::perform(
+                async move {
+                    crate::weather_api::geocoding::search(&client?, endpoints.geocoding(), &query)
+                        .await
+                        .map_err(|e| e.to_string())
+                },
+                Message::LocationSearched,
+            )
+        }
+        Message::LocationSearched(result) => {
+            // Preferences may be closed, or a different entry selected
+            // (which clears `is_searching_location`), by the time the
+            // search resolves -- the results belong to neither.
+            let Some(prefs_state) = state
+                .prefs_state
+                .as_mut()
+                .filter(|prefs_state| prefs_state.is_searching_location)
+            else {
+                return Task::none();
+            };
+            prefs_state.is_searching_location = false;
+            match result {
+                Ok(candidates) if candidates.is_empty() => {
+                    prefs_state.location_search_error = Some(
+                        "No places found by that name -- check the spelling and country."
+                            .to_string(),
+                    );
+                }
+                Ok(candidates) => prefs_state.location_candidates = candidates,
+                Err(e) => {
+                    log::warn!("Location search failed: {e}");
+                    prefs_state.location_search_error = Some(
+                        "Couldn't search for that place -- check your connection.".to_string(),
+                    );
+                }
+            }
+            Task::none()
+        }
+        Message::ApiTokenLoaded(provider, token) => {
             // Same reasoning as `LocationDetected`: Preferences may already
-            // be closed by the time this async keychain read resolves.
+            // be closed by the time this async keychain read resolves. Never
+            // clobbers a token the user already started typing.
             if let Some(prefs_state) = state.prefs_state.as_mut() {
-                prefs_state.token_input = token;
+                prefs_state.token_inputs.entry(provider).or_insert(token);
             }
             Task::none()
         }
+        Message::Preferences(preferences::Message::ProviderSelected(provider)) => {
+            let Some(prefs_state) = state.prefs_state.as_mut() else {
+                return Task::none();
+            };
+            let needs_token =
+                provider.requires_api_token() && !prefs_state.token_inputs.contains_key(&provider);
+            preferences::update(
+                prefs_state,
+                preferences::Message::ProviderSelected(provider.clone()),
+            );
+            if needs_token {
+                fetch_api_token_task(&state.config, provider)
+            } else {
+                Task::none()
+            }
+        }
         Message::Preferences(preferences::Message::TestConnectionRequested) => {
             let Some(prefs_state) = state.prefs_state.as_mut() else {
                 return Task::none();
@@ -1023,28 +1400,35 @@ pub fn update(state: &mut AppState, message: Message) -> Task<Message> {
             prefs_state.connection_test_result = None;
 
             let provider_type = prefs_state.provider.clone();
-            let token =
-                (!prefs_state.token_input.is_empty()).then(|| prefs_state.token_input.clone());
+            let token = Some(prefs_state.token_input().to_string()).filter(|t| !t.is_empty());
             // Tests against whichever entry is currently selected in the
             // Locations tab strip -- "currently-typed", same philosophy as
             // provider/token above, just per-entry now.
             let selected_location = &prefs_state.locations[prefs_state.selected_location_index];
-            let location = LocationConfig {
-                city: selected_location.city.clone(),
-                state: selected_location.state.clone(),
-                country: selected_location.country.clone(),
-            };
+            let location = SavedLocation::from(selected_location).location;
             let language = prefs_state.language;
+            let retry = state.config.retry;
+            let client = draft_http_client(prefs_state);
+            let endpoints = state.config.endpoints.clone();
 
             Task::perform(
                 async move {
-                    let provider =
-                        WeatherProviderFactory::create_provider(&provider_type, token, language)?;
+                    // Just the selected provider, never the fallback chain:
+                    // a fallback answering would hide exactly the bad key
+                    // this button exists to catch.
+                    let provider = WeatherProviderFactory::create_provider(
+                        &provider_type,
+                        token,
+                        language,
+                        retry,
+                        &client?,
+                        &endpoints,
+                    )?;
                     provider
                         .get_weather(&location)
                         .await
                         .map(|_| ())
-                        .map_err(|e| format!("{:?}", e))
+                        .map_err(|e| e.to_string())
                 },
                 Message::ConnectionTested,
             )
@@ -1086,23 +1470,58 @@ pub fn view(state: &AppState, window_id: window::Id) -> Element<'_, Message> {
     Space::new().into()
 }
 
-pub fn subscription(state: &AppState) -> Subscription<Message> {
-    let refresh_interval = match state.config.refresh_interval_secs {
+/// How often to refresh with `provider` as the primary: `configured`
+/// seconds (the user's `refresh_interval_secs`) if set, otherwise the
+/// provider's default -- and never under Google's floor either way. Shared
+/// with the CLI's `--watch`, so a status bar polls no harder than the app.
+pub(crate) fn refresh_interval(provider: &WeatherApiProvider, configured: Option<u64>) -> Duration {
+    match configured {
         Some(secs) => {
             let duration = Duration::from_secs(secs);
-            if state.config.weather_provider == WeatherApiProvider::GoogleWeather {
+            if *provider == WeatherApiProvider::GoogleWeather {
                 duration.max(GOOGLE_WEATHER_REFRESH_INTERVAL)
             } else {
                 duration
             }
         }
-        None => match state.config.weather_provider {
+        None => match provider {
             WeatherApiProvider::GoogleWeather => GOOGLE_WEATHER_REFRESH_INTERVAL,
+            WeatherApiProvider::OpenMeteo
+            | WeatherApiProvider::NationalWeatherService
+            | WeatherApiProvider::MetNorway => KEYLESS_REFRESH_INTERVAL,
             WeatherApiProvider::OpenWeather => AUTO_REFRESH_INTERVAL,
         },
-    };
+    }
+}
+
+/// How long to wait before the next automatic refresh: `interval`, doubled
+/// for each consecutive failure up to `MAX_REFRESH_BACKOFF`, so an outage
+/// isn't met with a request every interval. Shared with the CLI's
+/// `--watch`.
+pub(crate) fn backoff_interval(interval: Duration, consecutive_failures: u32) -> Duration {
+    let backed_off = interval.saturating_mul(2u32.saturating_pow(consecutive_failures));
+    backed_off.min(MAX_REFRESH_BACKOFF.max(interval))
+}
+
+/// The period of `subscription`'s `Tick`: the refresh interval, backed off
+/// for the current location's failures. A new period is a new timer to
+/// iced, so each failure pushes the next `Tick` out, and a success -- or
+/// switching to another location, see `discard_stale_location_data` --
+/// brings the regular one back. A manual Refresh still fetches straight
+/// away.
+fn tick_interval(state: &AppState) -> Duration {
+    backoff_interval(
+        refresh_interval(
+            &state.config.weather_provider,
+            state.config.refresh_interval_secs,
+        ),
+        state.consecutive_failures,
+    )
+}
+
+pub fn subscription(state: &AppState) -> Subscription<Message> {
     Subscription::batch([
-        iced::time::every(refresh_interval).map(Message::Tick),
+        iced::time::every(tick_interval(state)).map(Message::Tick),
         iced::time::every(ANIMATION_TICK_INTERVAL).map(|_| Message::AnimationTick),
         window::close_requests().map(Message::WindowCloseRequested),
     ])
@@ -1190,9 +1609,14 @@ mod tests {
                 config_manager,
                 weather: WeatherStatus::Loading,
                 forecast: ForecastStatus::Loading,
+                hourly: None,
                 alerts: vec![],
+                cache: ResponseCache::new(path.with_extension("cache")),
+                http_client: reqwest::Client::new(),
                 system_theme: Theme::Light,
                 last_updated: None,
+                consecutive_failures: 0,
+                last_error: None,
                 value_tracker: transition::ValueTracker::default(),
                 selected_forecast_day: None,
                 main_window: window::Id::unique(),
@@ -1233,6 +1657,8 @@ mod tests {
             },
             timezone: 0,
             name: name.to_string(),
+            coord: None,
+            provider: None,
         }
     }
 
@@ -1255,6 +1681,7 @@ mod tests {
                     pop: 0.1,
                 })
                 .collect(),
+            provider: None,
         }
     }
 
@@ -1268,6 +1695,7 @@ mod tests {
                 city: "Chicago".to_string(),
                 state: "IL".to_string(),
                 country: "US".to_string(),
+                ..Default::default()
             },
         });
         config
@@ -1335,6 +1763,39 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn test_location_switched_shows_cached_data_while_fetching() {
+        let (mut state, path) = test_state(two_location_config());
+        let chicago = state.config.locations[1].location.clone();
+        state
+            .cache
+            .store(&chicago, Entry::Weather, &sample_weather("Chicago"))
+            .unwrap();
+        state
+            .cache
+            .store(&chicago, Entry::Forecast, &sample_forecast(2))
+            .unwrap();
+
+        let _ = update(&mut state, Message::LocationSwitched(1));
+
+        match &state.weather {
+            WeatherStatus::Refreshing(data) => assert_eq!(data.name, "Chicago"),
+            other => panic!("expected Refreshing, got {other:?}"),
+        }
+        assert!(matches!(state.forecast, ForecastStatus::Refreshing(_)));
+        assert!(state.last_updated.is_some());
+
+        // A failed fetch (offline) keeps showing it.
+        let _ = update(
+            &mut state,
+            Message::WeatherFetched(Err("offline".to_string())),
+        );
+        assert!(matches!(state.weather, WeatherStatus::Loaded(_)));
+
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_dir_all(path.with_extension("cache"));
+    }
+
     #[test]
     fn test_location_switched_ignores_out_of_range_and_same_index() {
         let (mut state, path) = test_state(two_location_config());
@@ -1353,6 +1814,96 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn test_location_resolved_saves_coordinates_only_onto_unchanged_location() {
+        let (mut state, path) = test_state(two_location_config());
+        let fetched = state.config.current_location();
+        let coord = Coord {
+            lat: 40.6936,
+            lon: -89.589,
+            timezone: Some("America/Chicago".to_string()),
+        };
+
+        let _ = update(
+            &mut state,
+            Message::LocationResolved(fetched.clone(), coord.clone()),
+        );
+        let home = &state.config.locations[0].location;
+        assert_eq!(home.coordinates(), Some((40.6936, -89.589)));
+        assert_eq!(home.timezone.as_deref(), Some("America/Chicago"));
+        assert_eq!(state.config.locations[1].location.coordinates(), None);
+        let saved = std::fs::read_to_string(&path).unwrap();
+        assert!(saved.contains("\"lat\": 40.6936"));
+
+        // A location edited while the fetch was in flight no longer matches
+        // what was fetched, so it keeps no coordinates.
+        let (mut state, path) = test_state(two_location_config());
+        state.config.locations[0].location.city = "Peoria Heights".to_string();
+        let _ = update(&mut state, Message::LocationResolved(fetched, coord));
+        assert_eq!(state.config.locations[0].location.coordinates(), None);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_location_search_lists_candidates_and_picking_one_pins_coordinates() {
+        let (mut state, path) = test_state(AppConfig::default());
+        state.prefs_state = Some(preferences::State::from_config(&state.config));
+        let candidate = |admin1: &str, lat: f64| GeocodeCandidate {
+            name: "Peoria".to_string(),
+            admin1: Some(admin1.to_string()),
+            country_code: "US".to_string(),
+            lat,
+            lon: -89.589,
+            timezone: None,
+        };
+
+        let _ = update(
+            &mut state,
+            Message::Preferences(preferences::Message::SearchLocationRequested),
+        );
+        let _ = update(
+            &mut state,
+            Message::LocationSearched(Ok(vec![
+                candidate("Arizona", 33.5806),
+                candidate("Illinois", 40.6936),
+            ])),
+        );
+        let _ = update(
+            &mut state,
+            Message::Preferences(preferences::Message::LocationCandidatePicked(1)),
+        );
+        let prefs_state = state.prefs_state.as_ref().unwrap();
+        let entry = &prefs_state.locations[0];
+        assert_eq!(entry.state, "Illinois");
+        assert_eq!(entry.lat, Some(40.6936));
+        assert!(prefs_state.location_candidates.is_empty());
+
+        // Results arriving after the user moved to another entry are dropped.
+        let _ = update(
+            &mut state,
+            Message::Preferences(preferences::Message::SearchLocationRequested),
+        );
+        let _ = update(
+            &mut state,
+            Message::Preferences(preferences::Message::AddLocationRequested),
+        );
+        let _ = update(
+            &mut state,
+            Message::LocationSearched(Ok(vec![candidate("Arizona", 33.5806)])),
+        );
+        assert!(
+            state
+                .prefs_state
+                .as_ref()
+                .unwrap()
+                .location_candidates
+                .is_empty()
+        );
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_refresh_requested_carries_forward_loaded_data_as_refreshing() {
         let (mut state, path) = test_state(AppConfig::default());
@@ -1420,6 +1971,98 @@ mod tests {
         let _ = std::fs::remove_file(&path);
     }
 
+    #[test]
+    fn test_repeated_failures_mark_old_data_stale_until_a_fetch_succeeds() {
+        let (mut state, _) = test_state(AppConfig::default());
+        state.weather = WeatherStatus::Refreshing(sample_weather("Peoria"));
+        state.last_updated = Some(Instant::now());
+
+        let _ = update(
+            &mut state,
+            Message::WeatherFetched(Err("offline".to_string())),
+        );
+        assert_eq!(state.consecutive_failures, 1);
+        assert_eq!(state.last_error.as_deref(), Some("offline"));
+        // Failing, but the data is still recent.
+        assert!(!state.is_stale());
+
+        state.last_updated = Instant::now().checked_sub(Duration::from_secs(3600));
+        let _ = update(&mut state, Message::RefreshRequested);
+        let _ = update(
+            &mut state,
+            Message::WeatherFetched(Err("still offline".to_string())),
+        );
+        assert_eq!(state.consecutive_failures, 2);
+        assert_eq!(state.last_error.as_deref(), Some("still offline"));
+        assert!(state.is_stale());
+
+        let _ = update(
+            &mut state,
+            Message::WeatherFetched(Ok(sample_weather("Peoria"))),
+        );
+        assert_eq!(state.consecutive_failures, 0);
+        assert_eq!(state.last_error, None);
+        assert!(!state.is_stale());
+    }
+
+    #[test]
+    fn test_location_switch_clears_the_previous_locations_failures() {
+        let (mut state, path) = test_state(two_location_config());
+        state.weather = WeatherStatus::Loaded(sample_weather("Peoria"));
+        state.last_updated = Instant::now().checked_sub(Duration::from_secs(3600));
+        for _ in 0..3 {
+            let _ = update(&mut state, Message::RefreshRequested);
+            let _ = update(
+                &mut state,
+                Message::WeatherFetched(Err("offline".to_string())),
+            );
+        }
+        assert!(state.is_stale());
+
+        let _ = update(&mut state, Message::LocationSwitched(1));
+        assert_eq!(state.consecutive_failures, 0);
+        assert_eq!(state.last_error, None);
+        // Whatever the cache restores for the new location isn't stale
+        // until its own refreshes fail.
+        state.weather = WeatherStatus::Refreshing(sample_weather("Chicago"));
+        state.last_updated = None;
+        assert!(!state.is_stale());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_location_switch_restores_the_regular_tick_interval() {
+        let (mut state, path) = test_state(two_location_config());
+        let regular = tick_interval(&state);
+        for _ in 0..6 {
+            let _ = update(&mut state, Message::RefreshRequested);
+            let _ = update(
+                &mut state,
+                Message::WeatherFetched(Err("offline".to_string())),
+            );
+        }
+        assert_eq!(tick_interval(&state), backoff_interval(regular, 6));
+        assert!(tick_interval(&state) > regular);
+
+        let _ = update(&mut state, Message::LocationSwitched(1));
+        assert_eq!(tick_interval(&state), regular);
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_backoff_interval_doubles_per_failure_up_to_the_cap() {
+        let interval = Duration::from_secs(30);
+        assert_eq!(backoff_interval(interval, 0), interval);
+        assert_eq!(backoff_interval(interval, 1), Duration::from_secs(60));
+        assert_eq!(backoff_interval(interval, 3), Duration::from_secs(240));
+        assert_eq!(backoff_interval(interval, 40), MAX_REFRESH_BACKOFF);
+        // An interval already past the cap is never shortened.
+        let hourly = Duration::from_secs(3600);
+        assert_eq!(backoff_interval(hourly, 2), hourly);
+    }
+
     #[test]
     fn test_forecast_fetched_clears_stale_selected_day_when_out_of_range() {
         let (mut state, path) = test_state(AppConfig::default());
@@ -1581,21 +2224,21 @@ mod tests {
     #[test]
     fn test_tray_tooltip_text_reflects_weather_status_and_units() {
         assert_eq!(
-            tray_tooltip_text(&WeatherStatus::Loading, false, &[]),
+            tray_tooltip_text(&WeatherStatus::Loading, false, &[], false),
             "Weather Wizard — Loading…"
         );
         assert_eq!(
-            tray_tooltip_text(&WeatherStatus::Error("boom".to_string()), false, &[]),
+            tray_tooltip_text(&WeatherStatus::Error("boom".to_string()), false, &[], false),
             "Weather Wizard — couldn't fetch weather"
         );
 
         let weather = WeatherStatus::Loaded(sample_weather("Peoria"));
         assert_eq!(
-            tray_tooltip_text(&weather, false, &[]),
+            tray_tooltip_text(&weather, false, &[], false),
             "Weather Wizard — 20°C clear sky"
         );
         assert_eq!(
-            tray_tooltip_text(&weather, true, &[]),
+            tray_tooltip_text(&weather, true, &[], false),
             "Weather Wizard — 68°F clear sky"
         );
 
@@ -1605,9 +2248,19 @@ mod tests {
         let mut refreshing_data = sample_weather("Peoria");
         refreshing_data.main.temp = 20.0;
         assert_eq!(
-            tray_tooltip_text(&WeatherStatus::Refreshing(refreshing_data), false, &[]),
+            tray_tooltip_text(
+                &WeatherStatus::Refreshing(refreshing_data),
+                false,
+                &[],
+                false
+            ),
             "Weather Wizard — 20°C clear sky"
         );
+
+        assert_eq!(
+            tray_tooltip_text(&weather, false, &[], true),
+            "Weather Wizard — 20°C clear sky (stale)"
+        );
     }
 
     #[test]
@@ -1663,11 +2316,11 @@ mod tests {
 
         let weather = WeatherStatus::Loaded(sample_weather("Peoria"));
         assert_eq!(
-            tray_tooltip_text(&weather, false, &severe),
+            tray_tooltip_text(&weather, false, &severe, false),
             "⚠ Weather Wizard — 20°C clear sky"
         );
         assert_eq!(
-            tray_tooltip_text(&weather, false, &minor),
+            tray_tooltip_text(&weather, false, &minor, false),
             "Weather Wizard — 20°C clear sky",
             "a Minor alert shouldn't trigger the severe badge"
         );
@@ -0,0 +1,314 @@
+//! # Status-Bar Output
+//!
+//! `--format waybar|i3blocks|polybar`: the weather the tray icon shows, in
+//! each bar's own custom-module protocol, so nobody has to write a `jq`
+//! wrapper around `--json`. The text itself comes from the tray's
+//! `tray_title_text`/`tray_tooltip_text`/`has_severe_alert` (see
+//! `src/app.rs`), so a bar and the tray never disagree about what to show.
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::app::{WeatherStatus, has_severe_alert, tray_title_text, tray_tooltip_text};
+use crate::weather_api::alerts::WeatherAlert;
+use crate::weather_api::forecast::ForecastResponse;
+use crate::weather_api::openweather_api::{WeatherSymbol, get_weather_symbol};
+
+/// Which bar's protocol `--format` prints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BarFormat {
+    /// One JSON object per update, for a `custom` module with
+    /// `"return-type": "json"`.
+    Waybar,
+    /// `full_text`, `short_text`, and (with a severe alert) `color` lines --
+    /// just `full_text` under `--watch`, for `interval=persist`.
+    I3blocks,
+    /// One line per update, with polybar's `%{F}` color tags around it while
+    /// a severe alert is active -- for a `custom/script` module (`tail =
+    /// true` under `--watch`).
+    Polybar,
+}
+
+/// The severe-alert color in i3blocks and polybar output -- Waybar gets a
+/// `severe-alert` class instead, for its own CSS to style.
+const ALERT_COLOR: &str = "#FF5555";
+
+/// What a bar shows when there's no temperature to show -- an empty text
+/// would hide the module entirely, and with it the reason.
+const UNAVAILABLE: &str = "n/a";
+
+/// Waybar's `return-type: json` schema.
+#[derive(Serialize)]
+struct WaybarOutput {
+    text: String,
+    tooltip: String,
+    class: Vec<&'static str>,
+    /// Today's chance of rain, 0-100, for Waybar's `format-icons`. Omitted
+    /// without a forecast.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<u8>,
+}
+
+/// The CSS class for a condition, e.g. `rain` -- Waybar only.
+fn condition_class(symbol: WeatherSymbol) -> &'static str {
+    match symbol {
+        WeatherSymbol::Clear => "clear",
+        WeatherSymbol::Clouds => "clouds",
+        WeatherSymbol::Rain => "rain",
+        WeatherSymbol::Drizzle => "drizzle",
+        WeatherSymbol::Thunderstorm => "thunderstorm",
+        WeatherSymbol::Snow => "snow",
+        WeatherSymbol::Mist => "mist",
+        WeatherSymbol::Smoke => "smoke",
+        WeatherSymbol::Haze => "haze",
+        WeatherSymbol::Dust => "dust",
+        WeatherSymbol::Fog => "fog",
+        WeatherSymbol::Sand => "sand",
+        WeatherSymbol::Ash => "ash",
+        WeatherSymbol::Squall => "squall",
+        WeatherSymbol::Tornado => "tornado",
+        WeatherSymbol::Default => "unknown",
+    }
+}
+
+/// The tray title plus the condition, e.g. "⚠ 72°F light rain" -- the
+/// i3blocks/polybar text, which has room the macOS menu bar doesn't.
+fn summary(weather: &WeatherStatus, use_fahrenheit: bool, alerts: &[WeatherAlert]) -> String {
+    let title = tray_title_text(weather, use_fahrenheit, alerts);
+    match (weather, title) {
+        (WeatherStatus::Loaded(response) | WeatherStatus::Refreshing(response), Some(title)) => {
+            match response.weather.first() {
+                Some(condition) => format!("{title} {}", condition.description),
+                None => title,
+            }
+        }
+        (_, Some(badge)) => format!("{badge} {UNAVAILABLE}"),
+        (_, None) => UNAVAILABLE.to_string(),
+    }
+}
+
+/// Renders one update in `format`. `weather` is `Loaded` after a successful
+/// fetch or `Error` after a failed one; `watching` picks each format's
+/// streaming variant (see `BarFormat`).
+pub fn render(
+    format: BarFormat,
+    weather: &WeatherStatus,
+    forecast: Option<&ForecastResponse>,
+    alerts: &[WeatherAlert],
+    use_fahrenheit: bool,
+    watching: bool,
+) -> String {
+    let severe = has_severe_alert(alerts);
+    match format {
+        BarFormat::Waybar => {
+            let mut class = Vec::new();
+            if severe {
+                class.push("severe-alert");
+            }
+            let mut tooltip = tray_tooltip_text(weather, use_fahrenheit, alerts, false);
+            match weather {
+                WeatherStatus::Loaded(response) | WeatherStatus::Refreshing(response) => {
+                    if let Some(condition) = response.weather.first() {
+                        class.push(condition_class(get_weather_symbol(&condition.main)));
+                    }
+                }
+                WeatherStatus::Error(message) => {
+                    class.push("error");
+                    tooltip = format!("{tooltip}\n{message}");
+                }
+                WeatherStatus::Loading => {}
+            }
+            let output = WaybarOutput {
+                text: tray_title_text(weather, use_fahrenheit, alerts)
+                    .unwrap_or_else(|| UNAVAILABLE.to_string()),
+                tooltip,
+                class,
+                percentage: forecast
+                    .and_then(|forecast| forecast.days.first())
+                    .map(|today| (today.pop * 100.0).round().clamp(0.0, 100.0) as u8),
+            };
+            // A struct of strings and numbers can't fail to serialize.
+            serde_json::to_string(&output).unwrap_or_default()
+        }
+        BarFormat::I3blocks => {
+            let full_text = summary(weather, use_fahrenheit, alerts);
+            if watching {
+                return full_text;
+            }
+            let short_text = tray_title_text(weather, use_fahrenheit, alerts)
+                .unwrap_or_else(|| UNAVAILABLE.to_string());
+            if severe {
+                format!("{full_text}\n{short_text}\n{ALERT_COLOR}")
+            } else {
+                format!("{full_text}\n{short_text}")
+            }
+        }
+        BarFormat::Polybar => {
+            let text = summary(weather, use_fahrenheit, alerts);
+            if severe {
+                format!("%{{F{ALERT_COLOR}}}{text}%{{F-}}")
+            } else {
+                text
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::alerts::AlertSeverity;
+    use crate::weather_api::forecast::ForecastDay;
+    use crate::weather_api::openweather_api::{ApiResponse, Main, Sys, Weather, Wind};
+
+    fn rainy() -> WeatherStatus {
+        WeatherStatus::Loaded(ApiResponse {
+            weather: vec![Weather {
+                main: "Rain".to_string(),
+                description: "light rain".to_string(),
+            }],
+            main: Main {
+                temp: 20.0,
+                feels_like: 19.0,
+                temp_min: 15.0,
+                temp_max: 25.0,
+                pressure: 1013,
+                humidity: 80,
+            },
+            wind: Wind {
+                speed: 3.0,
+                deg: 180,
+            },
+            visibility: 10_000,
+            sys: Sys {
+                sunrise: 0,
+                sunset: 0,
+            },
+            timezone: 0,
+            name: "Peoria".to_string(),
+            coord: None,
+            provider: None,
+        })
+    }
+
+    fn forecast_with_pop(pop: f64) -> ForecastResponse {
+        ForecastResponse {
+            location_name: "Peoria".to_string(),
+            days: vec![ForecastDay {
+                date: "2026-07-02".to_string(),
+                temp_min: 15.0,
+                temp_max: 25.0,
+                description: "light rain".to_string(),
+                symbol: WeatherSymbol::Rain,
+                feels_like: 19.0,
+                humidity: 80,
+                wind_speed: 3.0,
+                wind_deg: 180,
+                pressure: 1013,
+                visibility: 10_000,
+                pop,
+            }],
+            provider: None,
+        }
+    }
+
+    fn severe_alert() -> WeatherAlert {
+        WeatherAlert {
+            id: "1".to_string(),
+            title: "Severe Thunderstorm".to_string(),
+            description: String::new(),
+            event_type: String::new(),
+            severity: AlertSeverity::Severe,
+            start_time: 0,
+            end_time: 0,
+            urgency: String::new(),
+            certainty: String::new(),
+            area_name: String::new(),
+            instruction: vec![],
+            safety_recommendations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_waybar_matches_the_tray_and_classes_the_condition() {
+        let forecast = forecast_with_pop(0.64);
+        let line = render(
+            BarFormat::Waybar,
+            &rainy(),
+            Some(&forecast),
+            &[severe_alert()],
+            true,
+            false,
+        );
+        let json: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(json["text"], "⚠ 68°F");
+        assert_eq!(json["tooltip"], "⚠ Weather Wizard — 68°F light rain");
+        assert_eq!(json["class"], serde_json::json!(["severe-alert", "rain"]));
+        assert_eq!(json["percentage"], 64);
+
+        let error = render(
+            BarFormat::Waybar,
+            &WeatherStatus::Error("Network error".to_string()),
+            None,
+            &[],
+            false,
+            true,
+        );
+        let json: serde_json::Value = serde_json::from_str(&error).unwrap();
+        assert_eq!(json["text"], "n/a");
+        assert_eq!(json["class"], serde_json::json!(["error"]));
+        assert!(
+            json["tooltip"]
+                .as_str()
+                .unwrap()
+                .ends_with("\nNetwork error")
+        );
+        assert!(json.get("percentage").is_none());
+    }
+
+    #[test]
+    fn test_line_formats_color_severe_alerts() {
+        assert_eq!(
+            render(BarFormat::I3blocks, &rainy(), None, &[], false, false),
+            "20°C light rain\n20°C"
+        );
+        assert_eq!(
+            render(
+                BarFormat::I3blocks,
+                &rainy(),
+                None,
+                &[severe_alert()],
+                false,
+                false
+            ),
+            "⚠ 20°C light rain\n⚠ 20°C\n#FF5555"
+        );
+        // Persistent blocks read every line as a new full_text.
+        assert_eq!(
+            render(
+                BarFormat::I3blocks,
+                &rainy(),
+                None,
+                &[severe_alert()],
+                false,
+                true
+            ),
+            "⚠ 20°C light rain"
+        );
+        assert_eq!(
+            render(BarFormat::Polybar, &rainy(), None, &[], false, true),
+            "20°C light rain"
+        );
+        assert_eq!(
+            render(
+                BarFormat::Polybar,
+                &rainy(),
+                None,
+                &[severe_alert()],
+                false,
+                true
+            ),
+            "%{F#FF5555}⚠ 20°C light rain%{F-}"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+//! # Shell Completions and Man Page
+//!
+//! Completions are clap_complete's *dynamic* kind: the script `completions
+//! <shell>` prints only registers a hook that calls back into this binary
+//! (with `COMPLETE=<shell>` set, answered by the `CompleteEnv` check at the
+//! top of `main`), so `--location` and the `locations` subcommands can
+//! offer the names of whatever locations are saved right now -- a static
+//! script could only ever know the ones saved when it was generated.
+//!
+//! `man` renders the whole `Cli` as a roff page. The Debian package ships
+//! both, generated at release time (see `.github/workflows/release.yml`).
+
+use std::io::Write;
+
+use clap::CommandFactory;
+use clap_complete::env::Shells;
+use clap_complete::{CompletionCandidate, Shell};
+
+use super::{Cli, CliError, ErrorKind};
+use crate::config::ConfigManager;
+
+/// The executable's name, as the shell sees it.
+const BIN_NAME: &str = "open-weather-wizard";
+
+/// The environment variable `CompleteEnv` answers completions on -- its
+/// default, so `COMPLETE=bash open-weather-wizard` also works directly.
+const COMPLETE_ENV_VAR: &str = "COMPLETE";
+
+/// Every saved location's name, for completing `--location` and the
+/// `locations` subcommands -- none if there's no config yet. Runs on every
+/// Tab press, so it only reads the config file: nothing here touches the
+/// network or the keychain.
+pub(super) fn saved_location_names() -> Vec<CompletionCandidate> {
+    let Ok(config_manager) = ConfigManager::new() else {
+        return Vec::new();
+    };
+    if !config_manager.config_exists() {
+        return Vec::new();
+    }
+    config_manager
+        .load_config()
+        .locations
+        .into_iter()
+        .map(|saved| CompletionCandidate::new(saved.name))
+        .collect()
+}
+
+/// Prints `shell`'s registration script -- see the module docs.
+pub(super) fn print_completions(shell: Shell) -> Result<(), CliError> {
+    let shells = Shells::builtins();
+    let completer = shells
+        .completer(&shell.to_string())
+        .ok_or_else(|| CliError::new(ErrorKind::Usage, format!("No completions for {shell}")))?;
+    let mut stdout = std::io::stdout().lock();
+    completer
+        .write_registration(COMPLETE_ENV_VAR, BIN_NAME, BIN_NAME, BIN_NAME, &mut stdout)
+        .and_then(|()| stdout.flush())
+        .map_err(write_error)
+}
+
+/// Prints the man page, in roff.
+pub(super) fn print_man() -> Result<(), CliError> {
+    let mut stdout = std::io::stdout().lock();
+    clap_mangen::Man::new(Cli::command())
+        .render(&mut stdout)
+        .and_then(|()| stdout.flush())
+        .map_err(write_error)
+}
+
+fn write_error(e: std::io::Error) -> CliError {
+    CliError::new(ErrorKind::Internal, format!("Failed to write output: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_shell_has_a_registration_script() {
+        for shell in [
+            Shell::Bash,
+            Shell::Elvish,
+            Shell::Fish,
+            Shell::PowerShell,
+            Shell::Zsh,
+        ] {
+            let shells = Shells::builtins();
+            let completer = shells
+                .completer(&shell.to_string())
+                .unwrap_or_else(|| panic!("no completer for {shell}"));
+            let mut script = Vec::new();
+            completer
+                .write_registration(COMPLETE_ENV_VAR, BIN_NAME, BIN_NAME, BIN_NAME, &mut script)
+                .unwrap();
+            assert!(String::from_utf8(script).unwrap().contains(BIN_NAME));
+        }
+    }
+}
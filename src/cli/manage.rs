@@ -0,0 +1,797 @@
+//! # Managing Settings Without the GUI
+//!
+//! The `config`, `locations`, and `token` subcommands, so a machine with no
+//! display can be set up from a shell instead of the first-run Preferences
+//! window. Every change is made the way that window makes it: the config is
+//! loaded into a `preferences::State`, edited with the same `Message`s its
+//! fields send, checked with the same `validation_errors`, and written back
+//! with `apply_to` -- so the CLI can't save anything Preferences' Save
+//! button would have refused.
+//!
+//! Saved locations are picked by name here, so unlike the Preferences form
+//! (which tracks entries by position) `add` and `rename` also refuse a
+//! name that another location already has.
+
+use std::io::{IsTerminal, Read};
+
+use clap::{Args, Subcommand, ValueEnum};
+use clap_complete::ArgValueCandidates;
+
+use super::completions::saved_location_names;
+use super::{CliError, ErrorKind, parse_provider, provider_token_env_var};
+use crate::config::{
+    AppConfig, ConfigManager, Language, LocationConfig, SavedLocation, ThemePreference,
+    WeatherApiProvider,
+};
+use crate::ui::preferences::{self, LANGUAGES, Message, RefreshIntervalPreset, State};
+use crate::weather_api::weather_provider::WeatherProviderFactory;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Create, show, or change settings.
+    #[command(subcommand)]
+    Config(ConfigCommand),
+    /// List, add, remove, or rename saved locations.
+    #[command(subcommand)]
+    Locations(LocationsCommand),
+    /// Store, remove, or test API tokens in the OS keychain.
+    #[command(subcommand)]
+    Token(TokenCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Write a first configuration -- what the GUI's first-run setup does.
+    /// A keyed provider needs its token saved first (`token set`).
+    Init {
+        /// "openweather", "google", "open-meteo", "nws", or "met-norway".
+        /// Defaults to "openweather", like the GUI.
+        #[arg(long)]
+        provider: Option<String>,
+        /// The first saved location's name.
+        #[arg(long, default_value = "Home")]
+        name: String,
+        #[command(flatten)]
+        place: PlaceArgs,
+        /// Replace an existing configuration.
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print one setting, or every setting if KEY is omitted.
+    Get { key: Option<ConfigKey> },
+    /// Change one setting.
+    Set { key: ConfigKey, value: String },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LocationsCommand {
+    /// List saved locations, marking the current one with "*".
+    List,
+    /// Save a new location.
+    Add {
+        name: String,
+        #[command(flatten)]
+        place: PlaceArgs,
+    },
+    /// Remove a saved location. The last one can't be removed.
+    Remove {
+        #[arg(add = ArgValueCandidates::new(saved_location_names))]
+        name: String,
+    },
+    /// Rename a saved location.
+    Rename {
+        #[arg(add = ArgValueCandidates::new(saved_location_names))]
+        name: String,
+        new_name: String,
+    },
+    /// Make a saved location the one shown on launch and queried by
+    /// `--headless` by default.
+    SetCurrent {
+        #[arg(add = ArgValueCandidates::new(saved_location_names))]
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TokenCommand {
+    /// Save an API token, read from standard input, in the OS keychain.
+    Set {
+        /// Defaults to the configured provider.
+        provider: Option<String>,
+    },
+    /// Delete a saved API token from the OS keychain.
+    Clear {
+        /// Defaults to the configured provider.
+        provider: Option<String>,
+    },
+    /// Fetch the current location's weather with a provider's token --
+    /// like Preferences' "Verify API" button. Reads the token the way
+    /// `--headless` does, environment variables first.
+    Check {
+        /// Defaults to the configured provider.
+        provider: Option<String>,
+    },
+}
+
+/// A place: a city name, or coordinates -- as in Preferences' Locations
+/// section, which also decides what's missing (see `validation_errors`).
+#[derive(Args, Debug)]
+pub struct PlaceArgs {
+    #[arg(long, conflicts_with_all = ["lat", "lon"])]
+    city: Option<String>,
+    #[arg(long, conflicts_with_all = ["lat", "lon"])]
+    state: Option<String>,
+    #[arg(long, conflicts_with_all = ["lat", "lon"])]
+    country: Option<String>,
+    /// Latitude (decimal degrees, north positive), instead of a city.
+    #[arg(long, requires = "lon", allow_negative_numbers = true)]
+    lat: Option<f64>,
+    /// Longitude (decimal degrees, east positive).
+    #[arg(long, requires = "lat", allow_negative_numbers = true)]
+    lon: Option<f64>,
+}
+
+/// The settings `config get`/`config set` know -- everything in
+/// Preferences except locations and tokens, which have their own
+/// subcommands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigKey {
+    /// The primary weather provider.
+    Provider,
+    /// Comma-separated providers to fall back to, or "none".
+    Fallbacks,
+    /// The language weather descriptions are requested in.
+    Language,
+    /// "light", "dark", or "system".
+    Theme,
+    /// "metric" or "imperial".
+    Units,
+    /// Seconds between refreshes: 30, 60, 300, 900, or 1800.
+    RefreshInterval,
+    /// "true" or "false".
+    LaunchAtLogin,
+    /// A proxy URL (http://, https://, socks5://, socks5h://), or "none".
+    Proxy,
+    /// A PEM file of extra CA certificates to trust, or "none".
+    CaBundle,
+    /// Seconds to wait for a connection to open; 0 waits forever.
+    ConnectTimeout,
+    /// Seconds to wait for a whole request; 0 waits forever.
+    Timeout,
+}
+
+impl ConfigKey {
+    fn name(self) -> &'static str {
+        match self {
+            ConfigKey::Provider => "provider",
+            ConfigKey::Fallbacks => "fallbacks",
+            ConfigKey::Language => "language",
+            ConfigKey::Theme => "theme",
+            ConfigKey::Units => "units",
+            ConfigKey::RefreshInterval => "refresh-interval",
+            ConfigKey::LaunchAtLogin => "launch-at-login",
+            ConfigKey::Proxy => "proxy",
+            ConfigKey::CaBundle => "ca-bundle",
+            ConfigKey::ConnectTimeout => "connect-timeout",
+            ConfigKey::Timeout => "timeout",
+        }
+    }
+}
+
+/// Runs a subcommand -- see the module docs.
+pub(super) fn run(command: &Command) -> Result<(), CliError> {
+    let config_manager = ConfigManager::new().map_err(|e| {
+        CliError::new(
+            ErrorKind::Config,
+            format!("Could not access config directory: {e}"),
+        )
+    })?;
+    match command {
+        Command::Config(ConfigCommand::Init {
+            provider,
+            name,
+            place,
+            force,
+        }) => {
+            if config_manager.config_exists() && !force {
+                return Err(CliError::new(
+                    ErrorKind::Config,
+                    "A configuration already exists -- change it with `config set`, or pass --force to start over",
+                ));
+            }
+            let mut config = AppConfig::default();
+            if let Some(provider) = provider {
+                config.weather_provider =
+                    parse_provider(provider).map_err(|e| CliError::new(ErrorKind::Usage, e))?;
+            }
+            config.locations = vec![SavedLocation {
+                name: name.clone(),
+                ..SavedLocation::default()
+            }];
+            save(&config_manager, config, |state| {
+                set_place(state, place);
+                Ok(())
+            })
+        }
+        Command::Config(ConfigCommand::Get { key }) => {
+            let state = State::from_config(&load(&config_manager)?);
+            match key {
+                Some(key) => println!("{}", setting(&state, *key)),
+                None => {
+                    for key in ConfigKey::value_variants() {
+                        println!("{} = {}", key.name(), setting(&state, *key));
+                    }
+                }
+            }
+            Ok(())
+        }
+        Command::Config(ConfigCommand::Set { key, value }) => {
+            let config = load(&config_manager)?;
+            save(&config_manager, config, |state| {
+                for message in setting_messages(*key, value)? {
+                    preferences::update(state, message);
+                }
+                Ok(())
+            })
+        }
+        Command::Locations(LocationsCommand::List) => {
+            let config = load(&config_manager)?;
+            let current = config
+                .current_location_index
+                .min(config.locations.len().saturating_sub(1));
+            for (index, saved) in config.locations.iter().enumerate() {
+                let marker = if index == current { '*' } else { ' ' };
+                println!("{marker} {}: {}", saved.name, describe(&saved.location));
+            }
+            Ok(())
+        }
+        Command::Locations(LocationsCommand::Add { name, place }) => {
+            let config = load(&config_manager)?;
+            ensure_unused(&config, name)?;
+            save(&config_manager, config, |state| {
+                add_location(state, name, place);
+                Ok(())
+            })
+        }
+        Command::Locations(LocationsCommand::Remove { name }) => {
+            let config = load(&config_manager)?;
+            let index = find(&config, name)?;
+            if config.locations.len() == 1 {
+                return Err(CliError::new(
+                    ErrorKind::Usage,
+                    format!(
+                        "'{name}' is the only saved location -- add another before removing it"
+                    ),
+                ));
+            }
+            save(&config_manager, config, |state| {
+                preferences::update(state, Message::LocationSelected(index));
+                preferences::update(state, Message::RemoveLocationRequested);
+                Ok(())
+            })
+        }
+        Command::Locations(LocationsCommand::Rename { name, new_name }) => {
+            let config = load(&config_manager)?;
+            let index = find(&config, name)?;
+            if !config.locations[index].name.eq_ignore_ascii_case(new_name) {
+                ensure_unused(&config, new_name)?;
+            }
+            save(&config_manager, config, |state| {
+                preferences::update(state, Message::LocationSelected(index));
+                preferences::update(state, Message::LocationNameChanged(new_name.clone()));
+                Ok(())
+            })
+        }
+        Command::Locations(LocationsCommand::SetCurrent { name }) => {
+            let mut config = load(&config_manager)?;
+            // `State::from_config` carries this through to `apply_to`.
+            config.current_location_index = find(&config, name)?;
+            save(&config_manager, config, |_| Ok(()))
+        }
+        Command::Token(TokenCommand::Set { provider }) => {
+            let provider = token_provider(&config_manager, provider.as_deref())?;
+            let token = read_token(&provider)?;
+            // Same rule as `validation_errors`' own token check.
+            if token.is_empty() {
+                return Err(CliError::new(
+                    ErrorKind::Usage,
+                    format!("API Token is required for {provider}."),
+                ));
+            }
+            config_manager
+                .load_config()
+                .set_api_token(&provider, &token)
+                .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+            println!("Saved the API token for {provider}");
+            Ok(())
+        }
+        Command::Token(TokenCommand::Clear { provider }) => {
+            let provider = token_provider(&config_manager, provider.as_deref())?;
+            config_manager
+                .load_config()
+                .delete_api_token(&provider)
+                .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+            println!("Removed the API token for {provider}");
+            Ok(())
+        }
+        Command::Token(TokenCommand::Check { provider }) => {
+            let config = load(&config_manager)?;
+            let provider = token_provider(&config_manager, provider.as_deref())?;
+            check_token(&config, &provider)
+        }
+    }
+}
+
+/// The saved config, or a `Config` error if there isn't one yet.
+fn load(config_manager: &ConfigManager) -> Result<AppConfig, CliError> {
+    if !config_manager.config_exists() {
+        return Err(CliError::new(
+            ErrorKind::Config,
+            "No configuration found yet -- create one with `config init`",
+        ));
+    }
+    Ok(config_manager.load_config())
+}
+
+/// Loads `config` into a Preferences form, lets `edit` change it, and
+/// saves the result if `validation_errors` finds nothing wrong with it.
+///
+/// The form's draft token for its provider is filled in only so validation
+/// sees it, then dropped before `apply_to`, which would otherwise write it
+/// back to the keychain -- a token from an environment variable shouldn't
+/// end up there, and on a box with no keychain that write would fail.
+fn save(
+    config_manager: &ConfigManager,
+    mut config: AppConfig,
+    edit: impl FnOnce(&mut State) -> Result<(), CliError>,
+) -> Result<(), CliError> {
+    let mut state = State::from_config(&config);
+    edit(&mut state)?;
+
+    if state.provider.requires_api_token()
+        && let Some(token) = super::token_for(&config, &state.provider, &state.provider)
+    {
+        state.token_inputs.insert(state.provider.clone(), token);
+    }
+    let errors = state.validation_errors();
+    if !errors.is_empty() {
+        let mut message = errors.join("\n  ");
+        if state.token_input().is_empty() && state.provider.requires_api_token() {
+            message.push_str(&format!(
+                "\n  (save one with `token set`, or set {})",
+                provider_token_env_var(&state.provider).unwrap_or(super::TOKEN_ENV_VAR)
+            ));
+        }
+        return Err(CliError::new(ErrorKind::Usage, message));
+    }
+    state.token_inputs.clear();
+
+    state
+        .apply_to(&mut config)
+        .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+    config_manager.save_config(&config).map_err(|e| {
+        CliError::new(
+            ErrorKind::Config,
+            format!("Failed to save configuration: {e}"),
+        )
+    })
+}
+
+/// Fills the form's selected location in from `place`, field by field, as
+/// if typed -- so switching to coordinates, or editing a city, clears the
+/// same saved coordinates it would in the window.
+fn set_place(state: &mut State, place: &PlaceArgs) {
+    if let (Some(lat), Some(lon)) = (place.lat, place.lon) {
+        preferences::update(state, Message::CoordinatesModeToggled(true));
+        preferences::update(state, Message::LatitudeChanged(lat.to_string()));
+        preferences::update(state, Message::LongitudeChanged(lon.to_string()));
+        return;
+    }
+    let field = |value: &Option<String>| value.clone().unwrap_or_default();
+    preferences::update(state, Message::CoordinatesModeToggled(false));
+    preferences::update(state, Message::CityChanged(field(&place.city)));
+    preferences::update(state, Message::StateChanged(field(&place.state)));
+    preferences::update(state, Message::CountryChanged(field(&place.country)));
+}
+
+/// Appends a location named `name` at `place` -- Preferences' "Add" button,
+/// then filling in the new entry.
+fn add_location(state: &mut State, name: &str, place: &PlaceArgs) {
+    preferences::update(state, Message::AddLocationRequested);
+    preferences::update(state, Message::LocationNameChanged(name.to_string()));
+    set_place(state, place);
+}
+
+/// The index of the saved location called `name` (ignoring case).
+fn find(config: &AppConfig, name: &str) -> Result<usize, CliError> {
+    super::saved_location(config, name)?;
+    Ok(config
+        .locations
+        .iter()
+        .position(|saved| saved.name.eq_ignore_ascii_case(name))
+        .unwrap_or_default())
+}
+
+/// Fails if a saved location is already called `name` (ignoring case).
+fn ensure_unused(config: &AppConfig, name: &str) -> Result<(), CliError> {
+    if config
+        .locations
+        .iter()
+        .any(|saved| saved.name.eq_ignore_ascii_case(name))
+    {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            format!("There's already a saved location named '{name}'"),
+        ));
+    }
+    Ok(())
+}
+
+/// `locations list`'s description of a place, e.g. "Peoria, IL, US
+/// (40.6936, -89.5890)", or just its coordinates.
+fn describe(location: &LocationConfig) -> String {
+    if location.is_coordinate_only() {
+        return location.display_name();
+    }
+    let mut description = [&location.city, &location.state, &location.country]
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Some((lat, lon)) = location.coordinates() {
+        description.push_str(&format!(" ({lat:.4}, {lon:.4})"));
+    }
+    description
+}
+
+/// How `config get` shows `key` -- in the form `config set` accepts.
+fn setting(state: &State, key: ConfigKey) -> String {
+    match key {
+        ConfigKey::Provider => provider_id(&state.provider).to_string(),
+        ConfigKey::Fallbacks => {
+            // `apply_to` drops the primary from its own fallbacks too.
+            let fallbacks = state
+                .fallback_providers
+                .iter()
+                .filter(|fallback| **fallback != state.provider)
+                .map(provider_id)
+                .collect::<Vec<_>>();
+            if fallbacks.is_empty() {
+                "none".to_string()
+            } else {
+                fallbacks.join(",")
+            }
+        }
+        ConfigKey::Language => state.language.to_string().to_ascii_lowercase(),
+        ConfigKey::Theme => match state.theme_preference {
+            ThemePreference::Light => "light",
+            ThemePreference::Dark => "dark",
+            ThemePreference::System => "system",
+        }
+        .to_string(),
+        ConfigKey::Units => if state.use_fahrenheit {
+            "imperial"
+        } else {
+            "metric"
+        }
+        .to_string(),
+        ConfigKey::RefreshInterval => state.refresh_interval.to_secs().to_string(),
+        ConfigKey::LaunchAtLogin => state.launch_at_login.to_string(),
+        ConfigKey::Proxy => or_none(&state.proxy_input),
+        ConfigKey::CaBundle => or_none(&state.ca_bundle_input),
+        ConfigKey::ConnectTimeout => state.connect_timeout_input.clone(),
+        ConfigKey::Timeout => state.timeout_input.clone(),
+    }
+}
+
+/// `value`, or "none" when it's empty -- what `config set` takes to clear
+/// an optional setting.
+fn or_none(value: &str) -> String {
+    if value.is_empty() {
+        "none".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// The form edits that set `key` to `value`.
+fn setting_messages(key: ConfigKey, value: &str) -> Result<Vec<Message>, CliError> {
+    let invalid = |expected: &str| {
+        CliError::new(
+            ErrorKind::Usage,
+            format!("Invalid {}: '{value}' -- expected {expected}", key.name()),
+        )
+    };
+    let value = value.trim();
+    let message = match key {
+        ConfigKey::Provider => Message::ProviderSelected(
+            parse_provider(value).map_err(|e| CliError::new(ErrorKind::Usage, e))?,
+        ),
+        ConfigKey::Fallbacks => {
+            let fallbacks = if value.is_empty() || value.eq_ignore_ascii_case("none") {
+                Vec::new()
+            } else {
+                value
+                    .split(',')
+                    .map(|name| parse_provider(name.trim()))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|e| CliError::new(ErrorKind::Usage, e))?
+            };
+            // Off for every provider first, then on for the chosen ones --
+            // `FallbackToggled` keeps them in Preferences' order.
+            return Ok(preferences::PROVIDERS
+                .iter()
+                .map(|provider| Message::FallbackToggled(provider.clone(), false))
+                .chain(
+                    fallbacks
+                        .into_iter()
+                        .map(|provider| Message::FallbackToggled(provider, true)),
+                )
+                .collect());
+        }
+        ConfigKey::Language => Message::LanguageSelected(
+            parse_language(value)
+                .ok_or_else(|| invalid("a language, e.g. \"english\" or \"de\""))?,
+        ),
+        ConfigKey::Theme => {
+            Message::ThemePreferenceSelected(match value.to_ascii_lowercase().as_str() {
+                "light" => ThemePreference::Light,
+                "dark" => ThemePreference::Dark,
+                "system" => ThemePreference::System,
+                _ => return Err(invalid("\"light\", \"dark\", or \"system\"")),
+            })
+        }
+        ConfigKey::Units => Message::UnitsToggled(match value.to_ascii_lowercase().as_str() {
+            "imperial" | "fahrenheit" => true,
+            "metric" | "celsius" => false,
+            _ => return Err(invalid("\"metric\" or \"imperial\"")),
+        }),
+        ConfigKey::RefreshInterval => Message::RefreshIntervalSelected(
+            value
+                .parse::<u64>()
+                .ok()
+                .and_then(|secs| {
+                    RefreshIntervalPreset::ALL
+                        .into_iter()
+                        .find(|preset| preset.to_secs() == secs)
+                })
+                .ok_or_else(|| invalid("30, 60, 300, 900, or 1800 (seconds)"))?,
+        ),
+        ConfigKey::LaunchAtLogin => Message::LaunchAtLoginToggled(
+            value
+                .parse::<bool>()
+                .map_err(|_| invalid("\"true\" or \"false\""))?,
+        ),
+        // Checked, like everything else, by `save`'s `validation_errors`.
+        ConfigKey::Proxy => Message::ProxyChanged(none_as_empty(value)),
+        ConfigKey::CaBundle => Message::CaBundleChanged(none_as_empty(value)),
+        ConfigKey::ConnectTimeout => Message::ConnectTimeoutChanged(value.to_string()),
+        ConfigKey::Timeout => Message::TimeoutChanged(value.to_string()),
+    };
+    Ok(vec![message])
+}
+
+/// `or_none` in reverse.
+fn none_as_empty(value: &str) -> String {
+    if value.eq_ignore_ascii_case("none") {
+        String::new()
+    } else {
+        value.to_string()
+    }
+}
+
+/// A language by English name ("German") or code ("de"), ignoring case.
+fn parse_language(value: &str) -> Option<Language> {
+    LANGUAGES.into_iter().find(|language| {
+        language.to_string().eq_ignore_ascii_case(value)
+            || language.openweather_code().eq_ignore_ascii_case(value)
+            || language.google_code().eq_ignore_ascii_case(value)
+    })
+}
+
+/// `--provider`'s name for `provider` -- what `parse_provider` reads back.
+pub(super) fn provider_id(provider: &WeatherApiProvider) -> &'static str {
+    match provider {
+        WeatherApiProvider::OpenWeather => "openweather",
+        WeatherApiProvider::GoogleWeather => "google",
+        WeatherApiProvider::OpenMeteo => "open-meteo",
+        WeatherApiProvider::NationalWeatherService => "nws",
+        WeatherApiProvider::MetNorway => "met-norway",
+    }
+}
+
+/// The `token` subcommands' provider: the one named, or the configured one.
+/// Keyless providers are refused -- they have no token to manage.
+fn token_provider(
+    config_manager: &ConfigManager,
+    name: Option<&str>,
+) -> Result<WeatherApiProvider, CliError> {
+    let provider = match name {
+        Some(name) => parse_provider(name).map_err(|e| CliError::new(ErrorKind::Usage, e))?,
+        None => load(config_manager)
+            .map_err(|_| {
+                CliError::new(
+                    ErrorKind::Usage,
+                    "No configuration yet to take the provider from -- name one, e.g. `token set openweather`",
+                )
+            })?
+            .weather_provider,
+    };
+    if !provider.requires_api_token() {
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            format!("{provider} doesn't use an API token"),
+        ));
+    }
+    Ok(provider)
+}
+
+/// Reads a token from standard input -- never from an argument, which
+/// would leave it in the shell's history and the process list. Prompts
+/// first when a person is typing it.
+fn read_token(provider: &WeatherApiProvider) -> Result<String, CliError> {
+    let stdin = std::io::stdin();
+    if stdin.is_terminal() {
+        eprint!("API token for {provider}: ");
+    }
+    let mut token = String::new();
+    let read = if stdin.is_terminal() {
+        stdin.read_line(&mut token).map(|_| ())
+    } else {
+        stdin.lock().read_to_string(&mut token).map(|_| ())
+    };
+    read.map_err(|e| {
+        CliError::new(
+            ErrorKind::Internal,
+            format!("Failed to read the token: {e}"),
+        )
+    })?;
+    Ok(token.trim().to_string())
+}
+
+/// `token check`: one current-conditions request with `provider` alone,
+/// never the fallback chain -- a fallback answering would hide exactly the
+/// bad key this exists to catch.
+fn check_token(config: &AppConfig, provider: &WeatherApiProvider) -> Result<(), CliError> {
+    let token = super::token_for(config, provider, &config.weather_provider);
+    let client = config
+        .http
+        .client()
+        .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+    let endpoints = super::endpoints_with_env(config, |name| std::env::var(name).ok())?;
+    let weather_provider = WeatherProviderFactory::create_provider(
+        provider,
+        token,
+        config.language,
+        config.retry,
+        &client,
+        &endpoints,
+    )
+    .map_err(|e| {
+        CliError::new(
+            ErrorKind::Config,
+            format!(
+                "{e} (save one with `token set`, or set {})",
+                provider_token_env_var(provider).unwrap_or(super::TOKEN_ENV_VAR)
+            ),
+        )
+    })?;
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| {
+        CliError::new(
+            ErrorKind::Internal,
+            format!("Failed to start async runtime: {e}"),
+        )
+    })?;
+    let location = config.current_location();
+    let weather = runtime
+        .block_on(weather_provider.get_weather(&location))
+        .map_err(|e| CliError::from_api(&format!("{provider} check failed"), &e))?;
+    println!("{provider}: the API token works (fetched {})", weather.name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(city: &str, country: &str) -> PlaceArgs {
+        PlaceArgs {
+            city: Some(city.to_string()),
+            state: None,
+            country: Some(country.to_string()),
+            lat: None,
+            lon: None,
+        }
+    }
+
+    #[test]
+    fn test_added_locations_get_the_preferences_validation() {
+        let mut config = AppConfig::default();
+        config.weather_provider = WeatherApiProvider::OpenMeteo;
+        let mut state = State::from_config(&config);
+        add_location(&mut state, "Lab", &place("Zurich", ""));
+        assert_eq!(state.validation_errors(), ["\"Lab\" needs a country."]);
+
+        let mut state = State::from_config(&config);
+        add_location(
+            &mut state,
+            "Summit",
+            &PlaceArgs {
+                lat: Some(95.0),
+                lon: Some(10.0),
+                ..place("", "")
+            },
+        );
+        assert_eq!(
+            state.validation_errors(),
+            ["\"Summit\": Latitude 95 must be between -90 and 90."]
+        );
+
+        let mut state = State::from_config(&config);
+        add_location(&mut state, "Lab", &place("Zurich", "CH"));
+        assert!(state.validation_errors().is_empty());
+        let saved = SavedLocation::from(&state.locations[1]);
+        assert_eq!(saved.name, "Lab");
+        assert_eq!(saved.location.city, "Zurich");
+    }
+
+    #[test]
+    fn test_settings_read_back_as_written() {
+        let mut state = State::from_config(&AppConfig::default());
+        for (key, value) in [
+            (ConfigKey::Provider, "met-norway"),
+            (ConfigKey::Fallbacks, "nws, open-meteo"),
+            (ConfigKey::Language, "de"),
+            (ConfigKey::Theme, "Dark"),
+            (ConfigKey::Units, "imperial"),
+            (ConfigKey::RefreshInterval, "900"),
+            (ConfigKey::LaunchAtLogin, "true"),
+            (ConfigKey::Proxy, "socks5h://127.0.0.1:9050"),
+            (ConfigKey::CaBundle, "none"),
+            (ConfigKey::ConnectTimeout, "5"),
+            (ConfigKey::Timeout, "0"),
+        ] {
+            for message in setting_messages(key, value).unwrap() {
+                preferences::update(&mut state, message);
+            }
+        }
+        let settings = ConfigKey::value_variants()
+            .iter()
+            .map(|key| setting(&state, *key))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            settings,
+            [
+                "met-norway",
+                "open-meteo,nws",
+                "german",
+                "dark",
+                "imperial",
+                "900",
+                "true",
+                "socks5h://127.0.0.1:9050",
+                "none",
+                "5",
+                "0"
+            ]
+        );
+        assert!(state.validation_errors().is_empty());
+
+        assert!(setting_messages(ConfigKey::RefreshInterval, "45").is_err());
+        assert!(setting_messages(ConfigKey::Theme, "sepia").is_err());
+
+        for (key, value) in [
+            (ConfigKey::Proxy, "ftp://proxy.corp"),
+            (ConfigKey::Timeout, "soon"),
+            (ConfigKey::CaBundle, "/nonexistent/corp-ca.pem"),
+        ] {
+            let mut state = State::from_config(&AppConfig::default());
+            let baseline = state.validation_errors().len();
+            for message in setting_messages(key, value).unwrap() {
+                preferences::update(&mut state, message);
+            }
+            assert_eq!(state.validation_errors().len(), baseline + 1, "{value}");
+        }
+    }
+}
@@ -0,0 +1,1520 @@
+//! # Headless CLI Mode
+//!
+//! `--headless` fetches weather once and prints it to stdout, without ever
+//! opening an `iced` window -- useful for scripting, status-bar widgets, or
+//! a genuinely headless machine (see issue #40). Deliberately a *bin-only*
+//! module (declared via `mod cli;` in `main.rs`, not re-exported from
+//! `lib.rs`): CLI parsing is a concern of this specific executable, not the
+//! library crate other code links against.
+//!
+//! This never touches `app::run()`/`iced::daemon`, so it's free to spin up
+//! its own `tokio::runtime::Runtime` -- the "don't nest a runtime around
+//! `app::run()`" constraint noted in `main.rs`'s doc comment only applies to
+//! the GUI path.
+//!
+//! A one-shot fetch-and-print by default. `--watch` instead keeps the one
+//! provider (and the location it resolved) alive and prints a line per
+//! refresh, for status bars that would otherwise re-run the whole thing --
+//! geocoding included -- from cron every minute.
+//!
+//! Failures exit with a code per `ErrorKind` (documented in the README), so
+//! a status-bar script can tell "offline, keep showing the last value" from
+//! "misconfigured, tell the user". With `--json`, the error is also printed
+//! to stdout as `{"error": {"kind", "message"}}` instead of to stderr.
+//!
+//! `--format` prints for a status bar instead (see `bar`), and
+//! `--template` in a layout of the user's own (see `template`).
+//!
+//! Active alerts are fetched with every refresh and listed in each output,
+//! most severe first; `--alerts-only` prints nothing else, for scripts that
+//! page someone when an `Extreme` one appears.
+//!
+//! Each refresh's requests -- conditions, forecast, alerts -- run
+//! concurrently against one resolved location. `--timeout` replaces the
+//! configured per-request timeout (`HttpSettings::timeout_secs`) for the
+//! run, so it bounds each HTTP request, retries included, separately.
+//!
+//! Every answer is saved through `weather_api::cache` -- the same store the
+//! GUI opens with -- and `--offline`/`--max-age` answer from it instead.
+//!
+//! Repeating `--location`, or `--all-locations`, queries several saved
+//! locations at once and prints a table (a JSON array with `--json`), one
+//! row per location -- a failed one doesn't keep the others from printing.
+//!
+//! The `config`, `locations`, and `token` subcommands set all of that up
+//! without the GUI -- see `manage`. `completions` and `man` print a shell
+//! completion script and the man page -- see `completions`.
+
+mod bar;
+mod completions;
+mod manage;
+mod template;
+
+use std::time::Duration;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use clap_complete::{ArgValueCandidates, Shell};
+use serde::Serialize;
+
+use crate::app::{WeatherStatus, backoff_interval, refresh_interval};
+use crate::config::{AppConfig, ConfigManager, LocationConfig, WeatherApiProvider};
+use crate::ui::temperature::{
+    celsius_to_display, compass_direction, distance_to_display, distance_unit,
+    format_local_datetime, format_local_time, speed_to_display, speed_unit, unit_symbol,
+};
+use crate::weather_api::alerts::{AlertSeverity, WeatherAlert};
+use crate::weather_api::cache::{CachingProvider, ResponseCache};
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::ForecastResponse;
+use crate::weather_api::geocoding::{self, SearchOutcome};
+use crate::weather_api::http::HttpSettings;
+use crate::weather_api::openweather_api::{ApiError, ApiResponse};
+use crate::weather_api::weather_provider::{WeatherProvider, WeatherProviderFactory};
+use bar::BarFormat;
+use template::{Template, TemplateData};
+
+/// Overrides whatever token is in the OS keychain -- the keychain (via the
+/// `keyring` crate's Secret Service backend on Linux) isn't guaranteed to be
+/// available on a genuinely headless machine with no D-Bus session, so
+/// scripted/server use needs a way to supply a token that doesn't depend on
+/// it. Applies to whichever provider is primary for this query; see
+/// `provider_token_env_var` for the per-provider variants.
+const TOKEN_ENV_VAR: &str = "OPEN_WEATHER_WIZARD_API_TOKEN";
+
+/// Per-provider counterparts of `TOKEN_ENV_VAR`, checked first -- the only
+/// way to hand a keyed *fallback* its own token without the keychain, since
+/// the generic variable belongs to the primary. `None` for keyless
+/// providers.
+fn provider_token_env_var(provider: &WeatherApiProvider) -> Option<&'static str> {
+    match provider {
+        WeatherApiProvider::OpenWeather => Some("OPEN_WEATHER_WIZARD_OPENWEATHER_API_TOKEN"),
+        WeatherApiProvider::GoogleWeather => Some("OPEN_WEATHER_WIZARD_GOOGLE_API_TOKEN"),
+        WeatherApiProvider::OpenMeteo
+        | WeatherApiProvider::NationalWeatherService
+        | WeatherApiProvider::MetNorway => None,
+    }
+}
+
+/// `config.http` with `--timeout` (if given) in place of its request
+/// timeout. reqwest applies it to each request on its own, so a retry
+/// gets the full time again and a backoff between attempts never counts
+/// against it.
+fn http_settings(config: &AppConfig, timeout: Option<u64>) -> HttpSettings {
+    HttpSettings {
+        timeout_secs: timeout.unwrap_or(config.http.timeout_secs),
+        ..config.http.clone()
+    }
+}
+
+/// Environment variables that override `AppConfig::endpoints` for one
+/// run -- a script or a test can point at a mirror or a local stand-in
+/// without touching the saved config. Location detection is GUI-only, so
+/// its two endpoints have none.
+fn endpoints_with_env(
+    config: &AppConfig,
+    var: impl Fn(&str) -> Option<String>,
+) -> Result<Endpoints, CliError> {
+    let mut endpoints = config.endpoints.clone();
+    for (name, url) in [
+        (
+            "OPEN_WEATHER_WIZARD_OPENWEATHER_API_BASE",
+            &mut endpoints.openweather,
+        ),
+        (
+            "OPEN_WEATHER_WIZARD_GOOGLE_API_BASE",
+            &mut endpoints.google_weather,
+        ),
+        (
+            "OPEN_WEATHER_WIZARD_OPEN_METEO_API_BASE",
+            &mut endpoints.open_meteo,
+        ),
+        (
+            "OPEN_WEATHER_WIZARD_GEOCODING_API_BASE",
+            &mut endpoints.geocoding,
+        ),
+        ("OPEN_WEATHER_WIZARD_NWS_API_BASE", &mut endpoints.nws),
+        (
+            "OPEN_WEATHER_WIZARD_MET_NORWAY_API_BASE",
+            &mut endpoints.met_norway,
+        ),
+    ] {
+        if let Some(value) = var(name).filter(|value| !value.is_empty()) {
+            *url = Some(value);
+        }
+    }
+    endpoints
+        .validate()
+        .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+    Ok(endpoints)
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "open-weather-wizard",
+    about = "A desktop weather app. Pass --headless to fetch and print weather without opening the GUI.",
+    version,
+    args_conflicts_with_subcommands = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Fetch weather once and print to stdout, without opening the GUI.
+    #[arg(long)]
+    pub headless: bool,
+
+    /// Output machine-readable JSON instead of human-readable text.
+    #[arg(long, requires = "headless")]
+    pub json: bool,
+
+    /// Output for a status bar's custom module instead: "waybar" (JSON with
+    /// text, tooltip, class, and percentage), "i3blocks", or "polybar".
+    #[arg(long, value_enum, requires = "headless", conflicts_with = "json")]
+    pub format: Option<BarFormat>,
+
+    /// Print one line laid out by TEMPLATE instead, e.g. "{icon} {temp}{unit}
+    /// {description} ↑{hi} ↓{lo}" -- see the README for the placeholders,
+    /// `{name:spec}` formatting, and `{?name}...{/}` conditionals.
+    #[arg(
+        long,
+        requires = "headless",
+        value_name = "TEMPLATE",
+        conflicts_with_all = ["json", "format"]
+    )]
+    pub template: Option<String>,
+
+    /// Print only the active weather alerts (no conditions or forecast) --
+    /// a line per alert, or an `alerts` array with `--json`.
+    #[arg(long, requires = "headless", conflicts_with_all = ["format", "template"])]
+    pub alerts_only: bool,
+
+    /// Leave out alerts less severe than this: "minor", "moderate",
+    /// "severe", or "extreme". Alerts of unknown severity are left out by
+    /// any threshold.
+    #[arg(long, value_enum, requires = "headless", value_name = "SEVERITY")]
+    pub min_severity: Option<MinSeverity>,
+
+    /// Keep running and print one line per refresh (one JSON object per
+    /// line with `--json`) until interrupted. Refreshes every SECONDS if
+    /// given, otherwise on the configured refresh interval -- never faster
+    /// than every 15 minutes with Google Weather.
+    #[arg(
+        long,
+        requires = "headless",
+        value_name = "SECONDS",
+        num_args = 0..=1,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub watch: Option<Option<u64>>,
+
+    /// Give up on each HTTP request after SECONDS, instead of the
+    /// configured timeout. A timed-out forecast or alerts lookup is left
+    /// out, like any other failure of theirs; timed-out conditions fail the
+    /// refresh.
+    #[arg(
+        long,
+        requires = "headless",
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub timeout: Option<u64>,
+
+    /// Answer from the weather saved by earlier fetches (this command's or
+    /// the GUI's) without touching the network. Fails if nothing is saved
+    /// for the location -- or, with --max-age, nothing recent enough.
+    #[arg(long, requires = "headless", conflicts_with = "provider")]
+    pub offline: bool,
+
+    /// Answer from saved weather up to SECONDS old instead of fetching,
+    /// fetching only when it's older. With --offline, anything older is
+    /// ignored instead.
+    #[arg(
+        long,
+        requires = "headless",
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_age: Option<u64>,
+
+    /// Select a saved location by name for this one query (see the GUI's
+    /// Preferences "Locations" section) -- falls back to whichever one is
+    /// current if omitted. `--city`/`--state`/`--country` below still
+    /// override individual fields on top of whichever location this
+    /// resolves to. Repeat it to query several locations at once.
+    #[arg(
+        long,
+        requires = "headless",
+        value_name = "NAME",
+        add = ArgValueCandidates::new(completions::saved_location_names)
+    )]
+    pub location: Vec<String>,
+
+    /// Query every saved location at once, printing a table (a JSON array
+    /// with `--json`) with a row per location.
+    #[arg(
+        long,
+        requires = "headless",
+        conflicts_with_all = [
+            "location", "city", "state", "country", "lat", "lon",
+            "watch", "format", "template", "alerts_only",
+        ]
+    )]
+    pub all_locations: bool,
+
+    /// Override the configured city for this one query.
+    #[arg(long, requires = "headless")]
+    pub city: Option<String>,
+
+    /// Override the configured state/province for this one query.
+    #[arg(long, requires = "headless")]
+    pub state: Option<String>,
+
+    /// Override the configured country for this one query.
+    #[arg(long, requires = "headless")]
+    pub country: Option<String>,
+
+    /// Query this latitude (decimal degrees, north positive) instead of a
+    /// named place -- requires `--lon`, and replaces the location entirely.
+    #[arg(
+        long,
+        requires_all = ["headless", "lon"],
+        conflicts_with_all = ["city", "state", "country"],
+        allow_negative_numbers = true
+    )]
+    pub lat: Option<f64>,
+
+    /// Query this longitude (decimal degrees, east positive) -- see `--lat`.
+    #[arg(
+        long,
+        requires_all = ["headless", "lat"],
+        conflicts_with_all = ["city", "state", "country"],
+        allow_negative_numbers = true
+    )]
+    pub lon: Option<f64>,
+
+    /// Override the configured weather provider for this one query:
+    /// "openweather", "google", "open-meteo", "nws", or "met-norway".
+    #[arg(long, requires = "headless")]
+    pub provider: Option<String>,
+}
+
+/// Everything that isn't `--headless` itself.
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(flatten)]
+    Manage(manage::Command),
+    /// Print a completion script for SHELL: "bash", "elvish", "fish",
+    /// "powershell", or "zsh". Completes saved location names, too.
+    #[command(hide = true)]
+    Completions { shell: Shell },
+    /// Print the man page, in roff.
+    Man,
+}
+
+/// `--min-severity`'s choices -- `AlertSeverity` minus "unknown", which
+/// would filter nothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MinSeverity {
+    Minor,
+    Moderate,
+    Severe,
+    Extreme,
+}
+
+impl From<MinSeverity> for AlertSeverity {
+    fn from(severity: MinSeverity) -> Self {
+        match severity {
+            MinSeverity::Minor => AlertSeverity::Minor,
+            MinSeverity::Moderate => AlertSeverity::Moderate,
+            MinSeverity::Severe => AlertSeverity::Severe,
+            MinSeverity::Extreme => AlertSeverity::Extreme,
+        }
+    }
+}
+
+/// What class of failure ended a headless run -- the process exit code and
+/// the `--json` error's `kind`. The codes are part of the CLI's interface
+/// (see the README's table); don't renumber them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ErrorKind {
+    /// Anything not covered below, e.g. the async runtime failing to start.
+    Internal,
+    /// An invalid option value: unknown provider or saved location, or
+    /// out-of-range coordinates. Matches clap's own exit code for usage
+    /// errors, which are reported before `run` is ever reached.
+    Usage,
+    /// No config yet, an unreadable config directory, or no API token.
+    Config,
+    /// The location doesn't exist, or its name matches several places.
+    Location,
+    /// The provider rejected the API key.
+    Auth,
+    /// The provider couldn't be reached at all -- likely offline.
+    Network,
+    /// The provider's rate limit was hit.
+    RateLimited,
+    /// The provider answered, but with a server error or an unreadable
+    /// response.
+    Service,
+}
+
+impl ErrorKind {
+    /// Whether trying again later could succeed -- what `--watch` keeps
+    /// going through.
+    fn is_transient(self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Network | ErrorKind::RateLimited | ErrorKind::Service
+        )
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Internal => 1,
+            ErrorKind::Usage => 2,
+            ErrorKind::Config => 3,
+            ErrorKind::Location => 4,
+            ErrorKind::Auth => 5,
+            ErrorKind::Network => 6,
+            ErrorKind::RateLimited => 7,
+            ErrorKind::Service => 8,
+        }
+    }
+}
+
+/// A failed headless run: what `run` prints, and the exit code to print it
+/// with.
+#[derive(Debug, Serialize)]
+struct CliError {
+    kind: ErrorKind,
+    message: String,
+}
+
+impl CliError {
+    fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// `error`'s kind, with `context` ("Failed to fetch weather") in front
+    /// of its message.
+    fn from_api(context: &str, error: &ApiError) -> Self {
+        let kind = match error {
+            ApiError::RequestFailed(_) | ApiError::NotCached => ErrorKind::Network,
+            ApiError::CityNotFound | ApiError::UnsupportedLocation => ErrorKind::Location,
+            ApiError::Unauthorized { .. } => ErrorKind::Auth,
+            ApiError::RateLimited { .. } => ErrorKind::RateLimited,
+            ApiError::ServerError { .. } | ApiError::InvalidResponse => ErrorKind::Service,
+        };
+        Self::new(kind, format!("{context}: {error}"))
+    }
+}
+
+/// `provider`'s token: its own environment variable, then
+/// `TOKEN_ENV_VAR` if it's the `primary`, then the keychain. `None` if
+/// none of them has one.
+fn token_for(
+    config: &AppConfig,
+    provider: &WeatherApiProvider,
+    primary: &WeatherApiProvider,
+) -> Option<String> {
+    let env_vars = provider_token_env_var(provider)
+        .into_iter()
+        .chain((provider == primary).then_some(TOKEN_ENV_VAR));
+    env_vars
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|token| !token.is_empty())
+        .or_else(|| config.get_api_token(provider).ok())
+        .filter(|token| !token.is_empty())
+}
+
+fn parse_provider(value: &str) -> Result<WeatherApiProvider, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "openweather" | "open-weather" | "owm" => Ok(WeatherApiProvider::OpenWeather),
+        "google" | "google-weather" | "googleweather" => Ok(WeatherApiProvider::GoogleWeather),
+        "open-meteo" | "openmeteo" => Ok(WeatherApiProvider::OpenMeteo),
+        "nws" | "weather.gov" | "national-weather-service" => {
+            Ok(WeatherApiProvider::NationalWeatherService)
+        }
+        "met-norway" | "metno" | "met.no" | "yr" => Ok(WeatherApiProvider::MetNorway),
+        other => Err(format!(
+            "Unknown provider '{other}' -- expected \"openweather\", \"google\", \"open-meteo\", \"nws\", or \"met-norway\""
+        )),
+    }
+}
+
+/// Runs headless mode, or a subcommand, and exits the process directly -- `main()`'s fixed
+/// `iced::Result` return type has no good way to represent a CLI success/
+/// failure/exit-code, and this path never returns control to it anyway.
+pub fn run(cli: &Cli) -> ! {
+    let result = match &cli.command {
+        Some(Command::Manage(command)) => manage::run(command).map(|()| 0),
+        Some(Command::Completions { shell }) => completions::print_completions(*shell).map(|()| 0),
+        Some(Command::Man) => completions::print_man().map(|()| 0),
+        None => run_inner(cli),
+    };
+    let exit_code = match result {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            // A template prints failures as plain text anyway, so there's
+            // no need for the parsed one here.
+            print_error(Output::from_cli(cli, None), &error, cli.watch.is_some());
+            error.kind.exit_code()
+        }
+    };
+    std::process::exit(exit_code);
+}
+
+/// `Ok` carries the exit code of a run that printed its own results --
+/// nonzero only when some of several locations failed (see `run_several`).
+fn run_inner(cli: &Cli) -> Result<i32, CliError> {
+    // Before anything slow, so a typo fails fast.
+    let template = cli
+        .template
+        .as_deref()
+        .map(Template::parse)
+        .transpose()
+        .map_err(|e| CliError::new(ErrorKind::Usage, format!("Invalid --template: {e}")))?;
+
+    let config_manager = ConfigManager::new().map_err(|e| {
+        CliError::new(
+            ErrorKind::Config,
+            format!("Could not access config directory: {e}"),
+        )
+    })?;
+
+    if !config_manager.config_exists() {
+        return Err(CliError::new(
+            ErrorKind::Config,
+            "No configuration found yet. Run the app normally once to complete first-run setup \
+             (provider, API key, Home location), or create one with `config init`, before using \
+             --headless.",
+        ));
+    }
+
+    let config = config_manager.load_config();
+
+    let provider_type = match &cli.provider {
+        Some(value) => parse_provider(value).map_err(|e| CliError::new(ErrorKind::Usage, e))?,
+        None => config.weather_provider.clone(),
+    };
+
+    // Several locations, each by its saved name -- or `None` for the usual
+    // single one, with any overrides applied.
+    let several = if cli.all_locations {
+        if config.locations.is_empty() {
+            return Err(CliError::new(
+                ErrorKind::Config,
+                "No saved locations -- add some with `open-weather-wizard locations add` first",
+            ));
+        }
+        Some(
+            config
+                .locations
+                .iter()
+                .map(|saved| (saved.name.clone(), saved.location.clone()))
+                .collect::<Vec<_>>(),
+        )
+    } else if cli.location.len() > 1 {
+        if cli.watch.is_some()
+            || cli.format.is_some()
+            || cli.template.is_some()
+            || cli.alerts_only
+            || cli.city.is_some()
+            || cli.state.is_some()
+            || cli.country.is_some()
+            || cli.lat.is_some()
+        {
+            return Err(CliError::new(
+                ErrorKind::Usage,
+                "Several --location values only work with plain text or --json output, \
+                 without --watch or --city/--state/--country/--lat/--lon",
+            ));
+        }
+        Some(
+            cli.location
+                .iter()
+                .map(|name| saved_location(&config, name))
+                .collect::<Result<Vec<_>, _>>()?,
+        )
+    } else {
+        None
+    };
+
+    let location = match &several {
+        Some(_) => None,
+        None => Some(single_location(cli, &config)?),
+    };
+
+    // `--provider` replaces only the primary; configured fallbacks still
+    // apply behind it.
+    let chain = {
+        let mut config = config.clone();
+        config.weather_provider = provider_type.clone();
+        config.provider_chain()
+    };
+
+    // Every answer is saved for `--offline`/`--max-age` (and the GUI's
+    // next launch) to fall back on. Offline needs no provider, and so no
+    // token, at all.
+    let cache = ResponseCache::new(config_manager.cache_dir());
+    let client = http_settings(&config, cli.timeout)
+        .client()
+        .map_err(|e| CliError::new(ErrorKind::Config, e))?;
+    let endpoints = endpoints_with_env(&config, |name| std::env::var(name).ok())?;
+    let provider = if cli.offline {
+        CachingProvider::offline(cache)
+    } else {
+        // `create_chain` only asks for keyed providers' tokens, so a keyless
+        // query never touches the keychain -- on a machine with no Secret
+        // Service running, even a failed lookup can stall on D-Bus.
+        let chain = WeatherProviderFactory::create_chain(
+            &chain,
+            |provider| token_for(&config, provider, &provider_type),
+            config.language,
+            config.retry,
+            &client,
+            &endpoints,
+        )
+        .map_err(|e| {
+            let env_var = provider_token_env_var(&provider_type).unwrap_or(TOKEN_ENV_VAR);
+            CliError::new(
+                ErrorKind::Config,
+                format!(
+                    "{e} (set {env_var} or save one with `open-weather-wizard token set {}` first)",
+                    manage::provider_id(&provider_type)
+                ),
+            )
+        })?;
+        CachingProvider::new(chain, cache)
+    };
+    let provider = match cli.max_age {
+        Some(seconds) => provider.with_max_age(Duration::from_secs(seconds)),
+        None => provider,
+    };
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| {
+        CliError::new(
+            ErrorKind::Internal,
+            format!("Failed to start async runtime: {e}"),
+        )
+    })?;
+
+    let report = Report {
+        output: Output::from_cli(cli, template.as_ref()),
+        use_fahrenheit: config.use_fahrenheit,
+        alerts_only: cli.alerts_only,
+        min_severity: cli
+            .min_severity
+            .map(AlertSeverity::from)
+            .unwrap_or_default(),
+    };
+    let Some(mut location) = location else {
+        let locations = several.unwrap_or_default();
+        return runtime.block_on(run_several(
+            &provider,
+            &client,
+            endpoints.geocoding(),
+            locations,
+            cli.offline,
+            report,
+        ));
+    };
+
+    // Offline, the name stays as saved -- it's what the cache is keyed by.
+    if location.coordinates().is_none() && !cli.offline {
+        runtime.block_on(disambiguate(&client, endpoints.geocoding(), &mut location))?;
+    }
+
+    if let Some(seconds) = cli.watch {
+        let interval = refresh_interval(&provider_type, seconds.or(config.refresh_interval_secs));
+        return runtime
+            .block_on(watch(&provider, location, interval, report))
+            .map(|()| 0);
+    }
+
+    let update = runtime.block_on(fetch(&provider, &location, report))?;
+    print_update(report, &update, false).map(|()| 0)
+}
+
+/// The saved location called `name` (ignoring case), with its name as
+/// saved.
+fn saved_location(config: &AppConfig, name: &str) -> Result<(String, LocationConfig), CliError> {
+    config
+        .locations
+        .iter()
+        .find(|saved| saved.name.eq_ignore_ascii_case(name))
+        .map(|saved| (saved.name.clone(), saved.location.clone()))
+        .ok_or_else(|| {
+            let available = config
+                .locations
+                .iter()
+                .map(|saved| saved.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            CliError::new(
+                ErrorKind::Usage,
+                format!("No saved location named '{name}' (available: {available})"),
+            )
+        })
+}
+
+/// The one location a query is for: `--location`'s, or the current one,
+/// with `--city`/`--state`/`--country` or `--lat`/`--lon` on top.
+fn single_location(cli: &Cli, config: &AppConfig) -> Result<LocationConfig, CliError> {
+    let base_location = match cli.location.first() {
+        Some(name) => saved_location(config, name)?.1,
+        None => config.current_location(),
+    };
+
+    // Saved coordinates describe the saved place -- any override makes it
+    // a different one, to be geocoded afresh.
+    if let (Some(lat), Some(lon)) = (cli.lat, cli.lon) {
+        LocationConfig::from_coordinates(lat, lon).map_err(|e| CliError::new(ErrorKind::Usage, e))
+    } else if cli.city.is_some() || cli.state.is_some() || cli.country.is_some() {
+        Ok(LocationConfig::new(
+            cli.city.clone().unwrap_or(base_location.city),
+            cli.state.clone().unwrap_or(base_location.state),
+            cli.country.clone().unwrap_or(base_location.country),
+        ))
+    } else {
+        Ok(base_location)
+    }
+}
+
+/// Fetches every one of `locations` (named as saved) at once and prints
+/// them together. Exits `0` only if they all succeeded, otherwise with the
+/// first failed one's code -- its error is already in its row. `offline`
+/// skips disambiguating place names, which needs the network; `geocoding`
+/// is where to look them up (`Endpoints::geocoding`).
+async fn run_several(
+    provider: &(dyn WeatherProvider + Send + Sync),
+    client: &reqwest::Client,
+    geocoding: &str,
+    locations: Vec<(String, LocationConfig)>,
+    offline: bool,
+    report: Report<'_>,
+) -> Result<i32, CliError> {
+    let results = futures_util::future::join_all(locations.into_iter().map(
+        |(name, mut location)| async move {
+            let result = async {
+                if location.coordinates().is_none() && !offline {
+                    disambiguate(client, geocoding, &mut location).await?;
+                }
+                fetch(provider, &location, report).await
+            };
+            (name, result.await)
+        },
+    ))
+    .await;
+
+    match report.output {
+        Output::Json => print_json_several(&results)?,
+        _ => print!("{}", table(&results, report.use_fahrenheit)),
+    }
+    Ok(results
+        .iter()
+        .find_map(|(_, result)| result.as_ref().err())
+        .map_or(0, |error| error.kind.exit_code()))
+}
+
+/// What each refresh fetches and how it's printed -- everything `watch`
+/// repeats.
+#[derive(Debug, Clone, Copy)]
+struct Report<'a> {
+    output: Output<'a>,
+    use_fahrenheit: bool,
+    /// `--alerts-only`: skip the forecast, and print only the alerts.
+    alerts_only: bool,
+    /// `--min-severity`, or `UnknownSeverity` -- the least severe -- to
+    /// keep every alert.
+    min_severity: AlertSeverity,
+}
+
+/// How results (and failures) are printed, from `--json`/`--format`/
+/// `--template`.
+#[derive(Debug, Clone, Copy)]
+enum Output<'a> {
+    Text,
+    Json,
+    Bar(BarFormat),
+    Template(&'a Template),
+}
+
+impl<'a> Output<'a> {
+    /// `template` is `--template`, already parsed.
+    fn from_cli(cli: &Cli, template: Option<&'a Template>) -> Self {
+        match (template, cli.format, cli.json) {
+            (Some(template), _, _) => Output::Template(template),
+            (None, Some(format), _) => Output::Bar(format),
+            (None, None, true) => Output::Json,
+            (None, None, false) => Output::Text,
+        }
+    }
+}
+
+/// One refresh's results.
+struct Update {
+    weather: ApiResponse,
+    forecast: Option<ForecastResponse>,
+    /// Most severe first, already filtered by `--min-severity`. `None` if
+    /// they couldn't be fetched -- which `--json` keeps apart from `[]`, so
+    /// a script never mistakes a failed lookup for an all-clear.
+    alerts: Option<Vec<WeatherAlert>>,
+}
+
+impl Update {
+    fn alerts(&self) -> &[WeatherAlert] {
+        self.alerts.as_deref().unwrap_or_default()
+    }
+}
+
+/// One refresh: current conditions, plus the forecast and alerts if they're
+/// available. With `--alerts-only`, the forecast is skipped and an alerts
+/// failure fails the refresh -- they're all it prints. Current conditions
+/// are still fetched: they carry the place's UTC offset, for showing when
+/// an alert expires in local time.
+///
+/// All three requests run at once when `location` already has coordinates.
+/// Otherwise conditions go first and the others reuse the coordinates they
+/// came back with, rather than each geocoding the same name again.
+async fn fetch(
+    provider: &(dyn WeatherProvider + Send + Sync),
+    location: &LocationConfig,
+    report: Report<'_>,
+) -> Result<Update, CliError> {
+    let weather_request = || fetched("weather", provider.get_weather(location));
+    let (weather, forecast, alerts) = if location.coordinates().is_some() {
+        tokio::join!(
+            weather_request(),
+            fetch_forecast(provider, location, report),
+            fetch_alerts(provider, location, report),
+        )
+    } else {
+        let weather = weather_request().await?;
+        let mut resolved = location.clone();
+        if let Some(coord) = &weather.coord {
+            resolved.lat = Some(coord.lat);
+            resolved.lon = Some(coord.lon);
+            resolved.timezone = coord.timezone.clone();
+        }
+        let (forecast, alerts) = tokio::join!(
+            fetch_forecast(provider, &resolved, report),
+            fetch_alerts(provider, &resolved, report),
+        );
+        (Ok(weather), forecast, alerts)
+    };
+    Ok(Update {
+        weather: weather?,
+        forecast,
+        alerts: alerts?,
+    })
+}
+
+/// `fetch`'s forecast, or `None` if it's skipped or fails. A forecast
+/// failure shouldn't sink the whole command -- current conditions are still
+/// useful on their own, same philosophy as the GUI's ForecastStatus being
+/// independent of WeatherStatus (see src/app.rs).
+async fn fetch_forecast(
+    provider: &(dyn WeatherProvider + Send + Sync),
+    location: &LocationConfig,
+    report: Report<'_>,
+) -> Option<ForecastResponse> {
+    if report.alerts_only {
+        return None;
+    }
+    fetched("forecast", provider.get_forecast(location))
+        .await
+        .inspect_err(|e| log::warn!("Forecast unavailable: {}", e.message))
+        .ok()
+}
+
+/// `fetch`'s alerts, ranked -- `None` if they fail, unless they're all
+/// `--alerts-only` prints.
+async fn fetch_alerts(
+    provider: &(dyn WeatherProvider + Send + Sync),
+    location: &LocationConfig,
+    report: Report<'_>,
+) -> Result<Option<Vec<WeatherAlert>>, CliError> {
+    match fetched("alerts", provider.get_alerts(location)).await {
+        Ok(alerts) => Ok(Some(rank_alerts(alerts, report.min_severity))),
+        Err(e) if report.alerts_only => Err(e),
+        Err(e) => {
+            log::warn!("Alerts unavailable: {}", e.message);
+            Ok(None)
+        }
+    }
+}
+
+/// Awaits `request`, naming it (`what`, e.g. "weather") in its error's
+/// message. A request that timed out (see `http_settings`) fails as
+/// `Network`, the same as the provider not answering at all.
+async fn fetched<T>(
+    what: &str,
+    request: impl Future<Output = Result<T, ApiError>>,
+) -> Result<T, CliError> {
+    request
+        .await
+        .map_err(|e| CliError::from_api(&format!("Failed to fetch {what}"), &e))
+}
+
+/// `alerts` at least `min_severity`, most severe first (in the provider's
+/// order on a tie).
+fn rank_alerts(mut alerts: Vec<WeatherAlert>, min_severity: AlertSeverity) -> Vec<WeatherAlert> {
+    alerts.retain(|alert| alert.severity >= min_severity);
+    alerts.sort_by_key(|alert| std::cmp::Reverse(alert.severity));
+    alerts
+}
+
+/// Prints a successful refresh in `output`'s format -- one line per
+/// update while `watching`.
+fn print_update(report: Report<'_>, update: &Update, watching: bool) -> Result<(), CliError> {
+    let use_fahrenheit = report.use_fahrenheit;
+    match report.output {
+        Output::Text if report.alerts_only => print_alerts(&update.weather, update.alerts()),
+        Output::Text if watching => println!(
+            "{}",
+            text_line(&update.weather, update.alerts(), use_fahrenheit)
+        ),
+        Output::Text => print_text(update, use_fahrenheit),
+        Output::Json => print_json(update, report.alerts_only, !watching)?,
+        Output::Bar(format) => println!(
+            "{}",
+            bar::render(
+                format,
+                &WeatherStatus::Loaded(update.weather.clone()),
+                update.forecast.as_ref(),
+                update.alerts(),
+                use_fahrenheit,
+                watching,
+            )
+        ),
+        Output::Template(template) => println!(
+            "{}",
+            template.render(
+                &TemplateData {
+                    weather: &update.weather,
+                    forecast: update.forecast.as_ref(),
+                    alerts: update.alerts(),
+                },
+                use_fahrenheit,
+            )
+        ),
+    }
+    Ok(())
+}
+
+/// Prints a failure in `output`'s format: on stderr as text or for a
+/// template, on stdout otherwise, where the script or bar reading the
+/// output will see it.
+fn print_error(output: Output<'_>, error: &CliError, watching: bool) {
+    match output {
+        Output::Text | Output::Template(_) => eprintln!("Error: {}", error.message),
+        Output::Json => print_json_error(error, !watching),
+        Output::Bar(format) => println!(
+            "{}",
+            bar::render(
+                format,
+                &WeatherStatus::Error(error.message.clone()),
+                None,
+                &[],
+                false,
+                watching,
+            )
+        ),
+    }
+}
+
+/// `--watch`'s loop: fetch, print a line, sleep, until SIGINT/SIGTERM
+/// (exiting `0`). Failures that another attempt could fix -- network,
+/// rate-limit, and server errors -- print an error line and back off; any
+/// other failure (a rejected key, an unknown city) ends the loop with its
+/// usual exit code, since retrying can't change the answer.
+async fn watch(
+    provider: &(dyn WeatherProvider + Send + Sync),
+    mut location: LocationConfig,
+    interval: Duration,
+    report: Report<'_>,
+) -> Result<(), CliError> {
+    let mut shutdown = std::pin::pin!(shutdown_signal());
+    let mut failures = 0;
+    loop {
+        let result = tokio::select! {
+            result = fetch(provider, &location, report) => result,
+            () = &mut shutdown => return Ok(()),
+        };
+        match result {
+            Ok(update) => {
+                failures = 0;
+                // Same as the GUI's `LocationResolved`: geocode once, then
+                // reuse the coordinates the first answer came back with.
+                if location.coordinates().is_none()
+                    && let Some(coord) = &update.weather.coord
+                {
+                    location.lat = Some(coord.lat);
+                    location.lon = Some(coord.lon);
+                    location.timezone = coord.timezone.clone();
+                }
+                print_update(report, &update, true)?;
+            }
+            Err(error) if error.kind.is_transient() => {
+                failures += 1;
+                print_error(report.output, &error, true);
+            }
+            Err(error) => return Err(error),
+        }
+
+        tokio::select! {
+            () = tokio::time::sleep(backoff_interval(interval, failures)) => {}
+            () = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Resolves on Ctrl-C (SIGINT), or SIGTERM on Unix -- how a status bar or
+/// service manager stops a `--watch` process.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+            }
+            Err(e) => {
+                log::warn!("Couldn't listen for SIGTERM: {e}");
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Pins a not-yet-geocoded `location` to the one place its name can mean,
+/// or fails listing the candidates when it could mean several -- a script
+/// has nobody to show the GUI's picker to, and silently guessing
+/// "Springfield" prints a real but wrong forecast. Search failures and
+/// no-match answers are left for the provider itself to report. `base` is
+/// `Endpoints::geocoding`.
+async fn disambiguate(
+    client: &reqwest::Client,
+    base: &str,
+    location: &mut LocationConfig,
+) -> Result<(), CliError> {
+    let candidates = match geocoding::search(client, base, location).await {
+        Ok(candidates) => candidates,
+        Err(e) => {
+            log::warn!("Location search failed, leaving it to the provider: {e:?}");
+            return Ok(());
+        }
+    };
+    match geocoding::narrow(&candidates, &location.state) {
+        SearchOutcome::NotFound => Ok(()),
+        SearchOutcome::Unique(candidate) => {
+            location.lat = Some(candidate.lat);
+            location.lon = Some(candidate.lon);
+            location.timezone = candidate.timezone.clone();
+            Ok(())
+        }
+        SearchOutcome::Ambiguous(candidates) => {
+            let listed = candidates
+                .iter()
+                .map(|candidate| format!("\n  {candidate}"))
+                .collect::<String>();
+            Err(CliError::new(
+                ErrorKind::Location,
+                format!(
+                    "'{}' matches several places -- add --state/--country, or pass --lat/--lon:{listed}",
+                    location.city
+                ),
+            ))
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct HeadlessOutput<'a> {
+    weather: &'a ApiResponse,
+    forecast: Option<&'a ForecastResponse>,
+    alerts: Option<&'a [WeatherAlert]>,
+}
+
+/// `--json --alerts-only`'s output.
+#[derive(Serialize)]
+struct AlertsOutput<'a> {
+    alerts: &'a [WeatherAlert],
+}
+
+/// Prints `--json` output: pretty-printed for a one-shot run, one line per
+/// refresh (NDJSON) for `--watch`.
+fn print_json(update: &Update, alerts_only: bool, pretty: bool) -> Result<(), CliError> {
+    let json = if alerts_only {
+        to_json(
+            &AlertsOutput {
+                alerts: update.alerts(),
+            },
+            pretty,
+        )
+    } else {
+        to_json(
+            &HeadlessOutput {
+                weather: &update.weather,
+                forecast: update.forecast.as_ref(),
+                alerts: update.alerts.as_deref(),
+            },
+            pretty,
+        )
+    };
+    let json = json.map_err(|e| {
+        CliError::new(
+            ErrorKind::Internal,
+            format!("Failed to serialize output as JSON: {e}"),
+        )
+    })?;
+    println!("{json}");
+    Ok(())
+}
+
+/// One location's entry in `--json`'s output for several locations: its
+/// results, or its error, alongside its saved name.
+#[derive(Serialize)]
+struct LocationOutput<'a> {
+    location: &'a str,
+    #[serde(flatten)]
+    result: LocationResult<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum LocationResult<'a> {
+    Ok(HeadlessOutput<'a>),
+    Err(HeadlessError<'a>),
+}
+
+/// `--json`'s output for several locations: an array with an entry per
+/// location, in the order asked for.
+fn print_json_several(results: &[(String, Result<Update, CliError>)]) -> Result<(), CliError> {
+    let entries = results
+        .iter()
+        .map(|(name, result)| LocationOutput {
+            location: name,
+            result: match result {
+                Ok(update) => LocationResult::Ok(HeadlessOutput {
+                    weather: &update.weather,
+                    forecast: update.forecast.as_ref(),
+                    alerts: update.alerts.as_deref(),
+                }),
+                Err(error) => LocationResult::Err(HeadlessError { error }),
+            },
+        })
+        .collect::<Vec<_>>();
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| {
+        CliError::new(
+            ErrorKind::Internal,
+            format!("Failed to serialize output as JSON: {e}"),
+        )
+    })?;
+    println!("{json}");
+    Ok(())
+}
+
+fn to_json(value: &impl Serialize, pretty: bool) -> serde_json::Result<String> {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+}
+
+#[derive(Serialize)]
+struct HeadlessError<'a> {
+    error: &'a CliError,
+}
+
+/// `--json`'s counterpart of `run`'s "Error: ..." line, on stdout like a
+/// successful result so a script reads one stream either way. `pretty` as
+/// for `print_json`.
+fn print_json_error(error: &CliError, pretty: bool) {
+    match to_json(&HeadlessError { error }, pretty) {
+        Ok(json) => println!("{json}"),
+        // Two strings can't fail to serialize, but don't swallow the error
+        // if they somehow do.
+        Err(_) => eprintln!("Error: {}", error.message),
+    }
+}
+
+/// `--watch`'s one-line summary, e.g. "Chicago: 72°F, light rain (feels
+/// like 70°F), humidity 60%, wind 8 mph NW", plus any alerts' titles.
+fn text_line(weather: &ApiResponse, alerts: &[WeatherAlert], use_fahrenheit: bool) -> String {
+    let unit = unit_symbol(use_fahrenheit);
+    let temp = celsius_to_display(weather.main.temp, use_fahrenheit);
+    let feels_like = celsius_to_display(weather.main.feels_like, use_fahrenheit);
+    let wind_speed = speed_to_display(weather.wind.speed, use_fahrenheit);
+    let description = weather
+        .weather
+        .first()
+        .map(|condition| format!(", {}", condition.description))
+        .unwrap_or_default();
+    let alerts = if alerts.is_empty() {
+        String::new()
+    } else {
+        let titles = alerts
+            .iter()
+            .map(|alert| alert.title.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" -- \u{26a0} {titles}")
+    };
+    format!(
+        "{}: {temp:.0}{unit}{description} (feels like {feels_like:.0}{unit}), humidity {}%, wind {wind_speed:.0} {} {}{alerts}",
+        weather.name,
+        weather.main.humidity,
+        speed_unit(use_fahrenheit),
+        compass_direction(weather.wind.deg),
+    )
+}
+
+/// Text output for several locations: a row per location, columns padded
+/// to line up, with a failed location's error in place of its weather.
+fn table(results: &[(String, Result<Update, CliError>)], use_fahrenheit: bool) -> String {
+    let unit = unit_symbol(use_fahrenheit);
+    let header: Vec<String> = [
+        "Location",
+        "Temp",
+        "High/Low",
+        "Conditions",
+        "Humidity",
+        "Wind",
+        "Alerts",
+    ]
+    .map(str::to_string)
+    .into();
+    let rows = results.iter().map(|(name, result)| {
+        let Ok(update) = result else {
+            return vec![name.clone()];
+        };
+        let weather = &update.weather;
+        let high_low = update
+            .forecast
+            .as_ref()
+            .and_then(|forecast| forecast.days.first())
+            .map(|today| {
+                let hi = celsius_to_display(today.temp_max, use_fahrenheit);
+                let lo = celsius_to_display(today.temp_min, use_fahrenheit);
+                format!("{hi:.0}{unit} / {lo:.0}{unit}")
+            })
+            .unwrap_or_default();
+        let alerts = match &update.alerts {
+            None => "?".to_string(),
+            Some(alerts) => match alerts.first() {
+                None => String::new(),
+                Some(worst) if alerts.len() == 1 => format!("\u{26a0} {}", worst.title),
+                Some(worst) => format!("\u{26a0} {} (+{})", worst.title, alerts.len() - 1),
+            },
+        };
+        vec![
+            name.clone(),
+            format!(
+                "{:.0}{unit}",
+                celsius_to_display(weather.main.temp, use_fahrenheit)
+            ),
+            high_low,
+            weather
+                .weather
+                .first()
+                .map(|condition| condition.description.clone())
+                .unwrap_or_default(),
+            format!("{}%", weather.main.humidity),
+            format!(
+                "{:.0} {} {}",
+                speed_to_display(weather.wind.speed, use_fahrenheit),
+                speed_unit(use_fahrenheit),
+                compass_direction(weather.wind.deg)
+            ),
+            alerts,
+        ]
+    });
+    let rows = std::iter::once(header).chain(rows).collect::<Vec<_>>();
+
+    // Failed rows only have their name, and don't widen the other columns.
+    let mut widths = vec![0; rows[0].len()];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let errors =
+        std::iter::once(None).chain(results.iter().map(|(_, result)| result.as_ref().err()));
+    let mut table = String::new();
+    for (row, error) in rows.iter().zip(errors) {
+        let mut line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        if let Some(error) = error {
+            line.push_str(&format!("  Error: {}", error.message));
+        }
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+/// One alert, e.g. "[Extreme] Tornado Warning -- Peoria County, until Thu
+/// 3:00 PM", with times local to the place (`tz_offset_secs` from UTC).
+fn alert_line(alert: &WeatherAlert, tz_offset_secs: i64) -> String {
+    let mut line = format!("[{}] {}", alert.severity, alert.title);
+    if !alert.area_name.is_empty() {
+        line.push_str(&format!(" -- {}", alert.area_name));
+    }
+    // Zero when the provider didn't say.
+    if alert.end_time > 0 {
+        line.push_str(&format!(
+            ", until {}",
+            format_local_datetime(alert.end_time, tz_offset_secs)
+        ));
+    }
+    line
+}
+
+/// `--alerts-only`'s text output: a count, then a line per alert.
+fn print_alerts(weather: &ApiResponse, alerts: &[WeatherAlert]) {
+    match alerts.len() {
+        0 => println!("{}: no active alerts", weather.name),
+        1 => println!("{}: 1 active alert", weather.name),
+        n => println!("{}: {n} active alerts", weather.name),
+    }
+    for alert in alerts {
+        println!("  {}", alert_line(alert, weather.timezone));
+    }
+}
+
+fn print_text(update: &Update, use_fahrenheit: bool) {
+    let weather = &update.weather;
+    let unit = unit_symbol(use_fahrenheit);
+    let temp = celsius_to_display(weather.main.temp, use_fahrenheit);
+    let feels_like = celsius_to_display(weather.main.feels_like, use_fahrenheit);
+    let wind_speed = speed_to_display(weather.wind.speed, use_fahrenheit);
+    let wind_unit = speed_unit(use_fahrenheit);
+    let compass = compass_direction(weather.wind.deg);
+    let visibility = distance_to_display(weather.visibility as f64, use_fahrenheit);
+    let visibility_unit = distance_unit(use_fahrenheit);
+    let sunrise = format_local_time(weather.sys.sunrise, weather.timezone);
+    let sunset = format_local_time(weather.sys.sunset, weather.timezone);
+
+    println!("{}", weather.name);
+    if let Some(condition) = weather.weather.first() {
+        println!("{}", condition.description);
+    }
+    println!("Temperature:  {temp:.1}{unit} (feels like {feels_like:.0}{unit})");
+    println!("Humidity:     {}%", weather.main.humidity);
+    println!("Wind:         {wind_speed:.0} {wind_unit} {compass}");
+    println!("Pressure:     {} hPa", weather.main.pressure);
+    println!("Visibility:   {visibility:.1} {visibility_unit}");
+    println!("Sunrise:      {sunrise}");
+    println!("Sunset:       {sunset}");
+
+    if !update.alerts().is_empty() {
+        println!("\nAlerts:");
+        for alert in update.alerts() {
+            println!("  {}", alert_line(alert, weather.timezone));
+        }
+    }
+
+    if let Some(forecast) = &update.forecast
+        && !forecast.days.is_empty()
+    {
+        println!("\nForecast:");
+        for day in &forecast.days {
+            let hi = celsius_to_display(day.temp_max, use_fahrenheit);
+            let lo = celsius_to_display(day.temp_min, use_fahrenheit);
+            println!(
+                "  {}: {hi:.0}{unit} / {lo:.0}{unit} -- {}",
+                day.date, day.description
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(title: &str, severity: AlertSeverity, end_time: i64) -> WeatherAlert {
+        WeatherAlert {
+            id: title.to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            event_type: String::new(),
+            severity,
+            start_time: 0,
+            end_time,
+            urgency: String::new(),
+            certainty: String::new(),
+            area_name: "Peoria County".to_string(),
+            instruction: vec![],
+            safety_recommendations: vec![],
+        }
+    }
+
+    #[test]
+    fn test_rank_alerts_filters_and_puts_the_worst_first() {
+        let alerts = vec![
+            alert("Heat Advisory", AlertSeverity::Moderate, 0),
+            alert("Special Statement", AlertSeverity::UnknownSeverity, 0),
+            alert("Tornado Warning", AlertSeverity::Extreme, 0),
+            alert("Flood Warning", AlertSeverity::Severe, 0),
+            alert("Flash Flood Warning", AlertSeverity::Severe, 0),
+        ];
+        let titles = |alerts: Vec<WeatherAlert>| {
+            alerts
+                .into_iter()
+                .map(|alert| alert.title)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            titles(rank_alerts(alerts.clone(), AlertSeverity::default())),
+            [
+                "Tornado Warning",
+                "Flood Warning",
+                "Flash Flood Warning",
+                "Heat Advisory",
+                "Special Statement"
+            ]
+        );
+        assert_eq!(
+            titles(rank_alerts(alerts.clone(), MinSeverity::Severe.into())),
+            ["Tornado Warning", "Flood Warning", "Flash Flood Warning"]
+        );
+        assert_eq!(
+            titles(rank_alerts(alerts, MinSeverity::Extreme.into())),
+            ["Tornado Warning"]
+        );
+    }
+
+    #[test]
+    fn test_table_lines_up_columns_and_shows_errors_in_place() {
+        use crate::weather_api::openweather_api::{Main, Sys, Weather, Wind};
+        let weather = ApiResponse {
+            weather: vec![Weather {
+                main: "Rain".to_string(),
+                description: "light rain".to_string(),
+            }],
+            main: Main {
+                temp: 20.4,
+                feels_like: 19.0,
+                temp_min: 15.0,
+                temp_max: 25.0,
+                pressure: 1013,
+                humidity: 80,
+            },
+            wind: Wind {
+                speed: 3.0,
+                deg: 180,
+            },
+            visibility: 10_000,
+            sys: Sys {
+                sunrise: 0,
+                sunset: 0,
+            },
+            timezone: 0,
+            name: "Peoria".to_string(),
+            coord: None,
+            provider: None,
+        };
+        let results = vec![
+            (
+                "Headquarters".to_string(),
+                Ok(Update {
+                    weather,
+                    forecast: None,
+                    alerts: Some(vec![alert("Flood Warning", AlertSeverity::Severe, 0)]),
+                }),
+            ),
+            (
+                "Lab".to_string(),
+                Err(CliError::new(ErrorKind::Location, "City not found")),
+            ),
+        ];
+        assert_eq!(
+            table(&results, false),
+            "Location      Temp  High/Low  Conditions  Humidity  Wind       Alerts\n\
+             Headquarters  20°C            light rain  80%       11 km/h S  \u{26a0} Flood Warning\n\
+             Lab           Error: City not found\n"
+        );
+    }
+
+    #[test]
+    fn test_timeout_flag_replaces_only_the_request_timeout() {
+        let mut config = AppConfig::default();
+        config.http.proxy = Some("socks5h://127.0.0.1:9050".to_string());
+        assert_eq!(http_settings(&config, None), config.http);
+
+        let http = http_settings(&config, Some(5));
+        assert_eq!(http.timeout_secs, 5);
+        assert_eq!(http.connect_timeout_secs, config.http.connect_timeout_secs);
+        assert_eq!(http.proxy, config.http.proxy);
+    }
+
+    #[tokio::test]
+    async fn test_fetched_names_the_failed_request() {
+        let error = fetched("forecast", async { Err::<(), _>(ApiError::CityNotFound) })
+            .await
+            .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Location);
+        assert!(error.message.starts_with("Failed to fetch forecast"));
+    }
+
+    #[test]
+    fn test_alert_line_shows_expiry_in_local_time() {
+        // 2026-07-02 20:00 UTC is 3:00 PM in Peoria (UTC-5).
+        let warning = alert("Tornado Warning", AlertSeverity::Extreme, 1_783_022_400);
+        assert_eq!(
+            alert_line(&warning, -18_000),
+            "[Extreme] Tornado Warning -- Peoria County, until Thu 3:00 PM"
+        );
+        let open_ended = alert("Special Statement", AlertSeverity::UnknownSeverity, 0);
+        assert_eq!(
+            alert_line(&open_ended, -18_000),
+            "[Unknown] Special Statement -- Peoria County"
+        );
+    }
+
+    #[test]
+    fn test_endpoint_variables_override_the_config() {
+        let mut config = AppConfig::default();
+        config.endpoints.open_meteo = Some("https://meteo.example.org/v1".to_string());
+        config.endpoints.nws = Some("https://nws.example.org".to_string());
+        let endpoints = endpoints_with_env(&config, |name| match name {
+            "OPEN_WEATHER_WIZARD_OPEN_METEO_API_BASE" => Some("http://127.0.0.1:8080/v1".into()),
+            "OPEN_WEATHER_WIZARD_NWS_API_BASE" => Some(String::new()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(endpoints.open_meteo(), "http://127.0.0.1:8080/v1");
+        assert_eq!(endpoints.nws(), "https://nws.example.org");
+        assert_eq!(endpoints.geocoding(), Endpoints::default().geocoding());
+
+        let error = endpoints_with_env(&AppConfig::default(), |name| {
+            (name == "OPEN_WEATHER_WIZARD_GOOGLE_API_BASE").then(|| "localhost:8080".to_string())
+        })
+        .unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Config);
+        assert!(
+            error.message.contains("google_weather"),
+            "{}",
+            error.message
+        );
+    }
+}
@@ -0,0 +1,725 @@
+//! # Output Templates
+//!
+//! `--template` lays out headless output however a prompt or bar wants it,
+//! e.g. `"{icon} {temp}{unit} {description} ↑{hi} ↓{lo}"`. The syntax:
+//!
+//! - `{name}` -- a placeholder (see `CURRENT_FIELDS`, `DAY_FIELDS`,
+//!   `ALERT_FIELDS`), in the configured units.
+//! - `{name:spec}` -- Rust-style `[[fill]align][width][.precision]`, e.g.
+//!   `{temp:.1}` or `{location:<12}`. Precision is decimal places for
+//!   numbers and a character limit for text.
+//! - `{name!metric}` / `{name!imperial}` -- that one value in the other
+//!   units (combines with a spec: `{temp!imperial:.0}`).
+//! - `{?name}...{/}` -- the enclosed part only if `name` has a value (text
+//!   that isn't empty, a number that isn't zero); `{^name}...{/}` only if it
+//!   doesn't. Sections nest.
+//! - `{{` and `}}` -- literal braces.
+//!
+//! Templates are parsed once up front, so a typo fails immediately (as a
+//! usage error) rather than on every `--watch` refresh. Values go through
+//! the same `ui::temperature` helpers as the GUI, so units and rounding
+//! match it.
+
+use crate::ui::temperature::{
+    celsius_to_display, compass_direction, distance_to_display, distance_unit,
+    format_local_datetime, format_local_time, pressure_to_display, pressure_unit, speed_to_display,
+    speed_unit, unit_symbol,
+};
+use crate::weather_api::alerts::WeatherAlert;
+use crate::weather_api::forecast::{ForecastDay, ForecastResponse};
+use crate::weather_api::openweather_api::{ApiResponse, WeatherSymbol, get_weather_symbol};
+
+/// Placeholders for current conditions, plus today's forecast shorthands
+/// (`hi`, `lo`, `pop`) and `alert_count`.
+pub const CURRENT_FIELDS: &[&str] = &[
+    "location",
+    "description",
+    "condition",
+    "icon",
+    "temp",
+    "feels_like",
+    "temp_min",
+    "temp_max",
+    "humidity",
+    "pressure",
+    "wind_speed",
+    "wind_deg",
+    "wind_dir",
+    "visibility",
+    "sunrise",
+    "sunset",
+    "utc_offset",
+    "lat",
+    "lon",
+    "provider",
+    "unit",
+    "speed_unit",
+    "distance_unit",
+    "pressure_unit",
+    "hi",
+    "lo",
+    "pop",
+    "alert_count",
+];
+
+/// Placeholders for one forecast day, prefixed `day0.` (today) through
+/// `day4.`.
+pub const DAY_FIELDS: &[&str] = &[
+    "date",
+    "hi",
+    "lo",
+    "description",
+    "condition",
+    "icon",
+    "feels_like",
+    "humidity",
+    "wind_speed",
+    "wind_deg",
+    "wind_dir",
+    "pressure",
+    "visibility",
+    "pop",
+];
+
+/// Placeholders for the most severe active alert, prefixed `alert.`.
+pub const ALERT_FIELDS: &[&str] = &[
+    "title",
+    "event",
+    "severity",
+    "urgency",
+    "certainty",
+    "area",
+    "start",
+    "end",
+    "description",
+    "instruction",
+    "safety",
+    "id",
+];
+
+/// Everything a template can draw from -- one refresh's results.
+pub struct TemplateData<'a> {
+    pub weather: &'a ApiResponse,
+    pub forecast: Option<&'a ForecastResponse>,
+    pub alerts: &'a [WeatherAlert],
+}
+
+/// A parsed `--template`.
+#[derive(Debug)]
+pub struct Template {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Literal(String),
+    Field(Field),
+    Section {
+        name: String,
+        inverted: bool,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug)]
+struct Field {
+    name: String,
+    /// `Some(fahrenheit)` from `!imperial`/`!metric`, overriding the config.
+    fahrenheit: Option<bool>,
+    spec: Spec,
+}
+
+#[derive(Debug, Default)]
+struct Spec {
+    fill: Option<char>,
+    align: Option<Align>,
+    width: usize,
+    precision: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// A `{?name}`/`{^name}` that `Template::parse` hasn't met the `{/}` for
+/// yet.
+struct OpenSection {
+    name: String,
+    inverted: bool,
+    body: Vec<Node>,
+}
+
+/// A placeholder's value, before `Spec` formatting.
+enum Value {
+    Text(String),
+    /// A number and the decimal places it gets without a precision -- the
+    /// GUI's own, e.g. none for temperatures, one for visibility.
+    Number(f64, usize),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::Text(text) => !text.is_empty(),
+            Value::Number(number, _) => *number != 0.0,
+        }
+    }
+}
+
+impl Template {
+    /// Parses `source`, failing on unbalanced braces or sections, an unknown
+    /// placeholder, or a malformed spec.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut chars = source.chars().peekable();
+        let mut nodes = Vec::new();
+        // Innermost last.
+        let mut open: Vec<OpenSection> = Vec::new();
+        let mut literal = String::new();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '}' => {
+                    return Err("unmatched '}' in template (write '}}' for a literal one)".into());
+                }
+                '{' => {
+                    let mut tag = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => tag.push(c),
+                            None => return Err(format!("unclosed '{{{tag}' in template")),
+                        }
+                    }
+                    let current = open
+                        .last_mut()
+                        .map_or(&mut nodes, |section| &mut section.body);
+                    if !literal.is_empty() {
+                        current.push(Node::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    if let Some(name) = tag.strip_prefix('?').or_else(|| tag.strip_prefix('^')) {
+                        validate_name(name)?;
+                        open.push(OpenSection {
+                            name: name.to_string(),
+                            inverted: tag.starts_with('^'),
+                            body: Vec::new(),
+                        });
+                    } else if tag.starts_with('/') {
+                        let Some(section) = open.pop() else {
+                            return Err(format!(
+                                "'{{{tag}}}' closes a section that was never opened"
+                            ));
+                        };
+                        open.last_mut()
+                            .map_or(&mut nodes, |parent| &mut parent.body)
+                            .push(Node::Section {
+                                name: section.name,
+                                inverted: section.inverted,
+                                body: section.body,
+                            });
+                    } else {
+                        current.push(Node::Field(parse_field(&tag)?));
+                    }
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if let Some(section) = open.last() {
+            return Err(format!(
+                "section '{}' is never closed with '{{/}}'",
+                section.name
+            ));
+        }
+        if !literal.is_empty() {
+            nodes.push(Node::Literal(literal));
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Fills the template in, in `fahrenheit`'s units unless a placeholder
+    /// says otherwise. Placeholders with no value (no forecast, no alert)
+    /// render as nothing.
+    pub fn render(&self, data: &TemplateData, fahrenheit: bool) -> String {
+        let mut output = String::new();
+        render_nodes(&self.nodes, data, fahrenheit, &mut output);
+        output
+    }
+}
+
+fn render_nodes(nodes: &[Node], data: &TemplateData, fahrenheit: bool, output: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Literal(text) => output.push_str(text),
+            Node::Field(field) => {
+                let fahrenheit = field.fahrenheit.unwrap_or(fahrenheit);
+                if let Some(value) = lookup(&field.name, data, fahrenheit) {
+                    output.push_str(&field.spec.apply(value));
+                }
+            }
+            Node::Section {
+                name,
+                inverted,
+                body,
+            } => {
+                let present = lookup(name, data, fahrenheit).is_some_and(|v| v.is_truthy());
+                if present != *inverted {
+                    render_nodes(body, data, fahrenheit, output);
+                }
+            }
+        }
+    }
+}
+
+/// `name!units:spec`.
+fn parse_field(tag: &str) -> Result<Field, String> {
+    let (head, spec) = match tag.split_once(':') {
+        Some((head, spec)) => (head, parse_spec(spec)?),
+        None => (tag, Spec::default()),
+    };
+    let (name, fahrenheit) = match head.split_once('!') {
+        Some((name, "metric")) => (name, Some(false)),
+        Some((name, "imperial")) => (name, Some(true)),
+        Some((_, other)) => {
+            return Err(format!(
+                "unknown units '!{other}' in '{{{tag}}}' -- expected !metric or !imperial"
+            ));
+        }
+        None => (head, None),
+    };
+    validate_name(name)?;
+    Ok(Field {
+        name: name.to_string(),
+        fahrenheit,
+        spec,
+    })
+}
+
+/// `[[fill]align][width][.precision]`.
+fn parse_spec(spec: &str) -> Result<Spec, String> {
+    let align_of = |c: char| match c {
+        '<' => Some(Align::Left),
+        '>' => Some(Align::Right),
+        '^' => Some(Align::Center),
+        _ => None,
+    };
+    let mut rest = spec;
+    let mut parsed = Spec::default();
+    let mut leading = rest.chars();
+    let (first, second) = (leading.next(), leading.next());
+    if let (Some(fill), Some(align)) = (first, second.and_then(align_of)) {
+        parsed.fill = Some(fill);
+        parsed.align = Some(align);
+        rest = &rest[fill.len_utf8() + 1..];
+    } else if let Some(align) = first.and_then(align_of) {
+        parsed.align = Some(align);
+        rest = &rest[1..];
+    }
+
+    let (width, precision) = match rest.split_once('.') {
+        Some((width, precision)) => (width, Some(precision)),
+        None => (rest, None),
+    };
+    let invalid =
+        || format!("invalid format spec ':{spec}' -- expected [[fill]align][width][.precision]");
+    if !width.is_empty() {
+        parsed.width = width.parse().map_err(|_| invalid())?;
+    }
+    if let Some(precision) = precision {
+        parsed.precision = Some(precision.parse().map_err(|_| invalid())?);
+    }
+    Ok(parsed)
+}
+
+impl Spec {
+    fn apply(&self, value: Value) -> String {
+        let text = match value {
+            Value::Number(number, default_precision) => {
+                format!("{number:.*}", self.precision.unwrap_or(default_precision))
+            }
+            Value::Text(text) => match self.precision {
+                Some(limit) => text.chars().take(limit).collect(),
+                None => text,
+            },
+        };
+        let padding = self.width.saturating_sub(text.chars().count());
+        if padding == 0 {
+            return text;
+        }
+        let fill = self.fill.unwrap_or(' ');
+        let pad = |n: usize| std::iter::repeat_n(fill, n).collect::<String>();
+        match self.align.unwrap_or(Align::Left) {
+            Align::Left => format!("{text}{}", pad(padding)),
+            Align::Right => format!("{}{text}", pad(padding)),
+            Align::Center => format!("{}{text}{}", pad(padding / 2), pad(padding - padding / 2)),
+        }
+    }
+}
+
+/// Checks `name` against the placeholder lists, so `render` never meets an
+/// unknown one.
+fn validate_name(name: &str) -> Result<(), String> {
+    let known = match split_name(name) {
+        Scope::Current(field) => CURRENT_FIELDS.contains(&field),
+        Scope::Day(index, field) => index < 5 && DAY_FIELDS.contains(&field),
+        Scope::Alert(field) => ALERT_FIELDS.contains(&field),
+        Scope::Unknown => false,
+    };
+    if known {
+        Ok(())
+    } else {
+        Err(format!(
+            "unknown placeholder '{name}' -- see the README for the list"
+        ))
+    }
+}
+
+enum Scope<'a> {
+    Current(&'a str),
+    Day(usize, &'a str),
+    Alert(&'a str),
+    Unknown,
+}
+
+fn split_name(name: &str) -> Scope<'_> {
+    match name.split_once('.') {
+        None => Scope::Current(name),
+        Some(("alert", field)) => Scope::Alert(field),
+        Some((day, field)) => match day.strip_prefix("day").and_then(|n| n.parse().ok()) {
+            Some(index) => Scope::Day(index, field),
+            None => Scope::Unknown,
+        },
+    }
+}
+
+/// An emoji for a condition -- `{icon}`, where a terminal or bar can't show
+/// the GUI's animated icons.
+fn symbol_emoji(symbol: WeatherSymbol) -> &'static str {
+    match symbol {
+        WeatherSymbol::Clear => "\u{2600}\u{fe0f}",
+        WeatherSymbol::Clouds => "\u{2601}\u{fe0f}",
+        WeatherSymbol::Rain => "\u{1f327}\u{fe0f}",
+        WeatherSymbol::Drizzle => "\u{1f326}\u{fe0f}",
+        WeatherSymbol::Thunderstorm => "\u{26c8}\u{fe0f}",
+        WeatherSymbol::Snow => "\u{2744}\u{fe0f}",
+        WeatherSymbol::Mist
+        | WeatherSymbol::Smoke
+        | WeatherSymbol::Haze
+        | WeatherSymbol::Dust
+        | WeatherSymbol::Fog
+        | WeatherSymbol::Sand
+        | WeatherSymbol::Ash => "\u{1f32b}\u{fe0f}",
+        WeatherSymbol::Squall => "\u{1f32c}\u{fe0f}",
+        WeatherSymbol::Tornado => "\u{1f32a}\u{fe0f}",
+        WeatherSymbol::Default => "\u{1f321}\u{fe0f}",
+    }
+}
+
+/// The alert `alert.*` describes: the most severe one, first listed on a
+/// tie.
+fn top_alert(alerts: &[WeatherAlert]) -> Option<&WeatherAlert> {
+    alerts.iter().rev().max_by_key(|alert| alert.severity)
+}
+
+fn text(value: impl Into<String>) -> Option<Value> {
+    Some(Value::Text(value.into()))
+}
+
+fn number(value: f64, default_precision: usize) -> Option<Value> {
+    Some(Value::Number(value, default_precision))
+}
+
+fn lookup(name: &str, data: &TemplateData, f: bool) -> Option<Value> {
+    let weather = data.weather;
+    let today = data.forecast.and_then(|forecast| forecast.days.first());
+    match split_name(name) {
+        Scope::Current(field) => match field {
+            "location" => text(&weather.name),
+            "description" => text(&weather.weather.first()?.description),
+            "condition" => text(&weather.weather.first()?.main),
+            "icon" => text(symbol_emoji(get_weather_symbol(
+                &weather.weather.first()?.main,
+            ))),
+            "temp" => number(celsius_to_display(weather.main.temp, f), 0),
+            "feels_like" => number(celsius_to_display(weather.main.feels_like, f), 0),
+            "temp_min" => number(celsius_to_display(weather.main.temp_min, f), 0),
+            "temp_max" => number(celsius_to_display(weather.main.temp_max, f), 0),
+            "humidity" => number(weather.main.humidity as f64, 0),
+            "pressure" => number(
+                pressure_to_display(weather.main.pressure, f),
+                if f { 2 } else { 0 },
+            ),
+            "wind_speed" => number(speed_to_display(weather.wind.speed, f), 0),
+            "wind_deg" => number(weather.wind.deg as f64, 0),
+            "wind_dir" => text(compass_direction(weather.wind.deg)),
+            "visibility" => number(distance_to_display(weather.visibility as f64, f), 1),
+            "sunrise" => text(format_local_time(weather.sys.sunrise, weather.timezone)),
+            "sunset" => text(format_local_time(weather.sys.sunset, weather.timezone)),
+            "utc_offset" => {
+                let sign = if weather.timezone < 0 { '-' } else { '+' };
+                let minutes = weather.timezone.abs() / 60;
+                text(format!("{sign}{:02}:{:02}", minutes / 60, minutes % 60))
+            }
+            "lat" => number(weather.coord.as_ref()?.lat, 4),
+            "lon" => number(weather.coord.as_ref()?.lon, 4),
+            "provider" => text(weather.provider.as_ref()?.to_string()),
+            "unit" => text(unit_symbol(f)),
+            "speed_unit" => text(speed_unit(f)),
+            "distance_unit" => text(distance_unit(f)),
+            "pressure_unit" => text(pressure_unit(f)),
+            "hi" => number(celsius_to_display(today?.temp_max, f), 0),
+            "lo" => number(celsius_to_display(today?.temp_min, f), 0),
+            "pop" => number(today?.pop * 100.0, 0),
+            "alert_count" => number(data.alerts.len() as f64, 0),
+            _ => None,
+        },
+        Scope::Day(index, field) => day_value(data.forecast?.days.get(index)?, field, f),
+        Scope::Alert(field) => {
+            let alert = top_alert(data.alerts)?;
+            match field {
+                "title" => text(&alert.title),
+                "event" => text(&alert.event_type),
+                "severity" => text(alert.severity.to_string()),
+                "urgency" => text(&alert.urgency),
+                "certainty" => text(&alert.certainty),
+                "area" => text(&alert.area_name),
+                // Zero when the provider didn't say.
+                "start" if alert.start_time > 0 => {
+                    text(format_local_datetime(alert.start_time, weather.timezone))
+                }
+                "end" if alert.end_time > 0 => {
+                    text(format_local_datetime(alert.end_time, weather.timezone))
+                }
+                "description" => text(&alert.description),
+                "instruction" => text(alert.instruction.join(" ")),
+                "safety" => text(alert.safety_recommendations.join(" ")),
+                "id" => text(&alert.id),
+                _ => None,
+            }
+        }
+        Scope::Unknown => None,
+    }
+}
+
+fn day_value(day: &ForecastDay, field: &str, f: bool) -> Option<Value> {
+    match field {
+        "date" => text(&day.date),
+        "hi" => number(celsius_to_display(day.temp_max, f), 0),
+        "lo" => number(celsius_to_display(day.temp_min, f), 0),
+        "description" => text(&day.description),
+        "condition" => text(format!("{:?}", day.symbol)),
+        "icon" => text(symbol_emoji(day.symbol)),
+        "feels_like" => number(celsius_to_display(day.feels_like, f), 0),
+        "humidity" => number(day.humidity as f64, 0),
+        "wind_speed" => number(speed_to_display(day.wind_speed, f), 0),
+        "wind_deg" => number(day.wind_deg as f64, 0),
+        "wind_dir" => text(compass_direction(day.wind_deg)),
+        "pressure" => number(pressure_to_display(day.pressure, f), if f { 2 } else { 0 }),
+        "visibility" => number(distance_to_display(day.visibility as f64, f), 1),
+        "pop" => number(day.pop * 100.0, 0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WeatherApiProvider;
+    use crate::weather_api::alerts::AlertSeverity;
+    use crate::weather_api::openweather_api::{Coord, Main, Sys, Weather, Wind};
+
+    fn weather() -> ApiResponse {
+        ApiResponse {
+            weather: vec![Weather {
+                main: "Rain".to_string(),
+                description: "light rain".to_string(),
+            }],
+            main: Main {
+                temp: 20.4,
+                feels_like: 19.0,
+                temp_min: 15.0,
+                temp_max: 25.0,
+                pressure: 1013,
+                humidity: 80,
+            },
+            wind: Wind {
+                speed: 3.0,
+                deg: 180,
+            },
+            visibility: 10_000,
+            sys: Sys {
+                sunrise: 1_782_986_400,
+                sunset: 1_783_040_400,
+            },
+            timezone: -18_000,
+            name: "Peoria".to_string(),
+            coord: Some(Coord {
+                lat: 40.6936,
+                lon: -89.589,
+                timezone: None,
+            }),
+            provider: Some(WeatherApiProvider::OpenMeteo),
+        }
+    }
+
+    fn forecast() -> ForecastResponse {
+        let day = |date: &str, pop: f64| ForecastDay {
+            date: date.to_string(),
+            temp_min: 15.0,
+            temp_max: 25.0,
+            description: "light rain".to_string(),
+            symbol: WeatherSymbol::Rain,
+            feels_like: 19.0,
+            humidity: 80,
+            wind_speed: 3.0,
+            wind_deg: 180,
+            pressure: 1013,
+            visibility: 10_000,
+            pop,
+        };
+        ForecastResponse {
+            location_name: "Peoria".to_string(),
+            days: (0..5)
+                .map(|n| day(&format!("2026-07-0{}", n + 2), 0.64))
+                .collect(),
+            provider: None,
+        }
+    }
+
+    fn alert(title: &str, severity: AlertSeverity) -> WeatherAlert {
+        WeatherAlert {
+            id: title.to_lowercase(),
+            title: title.to_string(),
+            description: "Storms likely.".to_string(),
+            event_type: "Thunderstorm".to_string(),
+            severity,
+            // Thu 2026-07-02 3:00 PM CDT.
+            start_time: 1_783_022_400,
+            end_time: 1_783_022_400,
+            urgency: "Immediate".to_string(),
+            certainty: "Likely".to_string(),
+            area_name: "Peoria County".to_string(),
+            instruction: vec!["Go indoors.".to_string()],
+            safety_recommendations: vec!["Avoid windows.".to_string()],
+        }
+    }
+
+    fn render(
+        source: &str,
+        forecast: Option<&ForecastResponse>,
+        alerts: &[WeatherAlert],
+    ) -> String {
+        let data = TemplateData {
+            weather: &weather(),
+            forecast,
+            alerts,
+        };
+        Template::parse(source).unwrap().render(&data, false)
+    }
+
+    #[test]
+    fn test_every_placeholder_parses_and_resolves() {
+        let weather = weather();
+        let forecast = forecast();
+        let alerts = [alert("Severe Thunderstorm Warning", AlertSeverity::Severe)];
+        let data = TemplateData {
+            weather: &weather,
+            forecast: Some(&forecast),
+            alerts: &alerts,
+        };
+        let names = CURRENT_FIELDS
+            .iter()
+            .map(|field| field.to_string())
+            .chain(DAY_FIELDS.iter().map(|field| format!("day4.{field}")))
+            .chain(ALERT_FIELDS.iter().map(|field| format!("alert.{field}")));
+        for name in names {
+            let template = Template::parse(&format!("{{{name}}}")).unwrap();
+            assert!(
+                !template.render(&data, false).is_empty(),
+                "{name} rendered nothing"
+            );
+        }
+    }
+
+    #[test]
+    fn test_renders_in_the_guis_units() {
+        let forecast = forecast();
+        assert_eq!(
+            render(
+                "{location}: {temp}{unit} {description} ↑{hi} ↓{lo} {pop}%",
+                Some(&forecast),
+                &[]
+            ),
+            "Peoria: 20°C light rain ↑25 ↓15 64%"
+        );
+        assert_eq!(
+            render(
+                "{temp!imperial}{unit!imperial} {wind_speed} {speed_unit}",
+                None,
+                &[]
+            ),
+            "69°F 11 km/h"
+        );
+        assert_eq!(
+            render(
+                "{pressure!imperial} {visibility} {sunrise}-{sunset} {utc_offset}",
+                None,
+                &[]
+            ),
+            "29.91 10.0 5:00 AM-8:00 PM -05:00"
+        );
+    }
+
+    #[test]
+    fn test_specs_pad_and_round() {
+        assert_eq!(render("[{temp:.1}]", None, &[]), "[20.4]");
+        assert_eq!(render("[{temp:>4}]", None, &[]), "[  20]");
+        assert_eq!(render("[{location:*^10}]", None, &[]), "[**Peoria**]");
+        assert_eq!(render("[{description:.5}]", None, &[]), "[light]");
+        assert_eq!(render("{{{temp}}}", None, &[]), "{20}");
+    }
+
+    #[test]
+    fn test_sections_depend_on_presence() {
+        let template = "{temp}{unit}{?hi} ↑{hi}{/}{?alert_count} ⚠ {alert.title} until {alert.end}{/}{^alert_count} ok{/}";
+        assert_eq!(render(template, None, &[]), "20°C ok");
+        let forecast = forecast();
+        let alerts = [
+            alert("Flood Advisory", AlertSeverity::Minor),
+            alert("Tornado Warning", AlertSeverity::Extreme),
+            alert("Severe Thunderstorm Warning", AlertSeverity::Severe),
+        ];
+        assert_eq!(
+            render(template, Some(&forecast), &alerts),
+            "20°C ↑25 ⚠ Tornado Warning until Thu 3:00 PM"
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_mistakes() {
+        for bad in [
+            "{tmp}",
+            "{day5.hi}",
+            "{alert.colour}",
+            "{temp:x}",
+            "{temp!kelvin}",
+            "{temp",
+            "temp}",
+            "{?hi}open",
+            "{/}",
+        ] {
+            assert!(Template::parse(bad).is_err(), "{bad} parsed");
+        }
+    }
+}
@@ -16,28 +16,73 @@
 //!   Config files saved by older versions of this app had the token
 //!   base64-"encoded" (not encrypted) directly in the file; `ConfigManager::
 //!   load_config` transparently migrates any such token into the OS keychain
-//!   the first time an old config file is loaded.
+//!   the first time an old config file is loaded. Each keyed provider has
+//!   its own entry (`WeatherApiProvider::keyring_key`), so switching
+//!   providers never overwrites another provider's key.
 
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::http::HttpSettings;
+use crate::weather_api::retry::RetryPolicy;
 use base64::{Engine as _, engine::general_purpose::STANDARD};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock, Mutex};
 
 /// Identifies this app's entries in the OS credential store (the `service`
 /// half of a `keyring::Entry`).
 const KEYRING_SERVICE: &str = "open-weather-wizard";
 /// The `username` half of the OpenWeatherMap API token's `keyring::Entry`.
 /// Not a real username -- `keyring::Entry` just needs *some* stable
-/// (service, username) pair to identify an entry.
+/// (service, username) pair to identify an entry. Before tokens were
+/// stored per provider this was the *only* entry, holding whichever
+/// provider's key was last saved -- see `migrate_shared_token`.
 const KEYRING_API_TOKEN_KEY: &str = "openweathermap-api-key";
+/// The `username` half of the Google Weather API token's `keyring::Entry`.
+const KEYRING_GOOGLE_API_TOKEN_KEY: &str = "google-weather-api-key";
 
 /// An enum representing the supported weather API providers.
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum WeatherApiProvider {
     #[default]
     OpenWeather,
     GoogleWeather,
+    /// Open-Meteo's free forecast API -- works without an API key (see
+    /// `weather_api::open_meteo_api`).
+    OpenMeteo,
+    /// The US National Weather Service (api.weather.gov) -- keyless, US
+    /// locations only, with native CAP alerts (see `weather_api::nws_api`).
+    NationalWeatherService,
+    /// MET Norway's Locationforecast API (api.met.no) -- keyless and global
+    /// (see `weather_api::met_norway_api`).
+    MetNorway,
+}
+
+impl WeatherApiProvider {
+    /// Whether `WeatherProviderFactory::create_provider` needs an API token
+    /// for this provider -- drives Preferences' token validation and key
+    /// hint, so a keyless provider never asks for one.
+    pub fn requires_api_token(&self) -> bool {
+        match self {
+            WeatherApiProvider::OpenWeather | WeatherApiProvider::GoogleWeather => true,
+            WeatherApiProvider::OpenMeteo
+            | WeatherApiProvider::NationalWeatherService
+            | WeatherApiProvider::MetNorway => false,
+        }
+    }
+
+    /// The `username` half of this provider's API token `keyring::Entry`,
+    /// or `None` for a keyless provider, which never touches the keychain.
+    pub fn keyring_key(&self) -> Option<&'static str> {
+        match self {
+            WeatherApiProvider::OpenWeather => Some(KEYRING_API_TOKEN_KEY),
+            WeatherApiProvider::GoogleWeather => Some(KEYRING_GOOGLE_API_TOKEN_KEY),
+            WeatherApiProvider::OpenMeteo
+            | WeatherApiProvider::NationalWeatherService
+            | WeatherApiProvider::MetNorway => None,
+        }
+    }
 }
 
 impl std::fmt::Display for WeatherApiProvider {
@@ -45,6 +90,11 @@ impl std::fmt::Display for WeatherApiProvider {
         match self {
             WeatherApiProvider::OpenWeather => write!(f, "OpenWeather"),
             WeatherApiProvider::GoogleWeather => write!(f, "Google Weather"),
+            WeatherApiProvider::OpenMeteo => write!(f, "Open-Meteo"),
+            WeatherApiProvider::NationalWeatherService => {
+                write!(f, "National Weather Service (US)")
+            }
+            WeatherApiProvider::MetNorway => write!(f, "MET Norway"),
         }
     }
 }
@@ -156,24 +206,95 @@ impl std::fmt::Display for Language {
     }
 }
 
-/// A struct representing the user's configured location.
+/// A struct representing the user's configured location: either a place
+/// name (`city`/`state`/`country`, geocoded into `lat`/`lon` on first use),
+/// or coordinates alone with an empty `city` -- for field sites, boats, or
+/// trailheads with no meaningful city (see `from_coordinates`).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct LocationConfig {
     pub city: String,
     pub state: String,
     pub country: String,
+    /// Where `city`/`state`/`country` resolved to, saved after the first
+    /// successful geocode (`app::Message::LocationResolved`) or filled in
+    /// by "Detect my location", so later fetches skip geocoding entirely.
+    /// Only meaningful as a pair -- see `coordinates`. Cleared whenever the
+    /// place itself is edited. `#[serde(default)]` so config files saved
+    /// before this field existed just geocode once more, then fill it in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    /// See `lat`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    /// IANA zone, e.g. "America/Chicago", when whatever resolved `lat`/`lon`
+    /// knew it (Open-Meteo's geocoder does; OpenWeatherMap's doesn't).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
 }
 
-impl Default for LocationConfig {
-    fn default() -> Self {
+impl LocationConfig {
+    /// A place with no saved coordinates yet.
+    pub fn new(city: String, state: String, country: String) -> Self {
         Self {
-            city: "Peoria".to_string(),
-            state: "IL".to_string(),
-            country: "US".to_string(),
+            city,
+            state,
+            country,
+            lat: None,
+            lon: None,
+            timezone: None,
+        }
+    }
+
+    /// A coordinate-only location. Fails on an out-of-range latitude or
+    /// longitude, with a message naming which.
+    pub fn from_coordinates(lat: f64, lon: f64) -> Result<Self, String> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(format!("Latitude {lat} must be between -90 and 90."));
+        }
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err(format!("Longitude {lon} must be between -180 and 180."));
+        }
+        Ok(Self {
+            lat: Some(lat),
+            lon: Some(lon),
+            ..Self::new(String::new(), String::new(), String::new())
+        })
+    }
+
+    /// The saved `(lat, lon)`, if both halves are present.
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.lat.zip(self.lon)
+    }
+
+    /// Whether this location is defined by its coordinates alone -- no
+    /// city to geocode or to show.
+    pub fn is_coordinate_only(&self) -> bool {
+        self.city.trim().is_empty() && self.coordinates().is_some()
+    }
+
+    /// What providers report as the location's name: the city, or for a
+    /// coordinate-only location the coordinates themselves, e.g.
+    /// "44.2706°N, 71.3033°W".
+    pub fn display_name(&self) -> String {
+        match self.coordinates() {
+            Some((lat, lon)) if self.is_coordinate_only() => format!(
+                "{:.4}\u{b0}{}, {:.4}\u{b0}{}",
+                lat.abs(),
+                if lat < 0.0 { 'S' } else { 'N' },
+                lon.abs(),
+                if lon < 0.0 { 'W' } else { 'E' },
+            ),
+            _ => self.city.clone(),
         }
     }
 }
 
+impl Default for LocationConfig {
+    fn default() -> Self {
+        Self::new("Peoria".to_string(), "IL".to_string(), "US".to_string())
+    }
+}
+
 /// A named, saved location -- one entry in `AppConfig.locations`. The name
 /// is purely a user-facing label (shown in the Preferences list and the
 /// main window's location switcher); it plays no role in fetching, which
@@ -197,6 +318,13 @@ impl Default for SavedLocation {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
     pub weather_provider: WeatherApiProvider,
+    /// Providers to try, in order, when `weather_provider` fails with a
+    /// network error, quota hit, or server error -- see
+    /// `weather_api::fallback`. `#[serde(default)]` so config files saved
+    /// before this field existed load with no fallbacks, same behavior as
+    /// before.
+    #[serde(default)]
+    pub fallback_providers: Vec<WeatherApiProvider>,
     /// Every saved location, in display order. Never empty in practice --
     /// `AppConfig::default()` seeds one entry, `migrate_legacy_location`
     /// guarantees at least one after loading an old config file, and
@@ -239,6 +367,24 @@ pub struct AppConfig {
     /// `Language::English`, matching both providers' own default.
     #[serde(default)]
     pub language: Language,
+    /// How `openweather_api` and `google_weather_api` retry a request that
+    /// timed out or hit a `429`/`5xx` -- see `weather_api::retry`. Only set
+    /// by hand in `config.json`; `#[serde(default)]` so files without it
+    /// (every one saved before it existed) get `RetryPolicy::default()`.
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Timeouts, proxy, and extra CA certificates for every request the
+    /// app makes -- see `weather_api::http`. Edited in Preferences' Network
+    /// section; `#[serde(default)]` so older config files get
+    /// `HttpSettings::default()`.
+    #[serde(default)]
+    pub http: HttpSettings,
+    /// Base URLs to use instead of the public services' -- see
+    /// `weather_api::endpoints`. Only set by hand in `config.json` (the
+    /// CLI also reads environment variables on top); `#[serde(default)]`
+    /// so files without it talk to the public services.
+    #[serde(default)]
+    pub endpoints: Endpoints,
     /// Present only to read config files saved by older versions of this
     /// app, which stored the API token base64-"encoded" (not encrypted)
     /// directly here. `#[serde(skip_serializing)]` means this is never
@@ -249,6 +395,13 @@ pub struct AppConfig {
     /// only through `get_api_token`/`set_api_token`.
     #[serde(rename = "api_token_encoded", default, skip_serializing)]
     legacy_api_token_encoded: Option<String>,
+    /// Whether API tokens are already stored one keychain entry per
+    /// provider. `#[serde(default)]` so config files saved before that
+    /// change read as `false`, which `ConfigManager::load_config` uses to
+    /// run `migrate_shared_token` exactly once; a brand-new config has
+    /// nothing to migrate and starts out `true`.
+    #[serde(default)]
+    per_provider_keychain: bool,
     /// Present only to read config files saved by a version of this app
     /// before `dark_mode: bool` became `theme_preference: ThemePreference`.
     /// `#[serde(skip_serializing)]` means this is never written back out --
@@ -274,6 +427,7 @@ impl Default for AppConfig {
     fn default() -> Self {
         Self {
             weather_provider: WeatherApiProvider::OpenWeather,
+            fallback_providers: Vec::new(),
             locations: vec![SavedLocation::default()],
             current_location_index: 0,
             theme_preference: ThemePreference::default(),
@@ -281,7 +435,11 @@ impl Default for AppConfig {
             launch_at_login: false,
             refresh_interval_secs: None,
             language: Language::default(),
+            retry: RetryPolicy::default(),
+            http: HttpSettings::default(),
+            endpoints: Endpoints::default(),
             legacy_api_token_encoded: None,
+            per_provider_keychain: true,
             legacy_dark_mode: None,
             legacy_location: None,
         }
@@ -314,21 +472,46 @@ impl AppConfig {
             .unwrap_or("Home")
     }
 
-    /// Stores the API token in the OS's secure credential store (macOS
-    /// Keychain, Windows Credential Manager, Linux Secret Service).
+    /// `weather_provider` followed by `fallback_providers`, in order, with
+    /// duplicates (including a fallback naming the primary itself, easy to
+    /// end up with by hand-editing the config file) dropped.
+    pub fn provider_chain(&self) -> Vec<WeatherApiProvider> {
+        let mut chain = vec![self.weather_provider.clone()];
+        for provider in &self.fallback_providers {
+            if !chain.contains(provider) {
+                chain.push(provider.clone());
+            }
+        }
+        chain
+    }
+
+    /// Stores `provider`'s API token in the OS's secure credential store
+    /// (macOS Keychain, Windows Credential Manager, Linux Secret Service).
+    /// Each provider has its own entry, so this never touches another
+    /// provider's key.
     ///
     /// # Arguments
     ///
+    /// * `provider` - The provider the token belongs to. Keyless providers
+    ///   have nowhere to store one and return an error.
     /// * `token` - The API token to set.
-    pub fn set_api_token(&mut self, token: &str) -> Result<(), String> {
-        keyring_entry()?
+    pub fn set_api_token(
+        &mut self,
+        provider: &WeatherApiProvider,
+        token: &str,
+    ) -> Result<(), String> {
+        let key = provider
+            .keyring_key()
+            .ok_or_else(|| format!("{provider} doesn't use an API token"))?;
+        keyring_entry(key)?
             .set_password(token)
             .map_err(|e| format!("Failed to store API token securely: {e}"))
     }
 
-    /// Reads the API token back from the OS's secure credential store.
-    /// No token having been set yet is not an error -- it returns an empty
-    /// string, same as an unset field would have before.
+    /// Reads `provider`'s API token back from the OS's secure credential
+    /// store. No token having been set yet is not an error -- it returns an
+    /// empty string, same as an unset field would have before. A keyless
+    /// provider always reads as empty, without touching the keychain.
     ///
     /// # Returns
     ///
@@ -336,27 +519,31 @@ impl AppConfig {
     /// success, or an error `String` if the credential store itself
     /// couldn't be accessed (e.g. a locked keychain, no Secret Service
     /// running).
-    pub fn get_api_token(&self) -> Result<String, String> {
-        match keyring_entry()?.get_password() {
+    pub fn get_api_token(&self, provider: &WeatherApiProvider) -> Result<String, String> {
+        let Some(key) = provider.keyring_key() else {
+            return Ok(String::new());
+        };
+        match keyring_entry(key)?.get_password() {
             Ok(token) => Ok(token),
             Err(keyring::Error::NoEntry) => Ok(String::new()),
             Err(e) => Err(format!("Failed to read API token: {e}")),
         }
     }
 
-    /// Removes the API token from the OS's secure credential store
+    /// Removes `provider`'s API token from the OS's secure credential store
     /// entirely, rather than overwriting it with an empty string --
     /// deleting the credential itself is what `examples/clear_credentials.rs`
     /// needs, and matches what a user uninstalling the app or switching
     /// machines would actually want. Not currently exposed anywhere in the
-    /// app's own UI (Preferences only ever sets a new token); a missing
-    /// entry is not an error, since that's already the desired end state.
-    // The binary crate's own `mod config` (src/main.rs) never calls this --
-    // only `examples/clear_credentials.rs` does, which links against the
-    // library crate's copy, a separate compilation -- hence the `allow`.
-    #[allow(dead_code)]
-    pub fn delete_api_token(&self) -> Result<(), String> {
-        match keyring_entry()?.delete_credential() {
+    /// app's own UI (Preferences only ever sets a new token) -- only
+    /// `migrate_shared_token` uses it, to clear the old shared entry; a
+    /// missing entry is not an error, since that's already the desired end
+    /// state.
+    pub fn delete_api_token(&self, provider: &WeatherApiProvider) -> Result<(), String> {
+        let Some(key) = provider.keyring_key() else {
+            return Ok(());
+        };
+        match keyring_entry(key)?.delete_credential() {
             Ok(()) => Ok(()),
             Err(keyring::Error::NoEntry) => Ok(()),
             Err(e) => Err(format!("Failed to delete API token: {e}")),
@@ -384,8 +571,9 @@ impl AppConfig {
     }
 }
 
-/// The single `keyring::Entry` this app ever uses for the OpenWeatherMap API
-/// token, constructed once per process and reused for every read/write.
+/// The `keyring::Entry` for each provider's API token, keyed by
+/// `WeatherApiProvider::keyring_key`, each constructed on first use and then
+/// reused for every read/write for the rest of the process.
 ///
 /// Constructing an `Entry` doesn't itself perform any OS I/O (that happens
 /// in `get_password`/`set_password`), so this isn't primarily a performance
@@ -398,13 +586,22 @@ impl AppConfig {
 /// without changing anything about how real platform backends behave (they
 /// persist by service+user at the OS level, independent of the `Entry`
 /// object).
-static API_TOKEN_ENTRY: LazyLock<Result<keyring::Entry, String>> = LazyLock::new(|| {
-    keyring::Entry::new(KEYRING_SERVICE, KEYRING_API_TOKEN_KEY)
-        .map_err(|e| format!("Failed to access the OS secure credential store: {e}"))
-});
-
-fn keyring_entry() -> Result<&'static keyring::Entry, String> {
-    API_TOKEN_ENTRY.as_ref().map_err(Clone::clone)
+static API_TOKEN_ENTRIES: LazyLock<Mutex<HashMap<&'static str, Arc<keyring::Entry>>>> =
+    LazyLock::new(Default::default);
+
+fn keyring_entry(key: &'static str) -> Result<Arc<keyring::Entry>, String> {
+    let mut entries = API_TOKEN_ENTRIES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(entry) = entries.get(key) {
+        return Ok(Arc::clone(entry));
+    }
+    let entry = Arc::new(
+        keyring::Entry::new(KEYRING_SERVICE, key)
+            .map_err(|e| format!("Failed to access the OS secure credential store: {e}"))?,
+    );
+    entries.insert(key, Arc::clone(&entry));
+    Ok(entry)
 }
 
 /// Manages the loading and saving of the application's configuration.
@@ -447,6 +644,12 @@ impl ConfigManager {
         self.config_path.exists()
     }
 
+    /// Where `weather_api::cache::ResponseCache` keeps the last responses
+    /// -- a `cache` directory beside the config file.
+    pub fn cache_dir(&self) -> PathBuf {
+        self.config_path.with_file_name("cache")
+    }
+
     /// Points a `ConfigManager` at an arbitrary file, bypassing the real OS
     /// config directory -- so tests can exercise `load_config`/`save_config`
     /// (in particular the legacy-token migration, which needs real file
@@ -473,6 +676,7 @@ impl ConfigManager {
                 Ok(mut config) => {
                     log::info!("Loaded configuration from {:?}", self.config_path);
                     self.migrate_legacy_token(&mut config);
+                    self.migrate_shared_token(&mut config);
                     migrate_legacy_dark_mode(&mut config);
                     migrate_legacy_location(&mut config);
                     config
@@ -522,25 +726,77 @@ impl ConfigManager {
             .and_then(|bytes| String::from_utf8(bytes).map_err(|e| format!("invalid UTF-8: {e}")));
 
         match decoded {
-            Ok(token) if !token.is_empty() => match config.set_api_token(&token) {
-                Ok(()) => {
-                    log::info!("Migrated API token from config file into the OS keychain");
-                    if let Err(e) = self.save_config(config) {
-                        log::warn!(
-                            "Migrated token to keychain but failed to rewrite config file (it will be retried next launch): {e}"
-                        );
+            Ok(token) if !token.is_empty() => {
+                match config.set_api_token(&config.weather_provider.clone(), &token) {
+                    Ok(()) => {
+                        log::info!("Migrated API token from config file into the OS keychain");
+                        if let Err(e) = self.save_config(config) {
+                            log::warn!(
+                                "Migrated token to keychain but failed to rewrite config file (it will be retried next launch): {e}"
+                            );
+                        }
                     }
+                    Err(e) => log::warn!(
+                        "Found a legacy API token in config file but failed to migrate it into the OS keychain: {e}"
+                    ),
                 }
-                Err(e) => log::warn!(
-                    "Found a legacy API token in config file but failed to migrate it into the OS keychain: {e}"
-                ),
-            },
+            }
             Ok(_) => {} // empty token, nothing to migrate
             Err(e) => log::warn!(
                 "Found an api_token_encoded field in config file but couldn't decode it, ignoring: {e}"
             ),
         }
     }
+
+    /// One-time migration for config files saved before API tokens were
+    /// stored per provider. Back then every provider shared the one entry
+    /// that now belongs to OpenWeatherMap (`KEYRING_API_TOKEN_KEY`), so it
+    /// holds whichever provider's key was saved last -- which can only be
+    /// the configured provider's, since Preferences always saved the token
+    /// alongside the provider. If that's another keyed provider with no
+    /// entry of its own yet, the token moves there. Any keychain access
+    /// re-saves the config with `per_provider_keychain` set, so it happens
+    /// only once; with OpenWeatherMap or a keyless provider selected there's
+    /// nothing to move, and like `migrate_legacy_dark_mode` the flag just
+    /// rides along with the next natural Save. Left unset (retried next
+    /// launch) if the keychain can't be read at all.
+    fn migrate_shared_token(&self, config: &mut AppConfig) {
+        if config.per_provider_keychain {
+            return;
+        }
+
+        let provider = config.weather_provider.clone();
+        if !provider.requires_api_token() || provider == WeatherApiProvider::OpenWeather {
+            config.per_provider_keychain = true;
+            return;
+        }
+
+        let shared = match config.get_api_token(&WeatherApiProvider::OpenWeather) {
+            Ok(token) => token,
+            Err(e) => {
+                log::warn!("Couldn't read the shared API token to migrate it: {e}");
+                return;
+            }
+        };
+        let own = config.get_api_token(&provider).unwrap_or_default();
+        if !shared.is_empty() && own.is_empty() {
+            if let Err(e) = config.set_api_token(&provider, &shared) {
+                log::warn!("Failed to move the shared API token to {provider}'s entry: {e}");
+                return;
+            }
+            if let Err(e) = config.delete_api_token(&WeatherApiProvider::OpenWeather) {
+                log::warn!("Moved the shared API token but failed to remove the old entry: {e}");
+            }
+            log::info!("Moved the shared API token to {provider}'s own keychain entry");
+        }
+
+        config.per_provider_keychain = true;
+        if let Err(e) = self.save_config(config) {
+            log::warn!(
+                "Failed to record the per-provider token migration (it will be retried next launch): {e}"
+            );
+        }
+    }
 }
 
 /// One-time migration for config files saved before `dark_mode: bool`
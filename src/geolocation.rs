@@ -24,17 +24,25 @@
 //! `detect_location` return an `Err` at all.
 
 use crate::config::LocationConfig;
+use crate::weather_api::endpoints::Endpoints;
 
 /// Detects an approximate "Home" location: OS-native positioning
-/// (reverse-geocoded) if available, otherwise an IP-based lookup.
-pub async fn detect_location() -> Result<LocationConfig, String> {
+/// (reverse-geocoded) if available, otherwise an IP-based lookup. Both
+/// lookups go through `client`, the app's shared one (see
+/// `weather_api::http`), to wherever `endpoints` points them.
+pub async fn detect_location(
+    client: reqwest::Client,
+    endpoints: Endpoints,
+) -> Result<LocationConfig, String> {
     if let Some((lat, lon)) = os_location::coordinates().await {
-        match reverse_geocode::reverse_geocode(lat, lon).await {
+        match reverse_geocode::reverse_geocode(&client, endpoints.reverse_geocoding(), lat, lon)
+            .await
+        {
             Ok(location) => return Ok(location),
             Err(e) => log::warn!("Reverse geocoding failed, falling back to IP lookup: {e}"),
         }
     }
-    ip_location::detect().await
+    ip_location::detect(&client, endpoints.ip_geolocation()).await
 }
 
 /// IP-based geolocation -- the fallback tier. `ipwho.is` (free, keyless,
@@ -46,18 +54,31 @@ mod ip_location {
     use super::LocationConfig;
     use serde::Deserialize;
 
-    const IP_GEOLOCATION_API: &str = "https://ipwho.is/";
-
     #[derive(Deserialize, Debug)]
     pub(super) struct IpGeolocationResponse {
         pub(super) success: bool,
         pub(super) city: String,
         pub(super) region_code: String,
         pub(super) country_code: String,
+        #[serde(default)]
+        pub(super) latitude: Option<f64>,
+        #[serde(default)]
+        pub(super) longitude: Option<f64>,
+        #[serde(default)]
+        pub(super) timezone: Option<IpTimezone>,
     }
 
-    pub async fn detect() -> Result<LocationConfig, String> {
-        let response = reqwest::get(IP_GEOLOCATION_API)
+    #[derive(Deserialize, Debug)]
+    pub(super) struct IpTimezone {
+        /// IANA zone, e.g. "America/Chicago".
+        pub(super) id: String,
+    }
+
+    /// `base` is `Endpoints::ip_geolocation`.
+    pub async fn detect(client: &reqwest::Client, base: &str) -> Result<LocationConfig, String> {
+        let response = client
+            .get(format!("{base}/"))
+            .send()
             .await
             .map_err(|e| format!("Request failed: {e}"))?;
 
@@ -74,6 +95,9 @@ mod ip_location {
             city: parsed.city,
             state: parsed.region_code,
             country: parsed.country_code,
+            lat: parsed.latitude,
+            lon: parsed.longitude,
+            timezone: parsed.timezone.map(|tz| tz.id),
         })
     }
 }
@@ -86,16 +110,6 @@ mod reverse_geocode {
     use super::LocationConfig;
     use serde::Deserialize;
 
-    const REVERSE_GEOCODE_API: &str = "https://nominatim.openstreetmap.org/reverse";
-    /// Nominatim's usage policy requires a descriptive User-Agent identifying
-    /// the calling application -- an unidentified/generic one risks being
-    /// rate-limited or blocked.
-    const USER_AGENT: &str = concat!(
-        "open-weather-wizard/",
-        env!("CARGO_PKG_VERSION"),
-        " (github.com/arunkumar-mourougappane/open-weather-wizard)"
-    );
-
     /// Deliberately country-independent: `state` and `country_code` are
     /// stored exactly as Nominatim returns them for *this* address, not
     /// normalized against any single country's convention (e.g. no US
@@ -117,17 +131,23 @@ mod reverse_geocode {
         pub(super) address: Address,
     }
 
-    pub async fn reverse_geocode(lat: f64, lon: f64) -> Result<LocationConfig, String> {
-        let client = reqwest::Client::new();
+    /// Nominatim's usage policy requires a descriptive User-Agent, which
+    /// `client` (the shared one) always sends. `base` is
+    /// `Endpoints::reverse_geocoding`.
+    pub async fn reverse_geocode(
+        client: &reqwest::Client,
+        base: &str,
+        lat: f64,
+        lon: f64,
+    ) -> Result<LocationConfig, String> {
         let response = client
-            .get(REVERSE_GEOCODE_API)
+            .get(format!("{base}/reverse"))
             .query(&[
                 ("lat", lat.to_string()),
                 ("lon", lon.to_string()),
                 ("format", "jsonv2".to_string()),
                 ("addressdetails", "1".to_string()),
             ])
-            .header(reqwest::header::USER_AGENT, USER_AGENT)
             .send()
             .await
             .map_err(|e| format!("Request failed: {e}"))?;
@@ -153,6 +173,11 @@ mod reverse_geocode {
             // convention elsewhere (defaults, Google Weather's country param)
             // is uppercase.
             country: parsed.address.country_code.to_uppercase(),
+            // The position itself, not the town's center -- it's what the
+            // user actually asked about.
+            lat: Some(lat),
+            lon: Some(lon),
+            timezone: None,
         })
     }
 }
@@ -427,13 +452,18 @@ mod tests {
             "city": "Peoria Heights",
             "region": "Illinois",
             "region_code": "IL",
-            "country_code": "US"
+            "country_code": "US",
+            "latitude": 40.7475,
+            "longitude": -89.5734,
+            "timezone": {"id": "America/Chicago", "abbr": "CDT"}
         }"#;
         let parsed: ip_location::IpGeolocationResponse = serde_json::from_str(json).unwrap();
         assert!(parsed.success);
         assert_eq!(parsed.city, "Peoria Heights");
         assert_eq!(parsed.region_code, "IL");
         assert_eq!(parsed.country_code, "US");
+        assert_eq!(parsed.latitude, Some(40.7475));
+        assert_eq!(parsed.timezone.unwrap().id, "America/Chicago");
     }
 
     #[test]
@@ -26,10 +26,12 @@ mod tests {
         AppConfig, ConfigManager, Language, LocationConfig, SavedLocation, ThemePreference,
         WeatherApiProvider,
     };
+    use crate::weather_api::endpoints::Endpoints;
+    use crate::weather_api::retry::RetryPolicy;
     use crate::weather_api::weather_provider::WeatherProviderFactory;
 
-    /// The API token lives in a single OS-keyring entry shared by the whole
-    /// process (see `config`'s `API_TOKEN_ENTRY`), and the mock credential
+    /// Each provider's API token lives in an OS-keyring entry shared by the
+    /// whole process (see `config`'s `API_TOKEN_ENTRIES`), and the mock credential
     /// backend used here doesn't key entries by service/user at all -- every
     /// test that reads or writes a token must run exclusive of every other
     /// one, or they'll observe each other's writes. Rust's default test
@@ -56,12 +58,131 @@ mod tests {
         let mut config = AppConfig::default();
         let test_token = "test_api_key_12345";
 
-        config.set_api_token(test_token).unwrap();
-        let round_tripped = config.get_api_token().unwrap();
+        config
+            .set_api_token(&WeatherApiProvider::OpenWeather, test_token)
+            .unwrap();
+        let round_tripped = config
+            .get_api_token(&WeatherApiProvider::OpenWeather)
+            .unwrap();
 
         assert_eq!(test_token, round_tripped);
     }
 
+    /// Verifies that each keyed provider has its own keyring entry -- saving
+    /// one provider's token leaves the other's alone -- and that keyless
+    /// providers read as empty and refuse to store anything.
+    #[test]
+    fn test_api_tokens_are_per_provider() {
+        let _guard = lock_mock_keyring();
+        let mut config = AppConfig::default();
+
+        config
+            .set_api_token(&WeatherApiProvider::OpenWeather, "owm-key")
+            .unwrap();
+        config
+            .set_api_token(&WeatherApiProvider::GoogleWeather, "google-key")
+            .unwrap();
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenWeather)
+                .unwrap(),
+            "owm-key"
+        );
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::GoogleWeather)
+                .unwrap(),
+            "google-key"
+        );
+
+        config
+            .delete_api_token(&WeatherApiProvider::GoogleWeather)
+            .unwrap();
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::GoogleWeather)
+                .unwrap(),
+            ""
+        );
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenWeather)
+                .unwrap(),
+            "owm-key"
+        );
+
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenMeteo)
+                .unwrap(),
+            ""
+        );
+        assert!(
+            config
+                .set_api_token(&WeatherApiProvider::OpenMeteo, "unused")
+                .is_err()
+        );
+    }
+
+    /// Verifies the one-time migration from the single shared keyring entry:
+    /// a config saved before per-provider tokens, with Google selected, has
+    /// the shared entry's token (necessarily Google's) moved into Google's
+    /// own entry, and is re-saved so the migration never runs again -- a
+    /// later OpenWeather key in that entry is left where it is.
+    #[test]
+    fn test_shared_token_migration() {
+        let _guard = lock_mock_keyring();
+
+        let config_path = std::env::temp_dir().join(format!(
+            "open-weather-wizard-shared-token-test-{:?}.json",
+            std::thread::current().id()
+        ));
+        std::fs::write(
+            &config_path,
+            r#"{"weather_provider":"GoogleWeather","use_fahrenheit":false}"#,
+        )
+        .unwrap();
+
+        let mut seed = AppConfig::default();
+        seed.delete_api_token(&WeatherApiProvider::GoogleWeather)
+            .unwrap();
+        seed.set_api_token(
+            &WeatherApiProvider::OpenWeather,
+            "google-key-in-shared-entry",
+        )
+        .unwrap();
+
+        let manager = ConfigManager::for_path(config_path.clone());
+        let config = manager.load_config();
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::GoogleWeather)
+                .unwrap(),
+            "google-key-in-shared-entry"
+        );
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenWeather)
+                .unwrap(),
+            ""
+        );
+        let saved = std::fs::read_to_string(&config_path).unwrap();
+        assert!(saved.contains(r#""per_provider_keychain": true"#));
+
+        // Already migrated: a real OpenWeather key saved since stays put.
+        seed.set_api_token(&WeatherApiProvider::OpenWeather, "owm-key")
+            .unwrap();
+        let config = manager.load_config();
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenWeather)
+                .unwrap(),
+            "owm-key"
+        );
+
+        let _ = std::fs::remove_file(&config_path);
+    }
+
     /// Verifies that the `AppConfig` struct can be serialized to and
     /// deserialized from JSON. The API token is deliberately not part of
     /// this -- it never lives in the JSON at all anymore, only in the OS
@@ -77,6 +198,7 @@ mod tests {
                 city: "Test City".to_string(),
                 state: "TS".to_string(),
                 country: "TC".to_string(),
+                ..Default::default()
             },
         }];
         config.refresh_interval_secs = Some(900);
@@ -174,7 +296,9 @@ mod tests {
         // already seeds a valid Peoria/IL/US "Home" location, so only the
         // token needs filling in for the other validators not to trigger.
         let mut prefs_state = PrefsState::from_config(&config);
-        prefs_state.token_input = "dummy_token".to_string();
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::OpenWeather, "dummy_token".to_string());
 
         let errors = prefs_state.validation_errors();
         assert!(
@@ -184,7 +308,11 @@ mod tests {
         );
 
         // Switching provider to Google Weather with 30s preset is invalid.
+        // Tokens are per provider, so Google needs its own.
         prefs_state.provider = WeatherApiProvider::GoogleWeather;
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::GoogleWeather, "dummy_token".to_string());
         prefs_state.refresh_interval = RefreshIntervalPreset::ThirtySeconds;
         let errors = prefs_state.validation_errors();
         assert!(
@@ -205,6 +333,103 @@ mod tests {
         );
     }
 
+    /// Open-Meteo needs no API key, so an empty token field must not block
+    /// Save for it -- while switching back to a keyed provider with the
+    /// same empty field still does.
+    #[test]
+    fn test_keyless_provider_skips_token_validation() {
+        use crate::ui::preferences::State as PrefsState;
+
+        let mut config = AppConfig::default();
+        config.weather_provider = WeatherApiProvider::OpenMeteo;
+
+        let mut prefs_state = PrefsState::from_config(&config);
+        assert!(prefs_state.token_input().is_empty());
+        let errors = prefs_state.validation_errors();
+        assert!(
+            errors.is_empty(),
+            "Open-Meteo without a token should be valid: {:?}",
+            errors
+        );
+
+        prefs_state.provider = WeatherApiProvider::OpenWeather;
+        assert!(
+            prefs_state
+                .validation_errors()
+                .iter()
+                .any(|e| e.contains("API Token is required for OpenWeather"))
+        );
+    }
+
+    /// Verifies the fallback chain: `provider_chain` puts the primary first
+    /// and drops duplicates, old configs load with no fallbacks, and the
+    /// Preferences togglers keep `PROVIDERS` order and never save the
+    /// primary as its own fallback.
+    #[test]
+    fn test_provider_chain_and_fallback_toggles() {
+        use crate::ui::preferences::{self, State as PrefsState};
+
+        let mut config = AppConfig::default();
+        config.weather_provider = WeatherApiProvider::GoogleWeather;
+        config.fallback_providers = vec![
+            WeatherApiProvider::MetNorway,
+            WeatherApiProvider::GoogleWeather,
+            WeatherApiProvider::MetNorway,
+            WeatherApiProvider::OpenMeteo,
+        ];
+        assert_eq!(
+            config.provider_chain(),
+            vec![
+                WeatherApiProvider::GoogleWeather,
+                WeatherApiProvider::MetNorway,
+                WeatherApiProvider::OpenMeteo,
+            ]
+        );
+
+        let old_json = r#"{"weather_provider":"OpenWeather","use_fahrenheit":false}"#;
+        let old_config: AppConfig = serde_json::from_str(old_json).unwrap();
+        assert!(old_config.fallback_providers.is_empty());
+        assert_eq!(
+            old_config.provider_chain(),
+            vec![WeatherApiProvider::OpenWeather]
+        );
+
+        let mut config = AppConfig::default();
+        config.weather_provider = WeatherApiProvider::GoogleWeather;
+        let mut prefs_state = PrefsState::from_config(&config);
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::FallbackToggled(WeatherApiProvider::MetNorway, true),
+        );
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::FallbackToggled(WeatherApiProvider::OpenMeteo, true),
+        );
+        assert_eq!(
+            prefs_state.fallback_providers,
+            vec![WeatherApiProvider::OpenMeteo, WeatherApiProvider::MetNorway]
+        );
+
+        // Promoting a fallback to primary doesn't save it as its own fallback.
+        prefs_state.provider = WeatherApiProvider::OpenMeteo;
+        prefs_state.apply_to(&mut config).unwrap();
+        assert_eq!(config.weather_provider, WeatherApiProvider::OpenMeteo);
+        assert_eq!(
+            config.fallback_providers,
+            vec![WeatherApiProvider::MetNorway]
+        );
+
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::FallbackToggled(WeatherApiProvider::MetNorway, false),
+        );
+        assert!(
+            !prefs_state
+                .fallback_providers
+                .contains(&WeatherApiProvider::MetNorway)
+        );
+    }
+
     /// Verifies the Preferences "Locations" list's add/remove/reorder
     /// messages (issue #55): a new entry is appended and selected, removal
     /// is refused once only one location remains (rather than emptying the
@@ -255,6 +480,47 @@ mod tests {
         );
     }
 
+    /// Verifies that saved coordinates survive a Preferences round trip
+    /// untouched, are dropped as soon as the place itself is edited (they'd
+    /// describe the old one), and that config files from before they
+    /// existed load without them.
+    #[test]
+    fn test_location_coordinates_round_trip_and_reset_on_edit() {
+        use crate::ui::preferences::{self, State as PrefsState};
+
+        let _guard = lock_mock_keyring();
+
+        let mut config = AppConfig::default();
+        config.locations[0].location.lat = Some(40.6936);
+        config.locations[0].location.lon = Some(-89.589);
+        config.locations[0].location.timezone = Some("America/Chicago".to_string());
+
+        let mut prefs_state = PrefsState::from_config(&config);
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::LocationNameChanged("House".to_string()),
+        );
+        prefs_state.apply_to(&mut config).unwrap();
+        assert_eq!(
+            config.current_location().coordinates(),
+            Some((40.6936, -89.589))
+        );
+
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::CityChanged("Peoria Heights".to_string()),
+        );
+        prefs_state.apply_to(&mut config).unwrap();
+        assert_eq!(config.current_location().coordinates(), None);
+        assert_eq!(config.current_location().timezone, None);
+
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("\"lat\""));
+        let old_json = r#"{"city":"Peoria","state":"IL","country":"US"}"#;
+        let location: LocationConfig = serde_json::from_str(old_json).unwrap();
+        assert_eq!(location, LocationConfig::default());
+    }
+
     /// Verifies that every saved location needs a name, city, and country
     /// before Save is allowed -- multiple entries are each validated, not
     /// just whichever one is currently selected in the form.
@@ -264,7 +530,9 @@ mod tests {
 
         let config = AppConfig::default();
         let mut prefs_state = PrefsState::from_config(&config);
-        prefs_state.token_input = "dummy_token".to_string();
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::OpenWeather, "dummy_token".to_string());
         assert!(prefs_state.validation_errors().is_empty());
 
         prefs_state.locations.push(LocationEntry {
@@ -272,6 +540,12 @@ mod tests {
             city: String::new(),
             state: String::new(),
             country: "US".to_string(),
+            by_coordinates: false,
+            lat_input: String::new(),
+            lon_input: String::new(),
+            lat: None,
+            lon: None,
+            timezone: None,
         });
 
         let errors = prefs_state.validation_errors();
@@ -279,6 +553,64 @@ mod tests {
         assert!(errors.iter().any(|e| e.contains("needs a city")));
     }
 
+    /// Verifies that a location can be saved by latitude/longitude alone:
+    /// the form validates the numbers (not a city) in coordinate mode, and
+    /// the saved `LocationConfig` round-trips back into coordinate mode with
+    /// a readable name in place of the missing city.
+    #[test]
+    fn test_coordinate_only_locations() {
+        use crate::ui::preferences::{self, State as PrefsState};
+
+        let _guard = lock_mock_keyring();
+
+        assert!(LocationConfig::from_coordinates(91.0, 0.0).is_err());
+        assert!(LocationConfig::from_coordinates(0.0, -180.5).is_err());
+        let summit = LocationConfig::from_coordinates(44.2706, -71.3033).unwrap();
+        assert!(summit.is_coordinate_only());
+        assert_eq!(summit.display_name(), "44.2706°N, 71.3033°W");
+        assert_eq!(LocationConfig::default().display_name(), "Peoria");
+        assert!(!LocationConfig::default().is_coordinate_only());
+
+        let mut config = AppConfig::default();
+        let mut prefs_state = PrefsState::from_config(&config);
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::OpenWeather, "dummy_token".to_string());
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::CoordinatesModeToggled(true),
+        );
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::LatitudeChanged("95".to_string()),
+        );
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::LongitudeChanged("east".to_string()),
+        );
+        let errors = prefs_state.validation_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("numeric longitude"));
+
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::LongitudeChanged("-71.3033".to_string()),
+        );
+        assert!(prefs_state.validation_errors()[0].contains("Latitude 95"));
+
+        preferences::update(
+            &mut prefs_state,
+            preferences::Message::LatitudeChanged("44.2706".to_string()),
+        );
+        assert!(prefs_state.validation_errors().is_empty());
+        prefs_state.apply_to(&mut config).unwrap();
+        assert_eq!(config.current_location(), summit);
+
+        let reopened = PrefsState::from_config(&config);
+        assert!(reopened.locations[0].by_coordinates);
+        assert_eq!(reopened.locations[0].lat_input, "44.2706");
+    }
+
     /// Verifies that `apply_to` keeps `AppConfig.current_location_index`
     /// pointing at the location the main window was actually showing,
     /// through operations that move entries around underneath it --
@@ -291,7 +623,7 @@ mod tests {
     fn test_apply_to_preserves_current_location_through_rename_and_reorder() {
         use crate::ui::preferences::{self, State as PrefsState};
 
-        // apply_to writes token_input via set_api_token, an OS keychain
+        // apply_to writes token_inputs via set_api_token, an OS keychain
         // call -- switch to the mock backend like every other test that
         // exercises apply_to/set_api_token.
         let _guard = lock_mock_keyring();
@@ -303,13 +635,16 @@ mod tests {
                 city: "Chicago".to_string(),
                 state: "IL".to_string(),
                 country: "US".to_string(),
+                ..Default::default()
             },
         });
         // "Home" (index 0) is current.
         assert_eq!(config.current_location_index, 0);
 
         let mut prefs_state = PrefsState::from_config(&config);
-        prefs_state.token_input = "dummy_token".to_string();
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::OpenWeather, "dummy_token".to_string());
 
         // Rename the currently-active entry -- a name-based lookup would
         // lose track of it here, since "Home" no longer exists afterward.
@@ -347,11 +682,14 @@ mod tests {
                 city: "Chicago".to_string(),
                 state: "IL".to_string(),
                 country: "US".to_string(),
+                ..Default::default()
             },
         });
 
         let mut prefs_state = PrefsState::from_config(&config);
-        prefs_state.token_input = "dummy_token".to_string();
+        prefs_state
+            .token_inputs
+            .insert(WeatherApiProvider::OpenWeather, "dummy_token".to_string());
 
         // Remove "Home" (the currently-active entry) while it's selected.
         preferences::update(&mut prefs_state, preferences::Message::LocationSelected(0));
@@ -392,7 +730,12 @@ mod tests {
         let manager = ConfigManager::for_path(config_path.clone());
         let config = manager.load_config();
 
-        assert_eq!(config.get_api_token().unwrap(), legacy_token);
+        assert_eq!(
+            config
+                .get_api_token(&WeatherApiProvider::OpenWeather)
+                .unwrap(),
+            legacy_token
+        );
 
         let saved = std::fs::read_to_string(&config_path).unwrap();
         assert!(
@@ -534,11 +877,17 @@ mod tests {
     /// This test covers both successful creation and error handling for missing API keys.
     #[test]
     fn test_weather_provider_factory() {
+        let client = reqwest::Client::new();
+        let endpoints = Endpoints::default();
+
         // Test OpenWeather provider creation
         let result = WeatherProviderFactory::create_provider(
             &WeatherApiProvider::OpenWeather,
             Some("test_key".to_string()),
             Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
         );
         assert!(result.is_ok());
 
@@ -547,6 +896,9 @@ mod tests {
             &WeatherApiProvider::OpenWeather,
             None,
             Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
         );
         assert!(result.is_err());
 
@@ -555,6 +907,9 @@ mod tests {
             &WeatherApiProvider::GoogleWeather,
             None,
             Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
         );
         assert!(result.is_err());
 
@@ -562,8 +917,101 @@ mod tests {
             &WeatherApiProvider::GoogleWeather,
             Some("test_key".to_string()),
             Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_ok());
+
+        // Open-Meteo is keyless -- no token is fine, and a stray one is
+        // simply ignored.
+        let result = WeatherProviderFactory::create_provider(
+            &WeatherApiProvider::OpenMeteo,
+            None,
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_ok());
+
+        let result = WeatherProviderFactory::create_provider(
+            &WeatherApiProvider::OpenMeteo,
+            Some("ignored".to_string()),
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_ok());
+
+        let result = WeatherProviderFactory::create_provider(
+            &WeatherApiProvider::NationalWeatherService,
+            None,
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
         );
         assert!(result.is_ok());
+
+        let result = WeatherProviderFactory::create_provider(
+            &WeatherApiProvider::MetNorway,
+            None,
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_ok());
+
+        // A chain needs the primary's token like `create_provider` does,
+        // but a keyed fallback without one is skipped, not an error. Each
+        // keyed provider is asked for its own token; keyless ones never are.
+        let asked = std::cell::RefCell::new(Vec::new());
+        let result = WeatherProviderFactory::create_chain(
+            &[
+                WeatherApiProvider::GoogleWeather,
+                WeatherApiProvider::OpenWeather,
+                WeatherApiProvider::OpenMeteo,
+            ],
+            |provider| {
+                asked.borrow_mut().push(provider.clone());
+                (*provider == WeatherApiProvider::GoogleWeather).then(|| "test_key".to_string())
+            },
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            asked.into_inner(),
+            vec![
+                WeatherApiProvider::GoogleWeather,
+                WeatherApiProvider::OpenWeather
+            ]
+        );
+
+        let result = WeatherProviderFactory::create_chain(
+            &[WeatherApiProvider::GoogleWeather],
+            |_| None,
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_err());
+
+        let result = WeatherProviderFactory::create_chain(
+            &[],
+            |_| None,
+            Language::English,
+            RetryPolicy::default(),
+            &client,
+            &endpoints,
+        );
+        assert!(result.is_err());
     }
 
     /// Verifies that the `AppConfig` can be safely shared and mutated across threads using `Arc<Mutex<>>`.
@@ -581,9 +1029,12 @@ mod tests {
                 city: "Test City".to_string(),
                 state: "TS".to_string(),
                 country: "TC".to_string(),
+                ..Default::default()
             },
         }];
-        config.set_api_token("test_token").unwrap();
+        config
+            .set_api_token(&WeatherApiProvider::OpenWeather, "test_token")
+            .unwrap();
 
         let shared_config = Arc::new(Mutex::new(config));
 
@@ -591,26 +1042,38 @@ mod tests {
         {
             let config_guard = shared_config.lock().unwrap();
             assert_eq!(config_guard.locations[0].location.city, "Test City");
-            assert_eq!(config_guard.get_api_token().unwrap(), "test_token");
+            assert_eq!(
+                config_guard
+                    .get_api_token(&WeatherApiProvider::OpenWeather)
+                    .unwrap(),
+                "test_token"
+            );
         }
 
         // Test writing to the Arc<Mutex<AppConfig>>
         {
             let mut config_guard = shared_config.lock().unwrap();
             config_guard.locations[0].location.city = "Updated City".to_string();
-            config_guard.set_api_token("new_token").unwrap();
+            config_guard
+                .set_api_token(&WeatherApiProvider::OpenWeather, "new_token")
+                .unwrap();
         }
 
         // Verify the changes
         {
             let config_guard = shared_config.lock().unwrap();
             assert_eq!(config_guard.locations[0].location.city, "Updated City");
-            assert_eq!(config_guard.get_api_token().unwrap(), "new_token");
+            assert_eq!(
+                config_guard
+                    .get_api_token(&WeatherApiProvider::OpenWeather)
+                    .unwrap(),
+                "new_token"
+            );
         }
     }
 
-    /// Verifies that `aggregate_daily` buckets 3-hourly entries by UTC calendar
-    /// date, computes correct min/max temperatures per day, picks the midday
+    /// Verifies that `aggregate_daily` buckets 3-hourly entries by calendar
+    /// date (a UTC location here; see `test_forecast_buckets_by_local_date`), computes correct min/max temperatures per day, picks the midday
     /// entry's condition as the day's dominant/representative condition, pulls
     /// feels-like/humidity/wind/pressure/visibility from that same
     /// representative entry, and takes the **max** `pop` across the whole day
@@ -622,8 +1085,11 @@ mod tests {
         };
         use crate::weather_api::openweather_api::{Main, Weather, Wind};
 
-        let item = |dt_txt: &str, temp: f64, main: &str, pop: f64| ForecastListItem {
-            dt: 0,
+        let item = |time: &str, temp: f64, main: &str, pop: f64| ForecastListItem {
+            dt: format!("{time}Z")
+                .parse::<jiff::Timestamp>()
+                .unwrap()
+                .as_second(),
             main: Main {
                 temp,
                 feels_like: temp,
@@ -642,12 +1108,12 @@ mod tests {
             },
             pop,
             visibility: 10_000,
-            dt_txt: dt_txt.to_string(),
         };
 
         let raw = RawForecastResponse {
             city: ForecastCity {
                 name: "Test City".to_string(),
+                timezone: 0,
             },
             list: vec![
                 // Day 1: cold overnight, midday is Rain -- should be the dominant condition.
@@ -692,6 +1158,102 @@ mod tests {
         assert_eq!(day2.pop, 0.5);
     }
 
+    /// Verifies that `aggregate_daily` buckets by the location's local date
+    /// and picks the local-midday entry, for a location east (Tokyo, UTC+9)
+    /// and west (Honolulu, UTC-10) of UTC. By UTC date, Tokyo's 16 entries
+    /// would straddle three days and Honolulu's 14:00 rain would land on the
+    /// wrong day.
+    #[test]
+    fn test_forecast_buckets_by_local_date() {
+        use crate::weather_api::forecast::{RawForecastResponse, aggregate_daily};
+
+        let tokyo: RawForecastResponse = serde_json::from_str(include_str!(
+            "../tests/fixtures/openweather/forecast_tokyo.json"
+        ))
+        .unwrap();
+        let forecast = aggregate_daily(tokyo);
+        let dates: Vec<&str> = forecast.days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2026-07-02", "2026-07-03"]);
+        let today = &forecast.days[0];
+        assert_eq!(today.temp_min, 21.4);
+        assert_eq!(today.temp_max, 28.0);
+        assert_eq!(today.description, "light rain");
+        assert_eq!(today.pop, 0.65);
+        let tomorrow = &forecast.days[1];
+        assert_eq!(tomorrow.temp_min, 22.5);
+        assert_eq!(tomorrow.temp_max, 31.4);
+        assert_eq!(tomorrow.description, "clear sky");
+
+        let honolulu: RawForecastResponse = serde_json::from_str(include_str!(
+            "../tests/fixtures/openweather/forecast_honolulu.json"
+        ))
+        .unwrap();
+        let forecast = aggregate_daily(honolulu);
+        let dates: Vec<&str> = forecast.days.iter().map(|d| d.date.as_str()).collect();
+        assert_eq!(dates, ["2026-07-01", "2026-07-02", "2026-07-03"]);
+        // 14:00 local on the 2nd is 00:00 UTC on the 3rd.
+        let second = &forecast.days[1];
+        assert_eq!(second.description, "moderate rain");
+        assert_eq!(second.temp_min, 23.8);
+        assert_eq!(second.temp_max, 29.9);
+        assert_eq!(second.pop, 0.8);
+        // The afternoon of the 1st starts at its own 14:00 entry.
+        assert_eq!(forecast.days[0].description, "clear sky");
+    }
+
+    /// Verifies that `hourly_from_list` keeps OpenWeatherMap's 3-hourly
+    /// entries unaggregated, each with its own condition and rain chance,
+    /// and stops at the 48-hour horizon.
+    #[test]
+    fn test_hourly_from_list() {
+        use crate::weather_api::forecast::{
+            ForecastCity, ForecastListItem, MAX_FORECAST_HOURS, RawForecastResponse,
+            hourly_from_list,
+        };
+        use crate::weather_api::openweather_api::{Main, Weather, WeatherSymbol, Wind};
+
+        let start = 1_783_000_800;
+        let list = (0..40)
+            .map(|step| {
+                let main = if step == 2 { "Rain" } else { "Clouds" };
+                ForecastListItem {
+                    dt: start + step * 3 * 3600,
+                    main: Main {
+                        temp: step as f64,
+                        feels_like: 0.0,
+                        temp_min: 0.0,
+                        temp_max: 0.0,
+                        pressure: 1013,
+                        humidity: 50,
+                    },
+                    weather: vec![Weather {
+                        main: main.to_string(),
+                        description: main.to_lowercase(),
+                    }],
+                    wind: Wind { speed: 0.0, deg: 0 },
+                    pop: if step == 2 { 0.8 } else { 0.0 },
+                    visibility: 10_000,
+                }
+            })
+            .collect();
+        let raw = RawForecastResponse {
+            city: ForecastCity {
+                name: "Test City".to_string(),
+                timezone: -18_000,
+            },
+            list,
+        };
+
+        let hourly = hourly_from_list(&raw);
+        assert_eq!(hourly.timezone, -18_000);
+        assert_eq!(hourly.hours.len(), MAX_FORECAST_HOURS / 3);
+        let rain = &hourly.hours[2];
+        assert_eq!(rain.dt, start + 6 * 3600);
+        assert_eq!(rain.pop, 0.8);
+        assert_eq!(rain.symbol, WeatherSymbol::Rain);
+        assert_eq!(rain.description, "rain");
+    }
+
     /// Verifies the hand-authored Lottie assets under `assets/lottie/` are
     /// valid, parseable compositions with a non-empty, finite frame range --
     /// catches malformed JSON before it ever reaches the animated-icon widget.
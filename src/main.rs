@@ -5,8 +5,9 @@
 //! All application logic, including UI construction, state management, and API
 //! calls, is handled within the `open_weather_wizard` library crate, organized into
 //! the `config`, `app`, `ui`, and `weather_api` modules. `cli` (this bin only,
-//! not part of the library) adds a `--headless` mode -- see its own doc comment.
-use clap::Parser;
+//! not part of the library) adds a `--headless` mode and subcommands for
+//! managing settings -- see its own doc comment.
+use clap::{CommandFactory, Parser};
 use env_logger::{self, Builder};
 use log::{self, LevelFilter};
 
@@ -25,6 +26,9 @@ mod weather_api;
 /// (the `--headless` path) never touches `app::run()`, so it's free to spin up its
 /// own runtime instead.
 fn main() -> iced::Result {
+    // Answers a shell's Tab press (see `cli::completions`) and exits, or
+    // does nothing when the shell isn't asking.
+    clap_complete::CompleteEnv::with_factory(cli::Cli::command).complete();
     let cli = cli::Cli::parse();
 
     // Info-level logging (config loads, fetch lifecycle, etc.) is useful
@@ -48,7 +52,7 @@ fn main() -> iced::Result {
         .parse_default_env()
         .init();
 
-    if cli.headless {
+    if cli.headless || cli.command.is_some() {
         cli::run(&cli);
     }
 
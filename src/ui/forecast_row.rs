@@ -30,13 +30,13 @@ const BOLD: Font = Font {
 pub(crate) const CARD_WIDTH: f32 = 120.0;
 pub(crate) const CARD_SPACING: f32 = 12.0;
 
-/// Total width `n` cards need laid out in a row with `CARD_SPACING` between
-/// them (no trailing gap after the last card).
-fn cards_width(n: usize) -> f32 {
+/// Total width `n` cards of `card_width` need laid out in a row with
+/// `CARD_SPACING` between them (no trailing gap after the last card).
+fn cards_width(n: usize, card_width: f32) -> f32 {
     if n == 0 {
         return 0.0;
     }
-    n as f32 * CARD_WIDTH + (n - 1) as f32 * CARD_SPACING
+    n as f32 * card_width + (n - 1) as f32 * CARD_SPACING
 }
 
 /// Tall enough for `day_card`'s content (date + 48px icon + hi/lo + short
@@ -64,49 +64,56 @@ pub fn view<'a>(
         ),
         Some(response) => {
             let days = &response.days;
-
-            // `scrollable` gives its content an *infinite* max-width limit
-            // along the scrolling axis (so content is actually allowed to
-            // exceed the viewport and scroll) -- which means a `Length::Fill`
-            // container placed inside it never resolves to "the visible
-            // viewport width" and can't be used to center content there.
-            // `responsive` sidesteps this by measuring the real available
-            // size at layout time: center a plain (non-scrolling) row when
-            // the cards fit, or fall back to the hidden-scrollbar carousel
-            // only once they don't.
-            Some(
-                responsive(move |size| {
-                    let cards = || {
-                        days.iter().enumerate().map(|(index, day)| {
-                            day_card(day, index, use_fahrenheit, tracker, selected)
-                        })
-                    };
-
-                    if cards_width(days.len()) <= size.width {
-                        container(row(cards()).spacing(CARD_SPACING))
-                            .center_x(Length::Fill)
-                            .into()
-                    } else {
-                        // A carousel, not a document: the scrollbar
-                        // track/thumb are hidden (Scrollbar::hidden() zeroes
-                        // their width), but the row still scrolls via
-                        // trackpad/mouse-wheel/click-drag -- hiding the
-                        // scrollbar doesn't disable scrolling itself.
-                        scrollable(row(cards()).spacing(CARD_SPACING))
-                            .direction(scrollable::Direction::Horizontal(
-                                scrollable::Scrollbar::hidden(),
-                            ))
-                            .width(Length::Fill)
-                            .into()
-                    }
-                })
-                .height(ROW_HEIGHT)
-                .into(),
-            )
+            Some(carousel(days.len(), CARD_WIDTH, ROW_HEIGHT, move || {
+                days.iter()
+                    .enumerate()
+                    .map(|(index, day)| day_card(day, index, use_fahrenheit, tracker, selected))
+                    .collect()
+            }))
         }
     }
 }
 
+/// Lays out `count` cards of `card_width` (built by `cards`) centered when
+/// they fit, or as a horizontally-scrolling carousel when they don't.
+/// Shared with `ui::hourly_row`.
+///
+/// `scrollable` gives its content an *infinite* max-width limit along the
+/// scrolling axis (so content is actually allowed to exceed the viewport
+/// and scroll) -- which means a `Length::Fill` container placed inside it
+/// never resolves to "the visible viewport width" and can't be used to
+/// center content there. `responsive` sidesteps this by measuring the real
+/// available size at layout time: center a plain (non-scrolling) row when
+/// the cards fit, or fall back to the hidden-scrollbar carousel only once
+/// they don't.
+pub(crate) fn carousel<'a>(
+    count: usize,
+    card_width: f32,
+    height: f32,
+    cards: impl Fn() -> Vec<Element<'a, Message>> + 'a,
+) -> Element<'a, Message> {
+    responsive(move |size| {
+        if cards_width(count, card_width) <= size.width {
+            container(row(cards()).spacing(CARD_SPACING))
+                .center_x(Length::Fill)
+                .into()
+        } else {
+            // A carousel, not a document: the scrollbar track/thumb are
+            // hidden (Scrollbar::hidden() zeroes their width), but the row
+            // still scrolls via trackpad/mouse-wheel/click-drag -- hiding
+            // the scrollbar doesn't disable scrolling itself.
+            scrollable(row(cards()).spacing(CARD_SPACING))
+                .direction(scrollable::Direction::Horizontal(
+                    scrollable::Scrollbar::hidden(),
+                ))
+                .width(Length::Fill)
+                .into()
+        }
+    })
+    .height(height)
+    .into()
+}
+
 fn day_card<'a>(
     day: &'a ForecastDay,
     index: usize,
@@ -0,0 +1,98 @@
+//! # Hourly Row
+//!
+//! A horizontally-scrollable strip of hour cards (time, icon, temperature,
+//! chance of rain) for the next 48 hours, rendered under the
+//! current-conditions card -- the daily cards below it can say *that* it
+//! will rain today, but not *when*. Same carousel layout as
+//! `ui::forecast_row`, with narrower cards. Omitted entirely until the
+//! first hourly fetch succeeds (and for a provider that returns no hours),
+//! rather than showing a placeholder: it's a supplement to the daily row,
+//! not something the screen looks broken without.
+
+use iced::widget::{column, container, text};
+use iced::{Alignment, Element, Font, font};
+
+use crate::app::Message;
+use crate::ui::forecast_row::carousel;
+use crate::ui::temperature::{celsius_to_display, format_local_time, unit_symbol};
+use crate::ui::{icons, style};
+use crate::weather_api::forecast::{ForecastHour, HourlyForecast};
+
+const BOLD: Font = Font {
+    weight: font::Weight::Bold,
+    ..Font::DEFAULT
+};
+
+/// `hour_card`'s content column width (56) plus its container's padding
+/// (8 on each side).
+const CARD_WIDTH: f32 = 72.0;
+
+/// Time + 32px icon + temperature + rain chance, plus padding and a little
+/// slack -- see `forecast_row::ROW_HEIGHT` for why it's set explicitly.
+const ROW_HEIGHT: f32 = 124.0;
+
+/// Below this, a rain chance isn't worth a label -- a strip full of "3%"
+/// buries the hours that matter.
+const MIN_SHOWN_POP: f64 = 0.1;
+
+/// Renders the hourly strip, or `None` if there's nothing to show.
+pub fn view(hourly: Option<&HourlyForecast>, use_fahrenheit: bool) -> Option<Element<'_, Message>> {
+    let hourly = hourly.filter(|hourly| !hourly.hours.is_empty())?;
+    let hours = &hourly.hours;
+    Some(carousel(hours.len(), CARD_WIDTH, ROW_HEIGHT, move || {
+        hours
+            .iter()
+            .enumerate()
+            .map(|(index, hour)| hour_card(hour, index == 0, hourly.timezone, use_fahrenheit))
+            .collect()
+    }))
+}
+
+/// "3 PM" rather than `format_local_time`'s "3:00 PM" -- every step starts
+/// on the hour, so the minutes are noise at this size.
+fn hour_label(unix_ts: i64, tz_offset_secs: i64) -> String {
+    format_local_time(unix_ts, tz_offset_secs).replace(":00", "")
+}
+
+fn hour_card(
+    hour: &ForecastHour,
+    is_first: bool,
+    tz_offset_secs: i64,
+    use_fahrenheit: bool,
+) -> Element<'_, Message> {
+    let label = if is_first {
+        "Now".to_string()
+    } else {
+        hour_label(hour.dt, tz_offset_secs)
+    };
+    let unit = unit_symbol(use_fahrenheit);
+    let temp = celsius_to_display(hour.temp, use_fahrenheit);
+    let pop = if hour.pop >= MIN_SHOWN_POP {
+        format!("\u{2602} {:.0}%", hour.pop * 100.0)
+    } else {
+        String::new()
+    };
+
+    container(
+        column![
+            text(label).size(12).font(BOLD).style(if is_first {
+                style::accent
+            } else {
+                style::default_text
+            }),
+            icons::view(hour.symbol, 32.0),
+            text(format!("{temp:.0}{unit}")).size(13).font(BOLD),
+            text(pop).size(11).style(style::accent),
+        ]
+        .spacing(4)
+        .align_x(Alignment::Center)
+        .width(56),
+    )
+    .padding(8)
+    .style(if is_first {
+        style::day_card_today
+    } else {
+        style::day_card
+    })
+    .into()
+}
@@ -1,8 +1,9 @@
 //! # Main Screen
 //!
 //! Renders the current-conditions view: weather icon, location, temperature,
-//! description, and humidity, plus Loading/Error states. This is the content of
-//! the app's main window (see `src/app.rs::view`).
+//! description, and humidity, plus Loading/Error states and a banner when
+//! refreshing keeps failing. This is the content of the app's main window
+//! (see `src/app.rs::view`).
 
 use std::time::Instant;
 
@@ -16,7 +17,7 @@ use crate::ui::temperature::{
     pressure_to_display, pressure_unit, speed_to_display, speed_unit, unit_symbol,
 };
 use crate::ui::transition::ValueTracker;
-use crate::ui::{forecast_row, icons, location_switcher, skeleton, style};
+use crate::ui::{forecast_row, hourly_row, icons, location_switcher, skeleton, style};
 use crate::weather_api::alerts::{AlertSeverity, WeatherAlert};
 use crate::weather_api::forecast::ForecastDay;
 use crate::weather_api::openweather_api::{ApiResponse, Weather, get_weather_symbol};
@@ -147,6 +148,10 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
         layout = layout.push(switcher);
     }
 
+    if let Some(banner) = stale_banner(state, is_refreshing) {
+        layout = layout.push(banner);
+    }
+
     layout = layout.push(
         container(content)
             .width(Length::Fill)
@@ -155,6 +160,14 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
             .style(style::panel),
     );
 
+    // Hours only make sense next to live conditions, not a selected
+    // forecast day's detail view.
+    if state.selected_forecast_day.is_none()
+        && let Some(hourly) = hourly_row::view(state.hourly.as_ref(), state.config.use_fahrenheit)
+    {
+        layout = layout.push(hourly);
+    }
+
     if let Some(forecast) = forecast_row::view(
         &state.forecast,
         state.config.use_fahrenheit,
@@ -175,11 +188,57 @@ pub fn view(state: &AppState) -> Element<'_, Message> {
     // bottom of the window rather than scrolling away with the content.
     column![
         scrollable(layout).height(Length::Fill),
-        provider_ribbon(&state.config.weather_provider),
+        provider_ribbon(
+            &state.config.weather_provider,
+            state.weather.data().and_then(|w| w.provider.as_ref()),
+        ),
     ]
     .into()
 }
 
+/// Shown over data that's gone stale because refreshing keeps failing (see
+/// `AppState::is_stale`): how old it is, why the last refresh failed, and
+/// a Retry button -- disabled, like the toolbar's Refresh, while a fetch is
+/// already in flight.
+fn stale_banner(state: &AppState, is_refreshing: bool) -> Option<Element<'_, Message>> {
+    if !state.is_stale() {
+        return None;
+    }
+    let shown = match state.last_updated {
+        Some(updated) => format!("showing weather from {}", ago(updated.elapsed().as_secs())),
+        None => "showing saved weather".to_string(),
+    };
+    let mut details = column![
+        text(format!("Couldn't refresh \u{2014} {shown}"))
+            .size(14)
+            .font(BOLD)
+            .style(style::warning)
+    ]
+    .spacing(4)
+    .width(Length::Fill);
+    if let Some(error) = &state.last_error {
+        details = details.push(text(error).size(12).style(style::muted));
+    }
+
+    let content = row![
+        text("\u{26A0}").size(16).style(style::warning),
+        details,
+        button(text("Retry").size(12))
+            .on_press_maybe((!is_refreshing).then_some(Message::RefreshRequested))
+            .style(style::secondary_button),
+    ]
+    .spacing(8)
+    .align_y(Alignment::Center);
+
+    Some(
+        container(content)
+            .padding(12)
+            .width(Length::Fill)
+            .style(style::warning_banner)
+            .into(),
+    )
+}
+
 fn alerts_view(alerts: &[WeatherAlert]) -> Element<'_, Message> {
     if alerts.is_empty() {
         return iced::widget::Space::new().into();
@@ -246,37 +305,52 @@ fn alerts_view(alerts: &[WeatherAlert]) -> Element<'_, Message> {
 }
 
 /// A thin footer strip naming whichever provider is currently powering the
-/// displayed data, linked to that provider's own homepage -- config-driven
-/// rather than tracked per-fetch, since a fetch always uses whatever
-/// provider is currently configured (there's no per-request override). Uses
-/// its own display label rather than `WeatherApiProvider`'s `Display` impl
-/// (which renders "OpenWeather" as one word, matching the service's own
-/// branding elsewhere, e.g. Preferences).
-fn provider_ribbon(provider: &WeatherApiProvider) -> Element<'_, Message> {
+/// displayed data, linked to that provider's own homepage. That's whoever
+/// actually answered the last current-conditions fetch (`answered`, stamped
+/// by `FallbackProvider`), marked "(fallback)" when it isn't the configured
+/// one -- falling back to the configured provider itself before the first
+/// fetch lands. Uses its own display label rather than
+/// `WeatherApiProvider`'s `Display` impl (which renders "OpenWeather" as
+/// one word, matching the service's own branding elsewhere, e.g.
+/// Preferences).
+fn provider_ribbon<'a>(
+    configured: &'a WeatherApiProvider,
+    answered: Option<&'a WeatherApiProvider>,
+) -> Element<'a, Message> {
+    let provider = answered.unwrap_or(configured);
+    let is_fallback = provider != configured;
     let (label, homepage) = match provider {
         WeatherApiProvider::OpenWeather => ("Open Weather", "https://openweathermap.org/"),
         WeatherApiProvider::GoogleWeather => (
             "Google Weather",
             "https://mapsplatform.google.com/maps-products/weather/",
         ),
+        WeatherApiProvider::OpenMeteo => ("Open-Meteo", "https://open-meteo.com/"),
+        WeatherApiProvider::NationalWeatherService => {
+            ("National Weather Service", "https://www.weather.gov/")
+        }
+        WeatherApiProvider::MetNorway => ("MET Norway", "https://www.met.no/"),
     };
 
-    container(
-        row![
-            space::horizontal(),
-            text("Powered by").size(11).style(style::muted),
-            button(text(label).size(11))
-                .on_press(Message::OpenUrl(homepage.to_string()))
-                .style(style::link_button)
-                .padding(0),
-        ]
-        .spacing(4)
-        .align_y(Alignment::Center),
-    )
-    .width(Length::Fill)
-    .padding(6)
-    .style(style::ribbon)
-    .into()
+    let mut credit = row![
+        space::horizontal(),
+        text("Powered by").size(11).style(style::muted),
+        button(text(label).size(11))
+            .on_press(Message::OpenUrl(homepage.to_string()))
+            .style(style::link_button)
+            .padding(0),
+    ]
+    .spacing(4)
+    .align_y(Alignment::Center);
+    if is_fallback {
+        credit = credit.push(text("(fallback)").size(11).style(style::muted));
+    }
+
+    container(credit)
+        .width(Length::Fill)
+        .padding(6)
+        .style(style::ribbon)
+        .into()
 }
 
 /// The left-hand hero: icon, location, big temperature, and a short
@@ -609,13 +683,21 @@ fn toolbar_button<'a>(
 }
 
 /// Formats "Updated just now" / "Updated Xm ago" from the last successful
-/// fetch time. `None` (nothing fetched yet) renders nothing.
+/// fetch time -- in hours or days for data restored from the cache (see
+/// `app::show_cached`). `None` (nothing fetched yet) renders nothing.
 fn updated_label(last_updated: Option<Instant>) -> Option<String> {
-    let elapsed = last_updated?.elapsed();
-    let label = if elapsed.as_secs() < 60 {
-        "Updated just now".to_string()
-    } else {
-        format!("Updated {}m ago", elapsed.as_secs() / 60)
-    };
-    Some(label)
+    Some(format!(
+        "Updated {}",
+        ago(last_updated?.elapsed().as_secs())
+    ))
+}
+
+/// "just now", "5m ago", "2h ago", or "3d ago".
+fn ago(secs: u64) -> String {
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3600 => format!("{}m ago", secs / 60),
+        3600..86400 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
 }
@@ -6,6 +6,7 @@
 
 pub mod about;
 pub mod forecast_row;
+pub mod hourly_row;
 pub mod icons;
 pub mod location_switcher;
 pub mod lottie;
@@ -9,20 +9,27 @@ use iced::widget::{
     button, column, container, pick_list, row, scrollable, space, text, text_input, toggler,
 };
 use iced::{Alignment, Element, Font, Length, font};
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 use crate::config::{
     AppConfig, Language, LocationConfig, SavedLocation, ThemePreference, WeatherApiProvider,
 };
 use crate::ui::style;
+use crate::weather_api::geocoding::GeocodeCandidate;
+use crate::weather_api::http::{HttpSettings, parse_proxy};
 
 const BOLD: Font = Font {
     weight: font::Weight::Bold,
     ..Font::DEFAULT
 };
 
-const PROVIDERS: [WeatherApiProvider; 2] = [
+pub(crate) const PROVIDERS: [WeatherApiProvider; 5] = [
     WeatherApiProvider::OpenWeather,
     WeatherApiProvider::GoogleWeather,
+    WeatherApiProvider::OpenMeteo,
+    WeatherApiProvider::NationalWeatherService,
+    WeatherApiProvider::MetNorway,
 ];
 
 const THEME_PREFERENCES: [ThemePreference; 3] = [
@@ -31,7 +38,7 @@ const THEME_PREFERENCES: [ThemePreference; 3] = [
     ThemePreference::System,
 ];
 
-const LANGUAGES: [Language; 12] = [
+pub(crate) const LANGUAGES: [Language; 12] = [
     Language::English,
     Language::Spanish,
     Language::French,
@@ -122,27 +129,97 @@ pub struct LocationEntry {
     pub city: String,
     pub state: String,
     pub country: String,
+    /// Whether this entry is defined by `lat_input`/`lon_input` alone
+    /// (`LocationConfig::is_coordinate_only`) -- swaps the City/State/
+    /// Country fields for Latitude/Longitude ones. Saving clears whichever
+    /// set of fields isn't in use.
+    pub by_coordinates: bool,
+    /// Raw text of the Latitude/Longitude fields, parsed (and range-checked
+    /// by `validation_errors`) only on Save, so a half-typed "-4" isn't
+    /// rejected mid-keystroke.
+    pub lat_input: String,
+    pub lon_input: String,
+    /// A place-named entry's resolved coordinates, carried through from
+    /// `LocationConfig` untouched (they aren't shown in the form) until
+    /// `city`/`state`/`country` is edited, which `forget_coordinates`
+    /// clears -- they'd describe the old place.
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub timezone: Option<String>,
+}
+
+impl LocationEntry {
+    fn forget_coordinates(&mut self) {
+        self.lat = None;
+        self.lon = None;
+        self.timezone = None;
+    }
+
+    /// `lat_input`/`lon_input` as a coordinate-only location, or the
+    /// problem with them.
+    fn parsed_coordinates(&self) -> Result<LocationConfig, String> {
+        let lat = self
+            .lat_input
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| "needs a numeric latitude".to_string())?;
+        let lon = self
+            .lon_input
+            .trim()
+            .parse::<f64>()
+            .map_err(|_| "needs a numeric longitude".to_string())?;
+        LocationConfig::from_coordinates(lat, lon)
+    }
 }
 
 impl From<&SavedLocation> for LocationEntry {
     fn from(saved: &SavedLocation) -> Self {
+        let by_coordinates = saved.location.is_coordinate_only();
+        let coordinate_input = |value: Option<f64>| match value {
+            Some(value) if by_coordinates => value.to_string(),
+            _ => String::new(),
+        };
         Self {
             name: saved.name.clone(),
             city: saved.location.city.clone(),
             state: saved.location.state.clone(),
             country: saved.location.country.clone(),
+            by_coordinates,
+            lat_input: coordinate_input(saved.location.lat),
+            lon_input: coordinate_input(saved.location.lon),
+            lat: saved.location.lat,
+            lon: saved.location.lon,
+            timezone: saved.location.timezone.clone(),
         }
     }
 }
 
 impl From<&LocationEntry> for SavedLocation {
     fn from(entry: &LocationEntry) -> Self {
+        if entry.by_coordinates {
+            // `validation_errors` blocks Save on unparsable input, so the
+            // fallback is never actually saved.
+            let location = entry
+                .parsed_coordinates()
+                .map(|location| LocationConfig {
+                    timezone: entry.timezone.clone(),
+                    ..location
+                })
+                .unwrap_or_default();
+            return Self {
+                name: entry.name.clone(),
+                location,
+            };
+        }
         Self {
             name: entry.name.clone(),
             location: LocationConfig {
                 city: entry.city.clone(),
                 state: entry.state.clone(),
                 country: entry.country.clone(),
+                lat: entry.lat,
+                lon: entry.lon,
+                timezone: entry.timezone.clone(),
             },
         }
     }
@@ -151,7 +228,17 @@ impl From<&LocationEntry> for SavedLocation {
 #[derive(Debug, Clone)]
 pub struct State {
     pub provider: WeatherApiProvider,
-    pub token_input: String,
+    /// Draft of `AppConfig.fallback_providers`. Kept in `PROVIDERS` order
+    /// by `FallbackToggled`, so the togglers' top-to-bottom order is the
+    /// order they're tried in. May still name `provider` itself (after
+    /// switching the primary to a former fallback) -- `apply_to` drops it.
+    pub fallback_providers: Vec<WeatherApiProvider>,
+    /// Draft API token per keyed provider, so switching the provider picker
+    /// back and forth doesn't lose what was typed (each provider has its
+    /// own keychain entry -- see `AppConfig::set_api_token`). Filled in
+    /// asynchronously via `app::Message::ApiTokenLoaded`; a provider with
+    /// no entry here just hasn't been loaded or typed into yet.
+    pub token_inputs: HashMap<WeatherApiProvider, String>,
     /// Every saved location, in display order -- a draft copy of
     /// `AppConfig.locations`, discarded on Cancel like every other field
     /// here. Always has at least one entry; `RemoveLocationRequested`
@@ -182,6 +269,12 @@ pub struct State {
     pub use_fahrenheit: bool,
     pub launch_at_login: bool,
     pub refresh_interval: RefreshIntervalPreset,
+    /// Draft of `AppConfig.http`'s fields, as typed -- see `http_settings`.
+    /// Empty proxy/CA bundle fields mean none.
+    pub proxy_input: String,
+    pub ca_bundle_input: String,
+    pub connect_timeout_input: String,
+    pub timeout_input: String,
     /// Set by `app::boot` when this window was opened automatically because
     /// no config file existed yet (see `ConfigManager::config_exists`).
     /// Purely cosmetic -- swaps in a welcome banner (`view`) and the
@@ -198,6 +291,17 @@ pub struct State {
     /// convenience prefill, not a required step; the fields can always be
     /// typed in by hand instead.
     pub location_detection_error: Option<String>,
+    /// Whether a place-name search (`Message::SearchLocationRequested`,
+    /// intercepted by `app::update`) is in flight -- same idea as
+    /// `is_detecting_location`.
+    pub is_searching_location: bool,
+    /// The last search's matches, listed under the form for the user to
+    /// pick one (`Message::LocationCandidatePicked`) -- a same-named city
+    /// in another state is otherwise indistinguishable until its weather
+    /// looks wrong. Cleared once picked.
+    pub location_candidates: Vec<GeocodeCandidate>,
+    /// Set if the last search failed or matched nothing.
+    pub location_search_error: Option<String>,
     /// Whether a connection test (`Message::TestConnectionRequested`,
     /// intercepted by `app::update`) is currently in flight -- disables the
     /// "Verify API" button and swaps its label, same idea as
@@ -211,7 +315,7 @@ pub struct State {
 
 impl State {
     /// Builds the form's initial state from the persisted config -- except
-    /// `token_input`, deliberately left empty here rather than reading it
+    /// `token_inputs`, deliberately left empty here rather than reading it
     /// synchronously via `AppConfig::get_api_token`. That's a blocking OS
     /// keychain call that can pop a permission prompt (macOS re-prompts per
     /// build, or every time if the user picked "Allow" over "Always
@@ -224,7 +328,8 @@ impl State {
     pub fn from_config(config: &AppConfig) -> Self {
         Self {
             provider: config.weather_provider.clone(),
-            token_input: String::new(),
+            fallback_providers: config.fallback_providers.clone(),
+            token_inputs: HashMap::new(),
             locations: config.locations.iter().map(LocationEntry::from).collect(),
             selected_location_index: config
                 .current_location_index
@@ -242,25 +347,98 @@ impl State {
                 .refresh_interval_secs
                 .map(RefreshIntervalPreset::from_secs)
                 .unwrap_or_else(|| match config.weather_provider {
-                    WeatherApiProvider::GoogleWeather => RefreshIntervalPreset::FifteenMinutes,
+                    WeatherApiProvider::GoogleWeather
+                    | WeatherApiProvider::OpenMeteo
+                    | WeatherApiProvider::NationalWeatherService
+                    | WeatherApiProvider::MetNorway => RefreshIntervalPreset::FifteenMinutes,
                     WeatherApiProvider::OpenWeather => RefreshIntervalPreset::ThirtySeconds,
                 }),
+            proxy_input: config.http.proxy.clone().unwrap_or_default(),
+            ca_bundle_input: config
+                .http
+                .ca_bundle
+                .as_ref()
+                .map(|path| path.display().to_string())
+                .unwrap_or_default(),
+            connect_timeout_input: config.http.connect_timeout_secs.to_string(),
+            timeout_input: config.http.timeout_secs.to_string(),
             is_first_run: false,
             is_detecting_location: false,
             location_detection_error: None,
+            is_searching_location: false,
+            location_candidates: Vec::new(),
+            location_search_error: None,
             is_testing_connection: false,
             connection_test_result: None,
         }
     }
 
-    /// Writes the edited fields back into the shared `AppConfig`. Two steps
-    /// can fail: `set_api_token` (an OS keychain write) and
-    /// `update_auto_launch` (an OS-level login-item registration) --
-    /// everything else here is an in-memory field assignment.
+    /// Drops any in-flight/finished detection or search state -- it
+    /// belongs to whichever entry was selected when it started.
+    pub fn reset_location_lookups(&mut self) {
+        self.is_detecting_location = false;
+        self.location_detection_error = None;
+        self.is_searching_location = false;
+        self.location_candidates.clear();
+        self.location_search_error = None;
+    }
+
+    /// The draft API token for the currently-selected provider -- what the
+    /// API Token field shows and validation checks.
+    pub fn token_input(&self) -> &str {
+        self.token_inputs
+            .get(&self.provider)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+
+    /// The Network fields as `HttpSettings`.
+    ///
+    /// # Errors
+    /// The first field that doesn't parse -- also listed by
+    /// `validation_errors`.
+    pub fn http_settings(&self) -> Result<HttpSettings, String> {
+        let seconds = |input: &str, label: &str| {
+            input
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("{label} must be a whole number of seconds (0 for none)."))
+        };
+        let proxy = Some(self.proxy_input.trim())
+            .filter(|proxy| !proxy.is_empty())
+            .map(|proxy| parse_proxy(proxy).map(|_| proxy.to_string()))
+            .transpose()?;
+        Ok(HttpSettings {
+            connect_timeout_secs: seconds(&self.connect_timeout_input, "Connect timeout")?,
+            timeout_secs: seconds(&self.timeout_input, "Request timeout")?,
+            proxy,
+            ca_bundle: Some(self.ca_bundle_input.trim())
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
+        })
+    }
+
+    /// Writes the edited fields back into the shared `AppConfig`. Three
+    /// steps can fail: the Network settings building a client (an
+    /// unreadable CA bundle, checked before anything is written),
+    /// `set_api_token` (an OS keychain write, one per provider with a
+    /// non-empty draft token), and `update_auto_launch` (an OS-level
+    /// login-item registration) -- everything else here is an in-memory
+    /// field assignment.
     pub fn apply_to(&self, config: &mut AppConfig) -> Result<(), String> {
+        let http = self.http_settings()?;
+        http.client()?;
         config.weather_provider = self.provider.clone();
-        if !self.token_input.is_empty() {
-            config.set_api_token(&self.token_input)?;
+        config.fallback_providers = self
+            .fallback_providers
+            .iter()
+            .filter(|fallback| **fallback != self.provider)
+            .cloned()
+            .collect();
+        for (provider, token) in &self.token_inputs {
+            if provider.requires_api_token() && !token.is_empty() {
+                config.set_api_token(provider, token)?;
+            }
         }
         config.locations = self.locations.iter().map(SavedLocation::from).collect();
         // `current_location_index` has already been kept in sync as an
@@ -274,6 +452,7 @@ impl State {
         config.use_fahrenheit = self.use_fahrenheit;
         config.launch_at_login = self.launch_at_login;
         config.refresh_interval_secs = Some(self.refresh_interval.to_secs());
+        config.http = http;
         config
             .update_auto_launch()
             .map_err(|e| format!("Failed to configure auto-launch: {}", e))?;
@@ -298,6 +477,12 @@ impl State {
             if entry.name.trim().is_empty() {
                 errors.push("Every saved location needs a name.".to_string());
             }
+            if entry.by_coordinates {
+                if let Err(e) = entry.parsed_coordinates() {
+                    errors.push(format!("\"{label}\": {e}"));
+                }
+                continue;
+            }
             if entry.city.trim().is_empty() {
                 errors.push(format!("\"{label}\" needs a city."));
             }
@@ -305,9 +490,9 @@ impl State {
                 errors.push(format!("\"{label}\" needs a country."));
             }
         }
-        // Both providers require a token -- WeatherProviderFactory::create_provider
-        // errors out without one for either WeatherApiProvider variant.
-        if self.token_input.trim().is_empty() {
+        // WeatherProviderFactory::create_provider errors out without a token
+        // for every keyed provider; the keyless ones need none.
+        if self.provider.requires_api_token() && self.token_input().trim().is_empty() {
             errors.push(format!("API Token is required for {}.", self.provider));
         }
         // Validate Google Weather refresh interval constraint
@@ -318,6 +503,16 @@ impl State {
                 "Google Weather requires a refresh interval of at least 15 minutes.".to_string(),
             );
         }
+        match self.http_settings() {
+            Err(e) => errors.push(e),
+            Ok(HttpSettings {
+                ca_bundle: Some(path),
+                ..
+            }) if !path.is_file() => {
+                errors.push(format!("CA bundle {} doesn't exist.", path.display()));
+            }
+            Ok(_) => {}
+        }
 
         errors
     }
@@ -326,6 +521,9 @@ impl State {
 #[derive(Debug, Clone)]
 pub enum Message {
     ProviderSelected(WeatherApiProvider),
+    /// Turns a provider on/off as a fallback -- see `State::
+    /// fallback_providers`.
+    FallbackToggled(WeatherApiProvider, bool),
     TokenChanged(String),
     /// Switches which entry in `State::locations` the form fields below
     /// are showing/editing -- not which one the main window displays, see
@@ -335,6 +533,11 @@ pub enum Message {
     CityChanged(String),
     StateChanged(String),
     CountryChanged(String),
+    /// Switches the selected entry between a place name and coordinates --
+    /// see `LocationEntry::by_coordinates`.
+    CoordinatesModeToggled(bool),
+    LatitudeChanged(String),
+    LongitudeChanged(String),
     /// Appends a new blank entry to `State::locations` and selects it.
     AddLocationRequested,
     /// Removes the currently-selected entry from `State::locations` -- a
@@ -351,6 +554,10 @@ pub enum Message {
     UnitsToggled(bool),
     LaunchAtLoginToggled(bool),
     RefreshIntervalSelected(RefreshIntervalPreset),
+    ProxyChanged(String),
+    CaBundleChanged(String),
+    ConnectTimeoutChanged(String),
+    TimeoutChanged(String),
     /// The "Get an API key" link -- intercepted by the parent (see
     /// `src/app.rs`) and turned into `Message::OpenUrl`, since opening a
     /// browser is an app-level concern, not something this module does
@@ -362,6 +569,14 @@ pub enum Message {
     /// firing an async `Task` isn't something this module's synchronous
     /// `update` can do itself.
     DetectLocationRequested,
+    /// The "Search" button -- intercepted by the parent, which looks up
+    /// every place matching the selected entry's city/country
+    /// (`crate::weather_api::geocoding::search`) and reports back via the
+    /// app-level `Message::LocationSearched`.
+    SearchLocationRequested,
+    /// One of `State::location_candidates` was clicked: fills the selected
+    /// entry with it, coordinates included.
+    LocationCandidatePicked(usize),
     /// The "Verify API" button -- intercepted by the parent, which
     /// builds a provider from the *currently-typed* provider/token/location
     /// (not the saved config), fires a single `get_weather()` call, and
@@ -374,20 +589,33 @@ pub enum Message {
 }
 
 /// Mutates field-edit messages; `Save`/`Cancel`/`OpenUrl`/
-/// `DetectLocationRequested`/`TestConnectionRequested` are intercepted by the
+/// `DetectLocationRequested`/`SearchLocationRequested`/
+/// `TestConnectionRequested` are intercepted by the
 /// parent `AppState::update` (see `src/app.rs`) since they need access to
 /// `AppConfig`/the OS's URL opener/an async `Task` respectively.
+/// `ProviderSelected` is handled here too, but the parent routes it through
+/// first so it can load the newly-selected provider's token.
 pub fn update(state: &mut State, message: Message) {
     match message {
         Message::ProviderSelected(provider) => state.provider = provider,
-        Message::TokenChanged(value) => state.token_input = value,
+        Message::FallbackToggled(provider, enabled) => {
+            state.fallback_providers.retain(|p| *p != provider);
+            if enabled {
+                state.fallback_providers.push(provider);
+                state
+                    .fallback_providers
+                    .sort_by_key(|p| PROVIDERS.iter().position(|known| known == p));
+            }
+        }
+        Message::TokenChanged(value) => {
+            state.token_inputs.insert(state.provider.clone(), value);
+        }
         Message::LocationSelected(index) => {
             if index < state.locations.len() {
                 state.selected_location_index = index;
-                // Stale detection state/error from a different entry
+                // Stale detection/search state from a different entry
                 // shouldn't bleed into the newly-selected one.
-                state.is_detecting_location = false;
-                state.location_detection_error = None;
+                state.reset_location_lookups();
             }
         }
         Message::LocationNameChanged(value) => {
@@ -398,16 +626,60 @@ pub fn update(state: &mut State, message: Message) {
         Message::CityChanged(value) => {
             if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
                 entry.city = value;
+                entry.forget_coordinates();
             }
         }
         Message::StateChanged(value) => {
             if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
                 entry.state = value;
+                entry.forget_coordinates();
             }
         }
         Message::CountryChanged(value) => {
             if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
                 entry.country = value;
+                entry.forget_coordinates();
+            }
+        }
+        Message::CoordinatesModeToggled(enabled) => {
+            if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
+                // Start from the place's resolved coordinates when it has
+                // them, so switching a named place over keeps its position.
+                if enabled
+                    && entry.lat_input.is_empty()
+                    && entry.lon_input.is_empty()
+                    && let (Some(lat), Some(lon)) = (entry.lat, entry.lon)
+                {
+                    entry.lat_input = lat.to_string();
+                    entry.lon_input = lon.to_string();
+                }
+                entry.by_coordinates = enabled;
+            }
+        }
+        Message::LatitudeChanged(value) => {
+            if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
+                entry.lat_input = value;
+                entry.timezone = None;
+            }
+        }
+        Message::LongitudeChanged(value) => {
+            if let Some(entry) = state.locations.get_mut(state.selected_location_index) {
+                entry.lon_input = value;
+                entry.timezone = None;
+            }
+        }
+        Message::LocationCandidatePicked(index) => {
+            if let Some(candidate) = state.location_candidates.get(index)
+                && let Some(entry) = state.locations.get_mut(state.selected_location_index)
+            {
+                let location = candidate.to_location();
+                entry.city = location.city;
+                entry.state = location.state;
+                entry.country = location.country;
+                entry.lat = location.lat;
+                entry.lon = location.lon;
+                entry.timezone = location.timezone;
+                state.location_candidates.clear();
             }
         }
         Message::AddLocationRequested => {
@@ -416,12 +688,17 @@ pub fn update(state: &mut State, message: Message) {
                 city: String::new(),
                 state: String::new(),
                 country: String::new(),
+                by_coordinates: false,
+                lat_input: String::new(),
+                lon_input: String::new(),
+                lat: None,
+                lon: None,
+                timezone: None,
             });
             state.selected_location_index = state.locations.len() - 1;
             // Appending doesn't shift anything before it, so the tracked
             // "current" index (if any) still points at the same entry.
-            state.is_detecting_location = false;
-            state.location_detection_error = None;
+            state.reset_location_lookups();
         }
         Message::RemoveLocationRequested => {
             // At least one saved location must always exist -- the main
@@ -441,8 +718,7 @@ pub fn update(state: &mut State, message: Message) {
                     Some(current) if current > removed_index => Some(current - 1),
                     other => other,
                 };
-                state.is_detecting_location = false;
-                state.location_detection_error = None;
+                state.reset_location_lookups();
             }
         }
         Message::MoveLocationUp => {
@@ -455,8 +731,7 @@ pub fn update(state: &mut State, message: Message) {
                 state.selected_location_index = b;
                 state.current_location_index =
                     swap_tracked_index(state.current_location_index, a, b);
-                state.is_detecting_location = false;
-                state.location_detection_error = None;
+                state.reset_location_lookups();
             }
         }
         Message::MoveLocationDown => {
@@ -469,8 +744,7 @@ pub fn update(state: &mut State, message: Message) {
                 state.selected_location_index = b;
                 state.current_location_index =
                     swap_tracked_index(state.current_location_index, a, b);
-                state.is_detecting_location = false;
-                state.location_detection_error = None;
+                state.reset_location_lookups();
             }
         }
         Message::LanguageSelected(value) => state.language = value,
@@ -478,8 +752,13 @@ pub fn update(state: &mut State, message: Message) {
         Message::UnitsToggled(value) => state.use_fahrenheit = value,
         Message::LaunchAtLoginToggled(value) => state.launch_at_login = value,
         Message::RefreshIntervalSelected(value) => state.refresh_interval = value,
+        Message::ProxyChanged(value) => state.proxy_input = value,
+        Message::CaBundleChanged(value) => state.ca_bundle_input = value,
+        Message::ConnectTimeoutChanged(value) => state.connect_timeout_input = value,
+        Message::TimeoutChanged(value) => state.timeout_input = value,
         Message::OpenUrl(_)
         | Message::DetectLocationRequested
+        | Message::SearchLocationRequested
         | Message::TestConnectionRequested
         | Message::Save
         | Message::Cancel => {
@@ -506,7 +785,9 @@ fn swap_tracked_index(tracked: Option<usize>, a: usize, b: usize) -> Option<usiz
 
 /// Where to get an API key for each provider, and a matching link label --
 /// shown under the API Token field regardless of first-run status, since
-/// switching providers later needs the same pointer.
+/// switching providers later needs the same pointer. Keyless providers link
+/// to their terms of use instead, since that's the one thing worth reading
+/// before relying on a free API.
 fn api_key_hint(provider: &WeatherApiProvider) -> (&'static str, &'static str) {
     match provider {
         WeatherApiProvider::OpenWeather => (
@@ -517,6 +798,18 @@ fn api_key_hint(provider: &WeatherApiProvider) -> (&'static str, &'static str) {
             "Get a Google Weather API key",
             "https://developers.google.com/maps/documentation/weather/overview",
         ),
+        WeatherApiProvider::OpenMeteo => (
+            "No API key needed -- Open-Meteo terms of use",
+            "https://open-meteo.com/en/terms",
+        ),
+        WeatherApiProvider::NationalWeatherService => (
+            "No API key needed -- US locations only",
+            "https://www.weather.gov/documentation/services-web-api",
+        ),
+        WeatherApiProvider::MetNorway => (
+            "No API key needed -- MET Norway terms of service",
+            "https://api.met.no/doc/TermsOfService",
+        ),
     }
 }
 
@@ -546,16 +839,10 @@ pub fn view(state: &State) -> Element<'_, Message> {
             .style(style::pick_list)
             .into()
         ),
-        labeled_row(
-            "API Token:",
-            text_input("Enter your API token", &state.token_input)
-                .secure(true)
-                .on_input(Message::TokenChanged)
-                .style(style::text_input)
-                .into()
-        ),
+        labeled_row("API Token:", token_input(state)),
         api_key_hint_row(hint_label, hint_url),
         test_connection_row(state.is_testing_connection, connected),
+        labeled_row("Fallbacks:", fallback_togglers(state)),
     ]
     .spacing(12);
 
@@ -609,27 +896,18 @@ pub fn view(state: &State) -> Element<'_, Message> {
                 .into()
         ),
         labeled_row(
-            "City:",
-            text_input("Enter city name", &selected.city)
-                .on_input(Message::CityChanged)
-                .style(style::text_input)
+            "",
+            toggler(selected.by_coordinates)
+                .label("Use coordinates instead of a city")
+                .on_toggle(Message::CoordinatesModeToggled)
                 .into()
         ),
-        labeled_row(
-            "State/Province:",
-            text_input("Enter state or province", &selected.state)
-                .on_input(Message::StateChanged)
-                .style(style::text_input)
-                .into()
-        ),
-        labeled_row(
-            "Country:",
-            text_input("Enter country code (e.g., US, CA)", &selected.country)
-                .on_input(Message::CountryChanged)
-                .style(style::text_input)
-                .into()
+        place_fields(selected),
+        location_lookup_row(
+            state.is_detecting_location,
+            state.is_searching_location,
+            !selected.by_coordinates && !selected.city.trim().is_empty()
         ),
-        detect_location_row(state.is_detecting_location),
         location_actions_row(
             can_remove,
             state.selected_location_index,
@@ -643,6 +921,16 @@ pub fn view(state: &State) -> Element<'_, Message> {
             text(error.clone()).size(12).style(style::danger).into(),
         ));
     }
+    if let Some(error) = &state.location_search_error {
+        location_column = location_column.push(location_hint_row(
+            text(error.clone()).size(12).style(style::danger).into(),
+        ));
+    }
+    if !state.location_candidates.is_empty() {
+        location_column = location_column.push(location_hint_row(location_candidates_list(
+            &state.location_candidates,
+        )));
+    }
 
     // "Locations" rather than "Home": the app now supports saving several
     // places and switching between them from the main window (issue #55);
@@ -683,6 +971,45 @@ pub fn view(state: &State) -> Element<'_, Message> {
         .into(),
     );
 
+    let network_section = section(
+        "\u{21c4} Network",
+        column![
+            labeled_row(
+                "Proxy:",
+                text_input(
+                    "From the environment, or e.g. socks5://host:1080",
+                    &state.proxy_input
+                )
+                .on_input(Message::ProxyChanged)
+                .style(style::text_input)
+                .into()
+            ),
+            labeled_row(
+                "CA Bundle:",
+                text_input("None -- path to a PEM file", &state.ca_bundle_input)
+                    .on_input(Message::CaBundleChanged)
+                    .style(style::text_input)
+                    .into()
+            ),
+            labeled_row(
+                "Connect Timeout (s):",
+                text_input("10", &state.connect_timeout_input)
+                    .on_input(Message::ConnectTimeoutChanged)
+                    .style(style::text_input)
+                    .into()
+            ),
+            labeled_row(
+                "Request Timeout (s):",
+                text_input("30", &state.timeout_input)
+                    .on_input(Message::TimeoutChanged)
+                    .style(style::text_input)
+                    .into()
+            ),
+        ]
+        .spacing(12)
+        .into(),
+    );
+
     let errors = state.validation_errors();
 
     let buttons = row![
@@ -712,9 +1039,10 @@ pub fn view(state: &State) -> Element<'_, Message> {
                     .font(BOLD)
                     .style(style::accent),
                 text(
-                    "Choose a weather provider, add its API key, and set your \
-                     Home location (typed in, or detected from your IP address) \
-                     to get started."
+                    "Choose a weather provider, add its API key (Open-Meteo, \
+                     MET Norway, and the National Weather Service need \
+                     none), and set your Home location (typed in, or \
+                     detected from your IP address) to get started."
                 )
                 .size(12)
                 .style(style::muted),
@@ -726,7 +1054,8 @@ pub fn view(state: &State) -> Element<'_, Message> {
     layout = layout
         .push(provider_section)
         .push(location_section)
-        .push(appearance_section);
+        .push(appearance_section)
+        .push(network_section);
 
     if !errors.is_empty() {
         let mut error_list = column![].spacing(2);
@@ -758,6 +1087,42 @@ fn section<'a>(title: &'a str, content: Element<'a, Message>) -> Element<'a, Mes
     .into()
 }
 
+/// The API Token field -- read-only (no `on_input`) with a placeholder
+/// explaining why when the selected provider doesn't take a key, rather
+/// than hiding the row outright and shifting the rest of the form around
+/// on every provider switch.
+fn token_input(state: &State) -> Element<'_, Message> {
+    if state.provider.requires_api_token() {
+        text_input("Enter your API token", state.token_input())
+            .secure(true)
+            .on_input(Message::TokenChanged)
+            .style(style::text_input)
+            .into()
+    } else {
+        text_input(&format!("Not required for {}", state.provider), "")
+            .style(style::text_input)
+            .into()
+    }
+}
+
+/// One toggler per provider other than the selected one, tried top to
+/// bottom when the selected provider fails (see `weather_api::fallback`).
+/// A keyed fallback uses its own saved token, and is skipped at fetch time
+/// if it has none -- select it above once to enter one.
+fn fallback_togglers(state: &State) -> Element<'_, Message> {
+    let mut togglers = column![].spacing(6);
+    for provider in PROVIDERS.iter().filter(|p| **p != state.provider) {
+        let enabled = state.fallback_providers.contains(provider);
+        let provider = provider.clone();
+        togglers = togglers.push(
+            toggler(enabled)
+                .label(provider.to_string())
+                .on_toggle(move |on| Message::FallbackToggled(provider.clone(), on)),
+        );
+    }
+    togglers.into()
+}
+
 fn labeled_row<'a>(label: &'a str, field: Element<'a, Message>) -> Element<'a, Message> {
     row![text(label).width(160), field]
         .spacing(12)
@@ -790,11 +1155,73 @@ fn location_hint_row(content: Element<'_, Message>) -> Element<'_, Message> {
         .into()
 }
 
-/// The "Detect my location" button, indented to align under the Home
-/// section's input fields. A separate function (rather than inline in
-/// `view`) purely to give the surrounding `column!` macro's `Into<Element>`
-/// call an unambiguous type to infer against.
-fn detect_location_row(is_detecting: bool) -> Element<'static, Message> {
+/// City/State/Country for a place-named entry, or Latitude/Longitude for a
+/// coordinate-only one -- see `LocationEntry::by_coordinates`.
+fn place_fields(entry: &LocationEntry) -> Element<'_, Message> {
+    if entry.by_coordinates {
+        return column![
+            labeled_row(
+                "Latitude:",
+                text_input(
+                    "Decimal degrees, north positive (e.g., 44.2706)",
+                    &entry.lat_input
+                )
+                .on_input(Message::LatitudeChanged)
+                .style(style::text_input)
+                .into()
+            ),
+            labeled_row(
+                "Longitude:",
+                text_input(
+                    "Decimal degrees, east positive (e.g., -71.3033)",
+                    &entry.lon_input
+                )
+                .on_input(Message::LongitudeChanged)
+                .style(style::text_input)
+                .into()
+            ),
+        ]
+        .spacing(12)
+        .into();
+    }
+    column![
+        labeled_row(
+            "City:",
+            text_input("Enter city name", &entry.city)
+                .on_input(Message::CityChanged)
+                .style(style::text_input)
+                .into()
+        ),
+        labeled_row(
+            "State/Province:",
+            text_input("Enter state or province", &entry.state)
+                .on_input(Message::StateChanged)
+                .style(style::text_input)
+                .into()
+        ),
+        labeled_row(
+            "Country:",
+            text_input("Enter country code (e.g., US, CA)", &entry.country)
+                .on_input(Message::CountryChanged)
+                .style(style::text_input)
+                .into()
+        ),
+    ]
+    .spacing(12)
+    .into()
+}
+
+/// The "Detect my location" and "Search" buttons, indented to align under
+/// the Locations section's input fields. A separate function (rather than
+/// inline in `view`) purely to give the surrounding `column!` macro's
+/// `Into<Element>` call an unambiguous type to infer against. Search needs
+/// a city to search for, so it's disabled without one (and for a
+/// coordinate-only entry, which has nothing to look up).
+fn location_lookup_row(
+    is_detecting: bool,
+    is_searching: bool,
+    can_search: bool,
+) -> Element<'static, Message> {
     row![
         space::horizontal().width(160),
         button(text(if is_detecting {
@@ -804,11 +1231,33 @@ fn detect_location_row(is_detecting: bool) -> Element<'static, Message> {
         }))
         .on_press_maybe((!is_detecting).then_some(Message::DetectLocationRequested))
         .style(style::secondary_button),
+        button(text(if is_searching {
+            "Searching..."
+        } else {
+            "Search"
+        }))
+        .on_press_maybe((can_search && !is_searching).then_some(Message::SearchLocationRequested))
+        .style(style::secondary_button),
     ]
+    .spacing(8)
     .align_y(Alignment::Center)
     .into()
 }
 
+/// The last search's matches, one button each -- see
+/// `State::location_candidates`.
+fn location_candidates_list(candidates: &[GeocodeCandidate]) -> Element<'_, Message> {
+    let mut list = column![text("Which one did you mean?").size(12).style(style::muted)].spacing(4);
+    for (index, candidate) in candidates.iter().enumerate() {
+        list = list.push(
+            button(text(candidate.to_string()).size(12))
+                .on_press(Message::LocationCandidatePicked(index))
+                .style(style::secondary_button),
+        );
+    }
+    list.into()
+}
+
 /// Remove/reorder controls for the currently-selected saved location,
 /// indented to align under the Locations section's fields. Remove is
 /// disabled entirely (rather than erroring on press) when it's the only
@@ -273,6 +273,23 @@ pub fn warning(_theme: &Theme) -> text::Style {
     }
 }
 
+/// The main screen's "couldn't refresh" banner: a faint `warning` amber
+/// tint with a matching border, like a Minor/Moderate alert's banner.
+pub fn warning_banner(_theme: &Theme) -> container::Style {
+    container::Style {
+        background: Some(Background::Color(Color {
+            a: 0.1,
+            ..STAT_SUNRISE
+        })),
+        border: Border {
+            color: STAT_SUNRISE,
+            width: 1.0,
+            radius: 8.0.into(),
+        },
+        ..container::Style::default()
+    }
+}
+
 /// Rounded corners for every `text_input` (API Token, City, State/Province,
 /// Country) -- otherwise identical to iced's own `text_input::default`.
 /// Uses fully-qualified paths rather than importing `iced::widget::text_input`
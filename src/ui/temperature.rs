@@ -3,7 +3,7 @@
 //! The API is always fetched in Celsius/metric; the °C/°F preference only
 //! affects how values are formatted for display, so toggling it never
 //! triggers a re-fetch. Also home to a couple of small formatting helpers
-//! (compass direction, local time-of-day) shared between `main_screen` and
+//! (compass direction, local time-of-day and weekday) shared between `main_screen` and
 //! `app::update`'s cross-fade field tracking.
 
 pub fn celsius_to_display(celsius: f64, fahrenheit: bool) -> f64 {
@@ -85,3 +85,13 @@ pub fn format_local_time(unix_ts: i64, tz_offset_secs: i64) -> String {
     };
     format!("{hours12}:{minutes:02} {period}")
 }
+
+/// `format_local_time` with the weekday in front, e.g. "Thu 3:00 PM" -- for
+/// times that may not be today, like an alert's expiry.
+pub fn format_local_datetime(unix_ts: i64, tz_offset_secs: i64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+    // Day 0 of the Unix epoch, 1970-01-01, was a Thursday.
+    let days = (unix_ts + tz_offset_secs).div_euclid(86_400);
+    let weekday = WEEKDAYS[days.rem_euclid(7) as usize];
+    format!("{weekday} {}", format_local_time(unix_ts, tz_offset_secs))
+}
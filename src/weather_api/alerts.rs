@@ -1,7 +1,10 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
 
-/// Severity of a weather alert.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+/// Severity of a weather alert. Variants are declared least to most severe,
+/// so they compare that way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Default)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum AlertSeverity {
     #[default]
@@ -12,6 +15,18 @@ pub enum AlertSeverity {
     Extreme,
 }
 
+impl fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AlertSeverity::UnknownSeverity => "Unknown",
+            AlertSeverity::Minor => "Minor",
+            AlertSeverity::Moderate => "Moderate",
+            AlertSeverity::Severe => "Severe",
+            AlertSeverity::Extreme => "Extreme",
+        })
+    }
+}
+
 /// A weather alert (e.g., severe thunderstorm warning) for a specific location.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WeatherAlert {
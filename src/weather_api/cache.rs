@@ -0,0 +1,424 @@
+//! # On-Disk Response Cache
+//!
+//! `ResponseCache` keeps the last successful current conditions, forecast,
+//! and alerts for each location as JSON files under the config directory
+//! (`ConfigManager::cache_dir`), each stamped with when it was fetched. It
+//! lets the app open straight to the last data it had -- on a train, or
+//! before the first fetch of the day answers -- instead of a loading
+//! skeleton or an error.
+//!
+//! `CachingProvider` wraps any provider (normally the `FallbackProvider`
+//! chain) behind the same `WeatherProvider` trait: every successful answer
+//! is saved on the way through. Given a maximum age, it answers from the
+//! cache instead while what's saved is recent enough; built `offline`, it
+//! never makes a request at all. Hourly forecasts pass straight through
+//! uncached -- yesterday's "next 48 hours" is mostly the past.
+//!
+//! Each kind of response is its own file, so the app's concurrent fetches
+//! never read-modify-write each other's entries, and each write goes
+//! through a temporary file and a rename, so a reader (another instance, or
+//! the CLI) never sees half of one.
+
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::config::LocationConfig;
+use crate::weather_api::alerts::WeatherAlert;
+use crate::weather_api::forecast::{ForecastResponse, HourlyForecast};
+use crate::weather_api::openweather_api::{ApiError, ApiResponse};
+use crate::weather_api::weather_provider::WeatherProvider;
+
+type BoxedProvider = Box<dyn WeatherProvider + Send + Sync>;
+type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ApiError>> + Send + 'a>>;
+
+/// Which response a cache file holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Entry {
+    /// An `ApiResponse`.
+    Weather,
+    /// A `ForecastResponse`.
+    Forecast,
+    /// A `Vec<WeatherAlert>`.
+    Alerts,
+}
+
+impl Entry {
+    fn file_name(self) -> &'static str {
+        match self {
+            Entry::Weather => "weather.json",
+            Entry::Forecast => "forecast.json",
+            Entry::Alerts => "alerts.json",
+        }
+    }
+}
+
+/// A saved response, with when it was fetched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Cached<T> {
+    /// Unix timestamp of the fetch.
+    pub fetched_at: i64,
+    pub data: T,
+}
+
+impl<T> Cached<T> {
+    /// How long ago this was fetched -- zero if the clock has since gone
+    /// backwards.
+    pub fn age(&self) -> Duration {
+        let seconds = jiff::Timestamp::now().as_second() - self.fetched_at;
+        Duration::from_secs(u64::try_from(seconds).unwrap_or(0))
+    }
+}
+
+/// The last responses per location -- see the module docs.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+}
+
+impl ResponseCache {
+    /// A cache rooted at `dir`, normally `ConfigManager::cache_dir`. Nothing
+    /// is created until the first `store`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// What was last stored for `location` as `entry`, if anything readable
+    /// was.
+    pub fn load<T: DeserializeOwned>(
+        &self,
+        location: &LocationConfig,
+        entry: Entry,
+    ) -> Option<Cached<T>> {
+        let path = self.path(location, entry);
+        let json = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&json)
+            .inspect_err(|e| log::warn!("Ignoring unreadable cache file {path:?}: {e}"))
+            .ok()
+    }
+
+    /// Saves `data` for `location` as `entry`, fetched just now.
+    pub fn store<T: Serialize>(
+        &self,
+        location: &LocationConfig,
+        entry: Entry,
+        data: &T,
+    ) -> std::io::Result<()> {
+        let path = self.path(location, entry);
+        let dir = path.parent().unwrap_or(Path::new("."));
+        fs::create_dir_all(dir)?;
+        let json = serde_json::to_string(&Cached {
+            fetched_at: jiff::Timestamp::now().as_second(),
+            data,
+        })?;
+        // Per-process, so the GUI and a CLI run writing at once can't
+        // clobber each other's half-written file.
+        let temp = dir.join(format!("{}.{}.tmp", entry.file_name(), std::process::id()));
+        fs::write(&temp, json)?;
+        fs::rename(&temp, &path)
+    }
+
+    fn path(&self, location: &LocationConfig, entry: Entry) -> PathBuf {
+        self.dir
+            .join(location_key(location))
+            .join(entry.file_name())
+    }
+}
+
+/// The directory name `location`'s responses go under: its place name, or
+/// for a coordinate-only location its coordinates. Never the coordinates a
+/// place name resolved to: they're saved (or, in the CLI, looked up) only
+/// after its first fetch, and the same place has to keep mapping to the same
+/// files from then on. Lowercased, with anything that isn't a letter, digit,
+/// `.` or `-` turned into `_`.
+fn location_key(location: &LocationConfig) -> String {
+    let key = match location.coordinates() {
+        Some((lat, lon)) if location.is_coordinate_only() => format!("{lat:.4},{lon:.4}"),
+        _ => format!(
+            "{},{},{}",
+            location.city.trim(),
+            location.state.trim(),
+            location.country.trim()
+        ),
+    };
+    key.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| {
+            if c.is_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// `alerts` minus any that expired while they sat in the cache. An alert
+/// without an end time is kept.
+pub fn unexpired(mut alerts: Vec<WeatherAlert>) -> Vec<WeatherAlert> {
+    let now = jiff::Timestamp::now().as_second();
+    alerts.retain(|alert| alert.end_time <= 0 || alert.end_time > now);
+    alerts
+}
+
+/// Saves what a provider answers, and answers from what's saved -- see the
+/// module docs.
+pub struct CachingProvider {
+    /// `None` when offline.
+    inner: Option<BoxedProvider>,
+    cache: ResponseCache,
+    /// Cached answers up to this old are used instead of fetching -- or,
+    /// offline, the oldest that's used at all. `None` never skips a fetch,
+    /// and offline uses any age.
+    max_age: Option<Duration>,
+}
+
+impl CachingProvider {
+    /// Fetches through `inner` every time, saving each answer to `cache`.
+    pub fn new(inner: BoxedProvider, cache: ResponseCache) -> Self {
+        Self {
+            inner: Some(inner),
+            cache,
+            max_age: None,
+        }
+    }
+
+    /// Answers only from `cache`, failing with `ApiError::NotCached` when
+    /// there's nothing to answer with.
+    pub fn offline(cache: ResponseCache) -> Self {
+        Self {
+            inner: None,
+            cache,
+            max_age: None,
+        }
+    }
+
+    /// Sets `max_age` -- see the field's docs.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// The cached `entry` if it's recent enough, otherwise `call`'s answer
+    /// (saved on success).
+    async fn answer<'a, T>(
+        &'a self,
+        location: &'a LocationConfig,
+        entry: Entry,
+        call: impl FnOnce(&'a BoxedProvider) -> ProviderFuture<'a, T> + Send,
+    ) -> Result<T, ApiError>
+    where
+        T: Serialize + DeserializeOwned + Send,
+    {
+        let cached = || {
+            self.cache
+                .load::<T>(location, entry)
+                .filter(|cached| self.max_age.is_none_or(|max_age| cached.age() <= max_age))
+                .map(|cached| cached.data)
+        };
+        let Some(inner) = &self.inner else {
+            return cached().ok_or(ApiError::NotCached);
+        };
+        if self.max_age.is_some()
+            && let Some(data) = cached()
+        {
+            return Ok(data);
+        }
+
+        let data = call(inner).await?;
+        if let Err(e) = self.cache.store(location, entry, &data) {
+            log::warn!("Failed to cache {entry:?} response: {e}");
+        }
+        Ok(data)
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for CachingProvider {
+    async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+        self.answer(location, Entry::Weather, |provider| {
+            provider.get_weather(location)
+        })
+        .await
+    }
+
+    async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
+        self.answer(location, Entry::Forecast, |provider| {
+            provider.get_forecast(location)
+        })
+        .await
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        match &self.inner {
+            Some(inner) => inner.get_hourly(location).await,
+            None => Err(ApiError::NotCached),
+        }
+    }
+
+    async fn get_alerts(&self, location: &LocationConfig) -> Result<Vec<WeatherAlert>, ApiError> {
+        self.answer(location, Entry::Alerts, |provider| {
+            provider.get_alerts(location)
+        })
+        .await
+        .map(unexpired)
+    }
+
+    /// Offline, whatever alerts were saved are still real ones.
+    fn supports_alerts(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_none_or(|provider| provider.supports_alerts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::openweather_api::{Main, Sys, Wind};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// Answers `get_weather` with a response named `name`, counting calls.
+    struct StubProvider {
+        name: &'static str,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl WeatherProvider for StubProvider {
+        async fn get_weather(&self, _location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ApiResponse {
+                weather: vec![],
+                main: Main {
+                    temp: 20.0,
+                    feels_like: 20.0,
+                    temp_min: 20.0,
+                    temp_max: 20.0,
+                    pressure: 0,
+                    humidity: 0,
+                },
+                wind: Wind { speed: 0.0, deg: 0 },
+                visibility: 0,
+                sys: Sys {
+                    sunrise: 0,
+                    sunset: 0,
+                },
+                timezone: 0,
+                name: self.name.to_string(),
+                coord: None,
+                provider: None,
+            })
+        }
+
+        async fn get_forecast(
+            &self,
+            _location: &LocationConfig,
+        ) -> Result<ForecastResponse, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+
+        async fn get_hourly(&self, _location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+    }
+
+    fn scratch_cache() -> ResponseCache {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        let dir = std::env::temp_dir().join(format!(
+            "open-weather-wizard-cache-test-{}-{n}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        ResponseCache::new(dir)
+    }
+
+    fn stub(name: &'static str) -> (BoxedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = StubProvider {
+            name,
+            calls: Arc::clone(&calls),
+        };
+        (Box::new(provider), calls)
+    }
+
+    #[test]
+    fn test_location_key_survives_resolving_and_is_a_safe_file_name() {
+        let place =
+            LocationConfig::new("St. Louis".to_string(), "MO".to_string(), "US".to_string());
+        assert_eq!(location_key(&place), "st._louis_mo_us");
+
+        let summit = LocationConfig::from_coordinates(44.27064, -71.30331).unwrap();
+        assert_eq!(location_key(&summit), "44.2706_-71.3033");
+        let resolved = LocationConfig {
+            lat: Some(38.627),
+            lon: Some(-90.1994),
+            ..place
+        };
+        assert_eq!(location_key(&resolved), "st._louis_mo_us");
+    }
+
+    #[tokio::test]
+    async fn test_answers_are_saved_and_served_offline() {
+        let cache = scratch_cache();
+        let location = LocationConfig::default();
+
+        let offline = CachingProvider::offline(cache.clone());
+        assert!(matches!(
+            offline.get_weather(&location).await,
+            Err(ApiError::NotCached)
+        ));
+
+        let (inner, calls) = stub("Peoria");
+        let online = CachingProvider::new(inner, cache.clone());
+        online.get_weather(&location).await.unwrap();
+        // A failed forecast leaves nothing behind.
+        assert!(online.get_forecast(&location).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let weather = offline.get_weather(&location).await.unwrap();
+        assert_eq!(weather.name, "Peoria");
+        assert!(matches!(
+            offline.get_forecast(&location).await,
+            Err(ApiError::NotCached)
+        ));
+        let cached = cache
+            .load::<ApiResponse>(&location, Entry::Weather)
+            .unwrap();
+        assert!(cached.age() < Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_max_age_skips_the_fetch_only_while_fresh() {
+        let cache = scratch_cache();
+        let location = LocationConfig::default();
+        let (inner, calls) = stub("Peoria");
+        let provider =
+            CachingProvider::new(inner, cache.clone()).with_max_age(Duration::from_secs(600));
+
+        provider.get_weather(&location).await.unwrap();
+        provider.get_weather(&location).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Backdate the saved response past `max_age`.
+        let mut cached = cache
+            .load::<ApiResponse>(&location, Entry::Weather)
+            .unwrap();
+        cached.fetched_at -= 3600;
+        let path = cache.path(&location, Entry::Weather);
+        fs::write(&path, serde_json::to_string(&cached).unwrap()).unwrap();
+
+        provider.get_weather(&location).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let offline = CachingProvider::offline(cache).with_max_age(Duration::from_secs(600));
+        assert!(offline.get_weather(&location).await.is_ok());
+    }
+}
@@ -0,0 +1,166 @@
+//! # Service Endpoints
+//!
+//! Where each service the app talks to lives. Every one defaults to the
+//! public service, and each can be pointed somewhere else -- a caching
+//! proxy in front of a metered API, a self-hosted Open-Meteo, or a local
+//! stand-in while testing. A base is the URL the service's paths hang off,
+//! so `https://weather.example.org/v1` for an Open-Meteo that answers
+//! `https://weather.example.org/v1/forecast`.
+
+use serde::{Deserialize, Serialize};
+
+const OPENWEATHER: &str = "https://api.openweathermap.org";
+const GOOGLE_WEATHER: &str = "https://weather.googleapis.com/v1";
+const OPEN_METEO: &str = "https://api.open-meteo.com/v1";
+const GEOCODING: &str = "https://geocoding-api.open-meteo.com/v1";
+const NWS: &str = "https://api.weather.gov";
+const MET_NORWAY: &str = "https://api.met.no/weatherapi/locationforecast/2.0";
+const IP_GEOLOCATION: &str = "https://ipwho.is";
+const REVERSE_GEOCODING: &str = "https://nominatim.openstreetmap.org";
+
+/// Base URL overrides -- `AppConfig::endpoints`, set by hand in
+/// `config.json`. `None` (the default, and never written out) means the
+/// public service; read them through the methods of the same name, which
+/// fill that in.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Endpoints {
+    /// OpenWeatherMap, both its weather and its geocoding API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub openweather: Option<String>,
+    /// Google Maps Platform's Weather API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub google_weather: Option<String>,
+    /// Open-Meteo's forecast API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub open_meteo: Option<String>,
+    /// Open-Meteo's geocoding API, which every provider but OpenWeatherMap
+    /// looks city names up with -- see `geocoding`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geocoding: Option<String>,
+    /// The US National Weather Service's API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nws: Option<String>,
+    /// MET Norway's Locationforecast API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub met_norway: Option<String>,
+    /// ipwho.is, which Preferences' Detect falls back on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_geolocation: Option<String>,
+    /// Nominatim, which names the place Detect found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse_geocoding: Option<String>,
+}
+
+impl Endpoints {
+    pub fn openweather(&self) -> &str {
+        base(&self.openweather, OPENWEATHER)
+    }
+
+    pub fn google_weather(&self) -> &str {
+        base(&self.google_weather, GOOGLE_WEATHER)
+    }
+
+    pub fn open_meteo(&self) -> &str {
+        base(&self.open_meteo, OPEN_METEO)
+    }
+
+    pub fn geocoding(&self) -> &str {
+        base(&self.geocoding, GEOCODING)
+    }
+
+    pub fn nws(&self) -> &str {
+        base(&self.nws, NWS)
+    }
+
+    pub fn met_norway(&self) -> &str {
+        base(&self.met_norway, MET_NORWAY)
+    }
+
+    pub fn ip_geolocation(&self) -> &str {
+        base(&self.ip_geolocation, IP_GEOLOCATION)
+    }
+
+    pub fn reverse_geocoding(&self) -> &str {
+        base(&self.reverse_geocoding, REVERSE_GEOCODING)
+    }
+
+    /// Every override, by the name it's set under.
+    fn overrides(&self) -> [(&'static str, &Option<String>); 8] {
+        [
+            ("openweather", &self.openweather),
+            ("google_weather", &self.google_weather),
+            ("open_meteo", &self.open_meteo),
+            ("geocoding", &self.geocoding),
+            ("nws", &self.nws),
+            ("met_norway", &self.met_norway),
+            ("ip_geolocation", &self.ip_geolocation),
+            ("reverse_geocoding", &self.reverse_geocoding),
+        ]
+    }
+
+    /// Checks every override is an `http://` or `https://` URL, so a typo
+    /// is reported as one rather than as every request failing to connect.
+    ///
+    /// # Errors
+    /// A user-facing message naming the first override that isn't.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, url) in self.overrides() {
+            let Some(url) = url else { continue };
+            let valid = reqwest::Url::parse(url.trim())
+                .is_ok_and(|parsed| ["http", "https"].contains(&parsed.scheme()));
+            if !valid {
+                return Err(format!(
+                    "Invalid {name} endpoint \"{url}\" -- expected an http:// or https:// URL"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An override without surrounding whitespace or a trailing `/` -- paths
+/// are appended with their own -- or the public service's base if there's
+/// none.
+fn base<'a>(url: &'a Option<String>, default: &'static str) -> &'a str {
+    url.as_deref()
+        .map(|url| url.trim().trim_end_matches('/'))
+        .filter(|url| !url.is_empty())
+        .unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_replace_the_public_base() {
+        let endpoints: Endpoints =
+            serde_json::from_str(r#"{"open_meteo": "http://localhost:8080/v1/"}"#).unwrap();
+        assert_eq!(endpoints.open_meteo(), "http://localhost:8080/v1");
+        assert_eq!(endpoints.geocoding(), GEOCODING);
+        assert_eq!(
+            serde_json::to_string(&Endpoints::default()).unwrap(),
+            "{}",
+            "unset overrides shouldn't be written out"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_non_http_urls() {
+        assert!(Endpoints::default().validate().is_ok());
+        let endpoints = Endpoints {
+            nws: Some("https://nws.mirror.example".to_string()),
+            ..Endpoints::default()
+        };
+        assert!(endpoints.validate().is_ok());
+        for url in ["api.weather.gov", "ftp://nws.mirror.example"] {
+            let endpoints = Endpoints {
+                nws: Some(url.to_string()),
+                ..Endpoints::default()
+            };
+            let error = endpoints.validate().unwrap_err();
+            assert!(error.contains("nws"), "{error}");
+        }
+    }
+}
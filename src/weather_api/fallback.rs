@@ -0,0 +1,380 @@
+//! # Provider Fallback Chain
+//!
+//! `FallbackProvider` wraps the configured provider plus any fallbacks
+//! (`AppConfig::provider_chain`) behind the same `WeatherProvider` trait, so
+//! callers don't change at all: each call goes to the first provider, and
+//! only moves on to the next when the failure says nothing about the
+//! request itself -- a network error (`RequestFailed`), an unusable
+//! response (`InvalidResponse`), a quota hit (`RateLimited`), a server
+//! error (`ServerError`), or a place outside the provider's coverage
+//! (`UnsupportedLocation`). `CityNotFound` is returned as-is: another
+//! provider won't find a city that doesn't exist either, and trying would
+//! only replace the useful error with a less relevant one. So is
+//! `Unauthorized`: a bad key is for the user to fix, and quietly answering
+//! from a fallback would hide it.
+//!
+//! Alerts only fall back to providers that publish them
+//! (`WeatherProvider::supports_alerts`): one that doesn't always answers
+//! "no alerts", which would turn a failed alerts request into an
+//! all-clear.
+//!
+//! Every successful response is stamped with the provider that answered
+//! (`ApiResponse::provider`/`ForecastResponse::provider`/
+//! `HourlyForecast::provider`), so the UI and
+//! CLI can say who they're showing data from.
+
+use crate::config::{LocationConfig, WeatherApiProvider};
+use crate::weather_api::alerts::WeatherAlert;
+use crate::weather_api::forecast::{ForecastResponse, HourlyForecast};
+use crate::weather_api::openweather_api::{ApiError, ApiResponse};
+use crate::weather_api::weather_provider::WeatherProvider;
+use async_trait::async_trait;
+use std::future::Future;
+use std::pin::Pin;
+
+type BoxedProvider = Box<dyn WeatherProvider + Send + Sync>;
+type ProviderFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, ApiError>> + Send + 'a>>;
+
+/// Whether `error` should send the request on to the next provider.
+fn should_fall_back(error: &ApiError) -> bool {
+    matches!(
+        error,
+        ApiError::RequestFailed(_)
+            | ApiError::InvalidResponse
+            | ApiError::RateLimited { .. }
+            | ApiError::ServerError { .. }
+            | ApiError::UnsupportedLocation
+    )
+}
+
+/// Tries each provider in order -- see the module docs.
+pub struct FallbackProvider {
+    /// Never empty: `WeatherProviderFactory::create_chain` always puts the
+    /// primary provider first.
+    providers: Vec<(WeatherApiProvider, BoxedProvider)>,
+}
+
+impl FallbackProvider {
+    pub fn new(providers: Vec<(WeatherApiProvider, BoxedProvider)>) -> Self {
+        Self { providers }
+    }
+
+    /// Runs `call` against each provider `eligible` accepts in turn until
+    /// one succeeds or fails for a reason `should_fall_back` rejects. All
+    /// of them failing returns the last one's error.
+    async fn first_success<'a, T>(
+        &'a self,
+        eligible: impl Fn(&BoxedProvider) -> bool + Send,
+        call: impl Fn(&'a BoxedProvider) -> ProviderFuture<'a, T> + Send + 'a,
+    ) -> Result<(WeatherApiProvider, T), ApiError> {
+        let mut last_error = ApiError::InvalidResponse;
+        let providers = self
+            .providers
+            .iter()
+            .filter(|(_, provider)| eligible(provider));
+        for (index, (kind, provider)) in providers.enumerate() {
+            match call(provider).await {
+                Ok(value) => {
+                    if index > 0 {
+                        log::info!("Fell back to {kind} after {index} failed provider(s)");
+                    }
+                    return Ok((kind.clone(), value));
+                }
+                Err(e) if should_fall_back(&e) => {
+                    log::warn!("{kind} failed ({e:?}), trying the next provider");
+                    last_error = e;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last_error)
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for FallbackProvider {
+    async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+        let (kind, mut weather) = self
+            .first_success(|_| true, |provider| provider.get_weather(location))
+            .await?;
+        weather.provider = Some(kind);
+        Ok(weather)
+    }
+
+    async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
+        let (kind, mut forecast) = self
+            .first_success(|_| true, |provider| provider.get_forecast(location))
+            .await?;
+        forecast.provider = Some(kind);
+        Ok(forecast)
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        let (kind, mut hourly) = self
+            .first_success(|_| true, |provider| provider.get_hourly(location))
+            .await?;
+        hourly.provider = Some(kind);
+        Ok(hourly)
+    }
+
+    async fn get_alerts(&self, location: &LocationConfig) -> Result<Vec<WeatherAlert>, ApiError> {
+        if !self.supports_alerts() {
+            return Ok(vec![]);
+        }
+        self.first_success(
+            |provider| provider.supports_alerts(),
+            |provider| provider.get_alerts(location),
+        )
+        .await
+        .map(|(_, alerts)| alerts)
+    }
+
+    fn supports_alerts(&self) -> bool {
+        self.providers
+            .iter()
+            .any(|(_, provider)| provider.supports_alerts())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::openweather_api::{Main, Sys, Wind};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Fails `get_weather` with `error()` if set, otherwise returns a stub
+    /// response named after itself; counts calls either way.
+    struct StubProvider {
+        name: &'static str,
+        error: Option<fn() -> ApiError>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl WeatherProvider for StubProvider {
+        async fn get_weather(&self, _location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if let Some(error) = self.error {
+                return Err(error());
+            }
+            Ok(ApiResponse {
+                weather: vec![],
+                main: Main {
+                    temp: 20.0,
+                    feels_like: 20.0,
+                    temp_min: 20.0,
+                    temp_max: 20.0,
+                    pressure: 0,
+                    humidity: 0,
+                },
+                wind: Wind { speed: 0.0, deg: 0 },
+                visibility: 0,
+                sys: Sys {
+                    sunrise: 0,
+                    sunset: 0,
+                },
+                timezone: 0,
+                name: self.name.to_string(),
+                coord: None,
+                provider: None,
+            })
+        }
+
+        async fn get_forecast(
+            &self,
+            _location: &LocationConfig,
+        ) -> Result<ForecastResponse, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+
+        async fn get_hourly(&self, _location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+    }
+
+    fn stub(
+        kind: WeatherApiProvider,
+        name: &'static str,
+        error: Option<fn() -> ApiError>,
+    ) -> ((WeatherApiProvider, BoxedProvider), Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = StubProvider {
+            name,
+            error,
+            calls: Arc::clone(&calls),
+        };
+        ((kind, Box::new(provider)), calls)
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_on_invalid_response_and_stamps_provider() {
+        let (google, google_calls) = stub(
+            WeatherApiProvider::GoogleWeather,
+            "google",
+            Some(|| ApiError::InvalidResponse),
+        );
+        let (meteo, meteo_calls) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![google, meteo]);
+
+        let weather = chain.get_weather(&LocationConfig::default()).await.unwrap();
+        assert_eq!(weather.name, "meteo");
+        assert_eq!(weather.provider, Some(WeatherApiProvider::OpenMeteo));
+        assert_eq!(google_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(meteo_calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_primary_success_never_touches_fallbacks() {
+        let (owm, _) = stub(WeatherApiProvider::OpenWeather, "owm", None);
+        let (meteo, meteo_calls) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![owm, meteo]);
+
+        let weather = chain.get_weather(&LocationConfig::default()).await.unwrap();
+        assert_eq!(weather.provider, Some(WeatherApiProvider::OpenWeather));
+        assert_eq!(meteo_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_city_not_found_stops_the_chain() {
+        let (owm, _) = stub(
+            WeatherApiProvider::OpenWeather,
+            "owm",
+            Some(|| ApiError::CityNotFound),
+        );
+        let (meteo, meteo_calls) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![owm, meteo]);
+
+        let result = chain.get_weather(&LocationConfig::default()).await;
+        assert!(matches!(result, Err(ApiError::CityNotFound)));
+        assert_eq!(meteo_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_location_falls_back() {
+        let (nws, _) = stub(
+            WeatherApiProvider::NationalWeatherService,
+            "nws",
+            Some(|| ApiError::UnsupportedLocation),
+        );
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![nws, meteo]);
+
+        let weather = chain.get_weather(&LocationConfig::default()).await.unwrap();
+        assert_eq!(weather.provider, Some(WeatherApiProvider::OpenMeteo));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_falls_back_but_rejected_key_does_not() {
+        let (owm, _) = stub(
+            WeatherApiProvider::OpenWeather,
+            "owm",
+            Some(|| ApiError::RateLimited {
+                message: None,
+                retry_after: None,
+            }),
+        );
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![owm, meteo]);
+        let weather = chain.get_weather(&LocationConfig::default()).await.unwrap();
+        assert_eq!(weather.provider, Some(WeatherApiProvider::OpenMeteo));
+
+        let (google, _) = stub(
+            WeatherApiProvider::GoogleWeather,
+            "google",
+            Some(|| ApiError::Unauthorized {
+                status: 403,
+                message: None,
+            }),
+        );
+        let (meteo, meteo_calls) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![google, meteo]);
+        let result = chain.get_weather(&LocationConfig::default()).await;
+        assert!(matches!(result, Err(ApiError::Unauthorized { .. })));
+        assert_eq!(meteo_calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_all_failing_returns_last_error() {
+        let (owm, _) = stub(WeatherApiProvider::OpenWeather, "owm", None);
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![owm, meteo]);
+
+        // Both stubs fail `get_forecast` with `InvalidResponse`.
+        let result = chain.get_forecast(&LocationConfig::default()).await;
+        assert!(matches!(result, Err(ApiError::InvalidResponse)));
+    }
+
+    /// Publishes alerts, answering them with `alerts()`.
+    struct AlertsStub {
+        alerts: fn() -> Result<Vec<WeatherAlert>, ApiError>,
+    }
+
+    #[async_trait]
+    impl WeatherProvider for AlertsStub {
+        async fn get_weather(&self, _location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+
+        async fn get_forecast(
+            &self,
+            _location: &LocationConfig,
+        ) -> Result<ForecastResponse, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+
+        async fn get_hourly(&self, _location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+            Err(ApiError::InvalidResponse)
+        }
+
+        async fn get_alerts(
+            &self,
+            _location: &LocationConfig,
+        ) -> Result<Vec<WeatherAlert>, ApiError> {
+            (self.alerts)()
+        }
+
+        fn supports_alerts(&self) -> bool {
+            true
+        }
+    }
+
+    fn alerts_stub(
+        kind: WeatherApiProvider,
+        alerts: fn() -> Result<Vec<WeatherAlert>, ApiError>,
+    ) -> (WeatherApiProvider, BoxedProvider) {
+        (kind, Box::new(AlertsStub { alerts }))
+    }
+
+    #[tokio::test]
+    async fn test_failed_alerts_never_fall_back_to_a_provider_without_them() {
+        let outage = || {
+            Err(ApiError::ServerError {
+                status: 503,
+                message: None,
+            })
+        };
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![
+            alerts_stub(WeatherApiProvider::NationalWeatherService, outage),
+            meteo,
+        ]);
+        let result = chain.get_alerts(&LocationConfig::default()).await;
+        assert!(matches!(result, Err(ApiError::ServerError { .. })));
+
+        // A provider that does publish alerts can still stand in.
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![
+            alerts_stub(WeatherApiProvider::NationalWeatherService, outage),
+            meteo,
+            alerts_stub(WeatherApiProvider::GoogleWeather, || Ok(vec![])),
+        ]);
+        assert!(chain.get_alerts(&LocationConfig::default()).await.is_ok());
+
+        // And a chain with no alerts at all has nothing to fail.
+        let (meteo, _) = stub(WeatherApiProvider::OpenMeteo, "meteo", None);
+        let chain = FallbackProvider::new(vec![meteo]);
+        assert!(!chain.supports_alerts());
+        assert!(chain.get_alerts(&LocationConfig::default()).await.is_ok());
+    }
+}
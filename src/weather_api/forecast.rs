@@ -6,17 +6,27 @@
 //!
 //! The free OpenWeatherMap tier doesn't include the newer One Call daily endpoint,
 //! so this aggregates the 3-hourly entries into daily min/max/dominant-condition
-//! buckets instead.
+//! buckets instead -- by the location's own calendar date, using the
+//! response's `city.timezone` offset, so "Today" means the location's today.
+//!
+//! The same entries also back the hourly strip (`HourlyForecast`, see
+//! `hourly_from_list`) -- there they're kept as-is rather than aggregated,
+//! since "when does the rain start today" is exactly what the daily
+//! buckets average away.
 
+use jiff::Timestamp;
+use jiff::civil::{Date, DateTime};
+use jiff::tz::Offset;
 use serde::{Deserialize, Serialize};
 
+use crate::config::WeatherApiProvider;
 use crate::weather_api::openweather_api::{Main, Weather, WeatherSymbol, Wind, get_weather_symbol};
 
 /// A single 3-hourly entry from OpenWeatherMap's `data/2.5/forecast` `list` array.
 #[derive(Deserialize, Debug)]
 pub struct ForecastListItem {
-    /// Unix timestamp (UTC) of this forecast entry.
-    #[allow(dead_code)]
+    /// Unix timestamp (UTC) of this forecast entry. Shifted by
+    /// `ForecastCity::timezone` for daily bucketing.
     pub dt: i64,
     pub main: Main,
     pub weather: Vec<Weather>,
@@ -26,14 +36,15 @@ pub struct ForecastListItem {
     /// Meters; OpenWeatherMap caps this at 10000 ("10km+"), same as the
     /// current-weather endpoint.
     pub visibility: i64,
-    /// e.g. "2026-07-02 15:00:00" (UTC). Used for daily bucketing.
-    pub dt_txt: String,
 }
 
 /// The `city` object in OpenWeatherMap's forecast response.
 #[derive(Deserialize, Debug)]
 pub struct ForecastCity {
     pub name: String,
+    /// UTC offset in seconds -- same as `ApiResponse::timezone`.
+    #[serde(default)]
+    pub timezone: i64,
 }
 
 /// The raw shape of OpenWeatherMap's `data/2.5/forecast` response.
@@ -44,11 +55,11 @@ pub struct RawForecastResponse {
 }
 
 /// An app-level daily forecast summary, aggregated from several 3-hourly entries.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForecastDay {
-    /// UTC calendar date, e.g. "2026-07-02". Kept as a `String` bucket key rather
-    /// than adding a date-handling crate; this is a display label, not something
-    /// the app performs date arithmetic on.
+    /// The location's local calendar date, e.g. "2026-07-02". Kept as a
+    /// `String` rather than a `jiff::civil::Date`; this is a display label, not
+    /// something the app performs date arithmetic on.
     pub date: String,
     pub temp_min: f64,
     pub temp_max: f64,
@@ -81,41 +92,125 @@ pub struct ForecastDay {
 }
 
 /// An app-level forecast, ready for the UI to render.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForecastResponse {
     pub location_name: String,
     pub days: Vec<ForecastDay>,
+    /// Which provider actually answered -- see `ApiResponse::provider`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<WeatherApiProvider>,
+}
+
+/// One step of an hourly forecast -- an hour for most providers,
+/// OpenWeatherMap's free tier only goes down to three.
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastHour {
+    /// Unix timestamp (UTC) of the start of this step.
+    pub dt: i64,
+    pub temp: f64,
+    pub description: String,
+    pub symbol: WeatherSymbol,
+    /// Chance of precipitation during this step, 0.0-1.0.
+    pub pop: f64,
 }
 
+/// An app-level hourly forecast, ready for the UI's hourly strip.
+#[derive(Debug, Clone, Serialize)]
+pub struct HourlyForecast {
+    pub location_name: String,
+    /// UTC offset in seconds for labelling `ForecastHour::dt` in local
+    /// time -- same convention (and same one-offset-for-the-whole-response
+    /// simplification across a DST change) as `ApiResponse::timezone`.
+    pub timezone: i64,
+    /// Starts at the current hour (or the nearest step after it), at most
+    /// `MAX_FORECAST_HOURS` long.
+    pub hours: Vec<ForecastHour>,
+    /// Which provider actually answered -- see `ApiResponse::provider`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<WeatherApiProvider>,
+}
+
+/// How far ahead the hourly strip looks: today plus tomorrow, whatever the
+/// time of day.
+pub const MAX_FORECAST_HOURS: usize = 48;
+
 /// Number of daily cards to show in the forecast row.
 const MAX_FORECAST_DAYS: usize = 5;
 
+/// Local hours (inclusive) whose entry stands in for the whole day -- see
+/// `aggregate_daily`.
+const MIDDAY_HOURS: std::ops::RangeInclusive<i8> = 12..=15;
+
+/// `dt` as a wall-clock time at `utc_offset_seconds` from UTC, or `None` if
+/// either is out of range.
+fn local_datetime(dt: i64, utc_offset_seconds: i64) -> Option<DateTime> {
+    let offset = Offset::from_seconds(i32::try_from(utc_offset_seconds).ok()?).ok()?;
+    Some(offset.to_datetime(Timestamp::from_second(dt).ok()?))
+}
+
+/// OpenWeatherMap's 3-hourly entries as an `HourlyForecast`, one step per
+/// entry -- the free tier has nothing finer. Covers the same 48 hours the
+/// other providers do, i.e. 16 steps.
+pub fn hourly_from_list(raw: &RawForecastResponse) -> HourlyForecast {
+    let horizon = raw.list.first().map_or(0, |item| item.dt) + (MAX_FORECAST_HOURS as i64) * 3600;
+    HourlyForecast {
+        location_name: raw.city.name.clone(),
+        timezone: raw.city.timezone,
+        hours: raw
+            .list
+            .iter()
+            .take_while(|item| item.dt < horizon)
+            .map(|item| {
+                let weather = item.weather.first();
+                ForecastHour {
+                    dt: item.dt,
+                    temp: item.main.temp,
+                    description: weather.map(|w| w.description.clone()).unwrap_or_default(),
+                    symbol: get_weather_symbol(weather.map(|w| w.main.as_str()).unwrap_or("")),
+                    pop: item.pop,
+                }
+            })
+            .collect(),
+        provider: None,
+    }
+}
+
 /// Aggregates OpenWeatherMap's 3-hourly forecast entries into daily summaries.
 ///
-/// Buckets by the UTC calendar date portion of `dt_txt` (the API returns UTC
-/// timestamps; this accepts minor skew near local midnight rather than doing full
-/// timezone arithmetic -- a known simplification for a first cut).
+/// Buckets by the location's local calendar date: each entry's `dt` shifted
+/// by `city.timezone`. The offset is the location's *current* one, so a DST
+/// change inside the 5-day window shifts the later days' boundaries by an
+/// hour -- the same simplification as `ApiResponse::timezone`. Bucketing by
+/// the UTC date instead put half of tomorrow into "Today" for locations far
+/// east of UTC.
 ///
 /// Within each day, `temp_min`/`temp_max` are the extremes across all entries, and
-/// the "dominant" condition is taken from the entry closest to local noon
-/// (12:00-15:00), falling back to the most frequent condition for the day if no
-/// midday entry exists -- this avoids biasing the icon/description toward
+/// the "dominant" condition is taken from the first entry between 12:00 and
+/// 15:00 local time, falling back to the most frequent condition for the day
+/// if no midday entry exists -- this avoids biasing the icon/description toward
 /// whatever happened at 00:00/03:00.
 pub fn aggregate_daily(raw: RawForecastResponse) -> ForecastResponse {
     use std::collections::BTreeMap;
 
-    let mut by_date: BTreeMap<&str, Vec<&ForecastListItem>> = BTreeMap::new();
+    let mut by_date: BTreeMap<Date, Vec<(i8, &ForecastListItem)>> = BTreeMap::new();
     for item in &raw.list {
-        let date = item.dt_txt.split(' ').next().unwrap_or(&item.dt_txt);
-        by_date.entry(date).or_default().push(item);
+        let Some(local) = local_datetime(item.dt, raw.city.timezone) else {
+            log::warn!("Skipping forecast entry with out-of-range time {}", item.dt);
+            continue;
+        };
+        by_date
+            .entry(local.date())
+            .or_default()
+            .push((local.hour(), item));
     }
 
     let mut days: Vec<ForecastDay> = by_date
         .into_iter()
-        .filter_map(|(date, items)| {
-            if items.is_empty() {
+        .filter_map(|(date, entries)| {
+            if entries.is_empty() {
                 return None;
             }
+            let items: Vec<&ForecastListItem> = entries.iter().map(|(_, item)| *item).collect();
 
             let temp_min = items
                 .iter()
@@ -126,15 +221,12 @@ pub fn aggregate_daily(raw: RawForecastResponse) -> ForecastResponse {
                 .map(|i| i.main.temp)
                 .fold(f64::NEG_INFINITY, f64::max);
 
-            let midday = items.iter().find(|item| {
-                item.dt_txt
-                    .split(' ')
-                    .nth(1)
-                    .map(|time| ("12:00:00"..="15:00:00").contains(&time))
-                    .unwrap_or(false)
-            });
+            let midday = entries
+                .iter()
+                .find(|(hour, _)| MIDDAY_HOURS.contains(hour))
+                .map(|(_, item)| *item);
 
-            let representative = midday.copied().unwrap_or_else(|| {
+            let representative = midday.unwrap_or_else(|| {
                 let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
                 for item in &items {
                     if let Some(weather) = item.weather.first() {
@@ -184,5 +276,6 @@ pub fn aggregate_daily(raw: RawForecastResponse) -> ForecastResponse {
     ForecastResponse {
         location_name: raw.city.name.clone(),
         days,
+        provider: None,
     }
 }
@@ -0,0 +1,478 @@
+//! # Open-Meteo Geocoding
+//!
+//! City-name -> coordinates lookup via the free, keyless Open-Meteo
+//! Geocoding API (`https://geocoding-api.open-meteo.com`, or wherever
+//! `Endpoints::geocoding` points), shared by every
+//! provider whose weather endpoints only accept coordinates (Google
+//! Weather, Open-Meteo, NWS, MET Norway). Lived in `google_weather_api.rs`
+//! while Google was its only caller; pulled out here once a second provider
+//! needed it, rather than having `open_meteo_api.rs` reach into a sibling
+//! provider's internals.
+//!
+//! OpenWeatherMap keeps using its own (keyed) geocoding endpoint -- see
+//! `openweather_api::get_coords`.
+//!
+//! Providers call `resolve`, which skips the lookup entirely for a location
+//! that already has saved coordinates (`LocationConfig::lat`/`lon`).
+//! `resolve`'s own pick (`select_result`) is only a best guess between
+//! same-named places, so Preferences' location search and the headless CLI
+//! use `search`/`narrow` instead, to let the user choose or to refuse to
+//! guess.
+
+use crate::config::LocationConfig;
+use crate::weather_api::openweather_api::{ApiError, Coord};
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug)]
+struct GeocodeResult {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    country_code: String,
+    latitude: f64,
+    longitude: f64,
+    /// Full admin-1 (state/province) name, e.g. "Illinois" -- present when
+    /// the location has one. Used to disambiguate same-named cities in
+    /// different states/provinces (e.g. Peoria, IL vs. Peoria, AZ), since
+    /// Open-Meteo's `name` search has no state/province filter parameter.
+    #[serde(default)]
+    admin1: Option<String>,
+    /// IANA zone, e.g. "America/Chicago". Only MET Norway needs it -- its
+    /// timeseries is UTC-only, with no offset to bucket local days by.
+    #[serde(default)]
+    timezone: Option<String>,
+}
+
+/// Open-Meteo omits the `results` key entirely (rather than returning `[]`)
+/// when nothing matches, hence `#[serde(default)]`.
+#[derive(Deserialize, Debug, Default)]
+struct GeocodeResponse {
+    #[serde(default)]
+    results: Vec<GeocodeResult>,
+}
+
+/// One place a name search matched -- see `search`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeocodeCandidate {
+    pub name: String,
+    /// State/province, when the place has one -- see `GeocodeResult::admin1`.
+    pub admin1: Option<String>,
+    /// ISO 3166-1 alpha-2, e.g. "US".
+    pub country_code: String,
+    pub lat: f64,
+    pub lon: f64,
+    pub timezone: Option<String>,
+}
+
+impl GeocodeCandidate {
+    /// This place as a location with its coordinates already resolved, so
+    /// it's never geocoded (and possibly mis-picked) again.
+    pub fn to_location(&self) -> LocationConfig {
+        LocationConfig {
+            lat: Some(self.lat),
+            lon: Some(self.lon),
+            timezone: self.timezone.clone(),
+            ..LocationConfig::new(
+                self.name.clone(),
+                self.admin1.clone().unwrap_or_default(),
+                self.country_code.clone(),
+            )
+        }
+    }
+}
+
+impl std::fmt::Display for GeocodeCandidate {
+    /// e.g. "Springfield, Illinois, US (39.8017, -89.6437)".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(admin1) = &self.admin1 {
+            write!(f, ", {admin1}")?;
+        }
+        if !self.country_code.is_empty() {
+            write!(f, ", {}", self.country_code)?;
+        }
+        write!(f, " ({:.4}, {:.4})", self.lat, self.lon)
+    }
+}
+
+impl From<&GeocodeResult> for GeocodeCandidate {
+    fn from(result: &GeocodeResult) -> Self {
+        Self {
+            name: result.name.clone(),
+            admin1: result.admin1.clone(),
+            country_code: result.country_code.clone(),
+            lat: result.latitude,
+            lon: result.longitude,
+            timezone: result.timezone.clone(),
+        }
+    }
+}
+
+/// What `narrow` makes of a search's candidates.
+#[derive(Debug, PartialEq)]
+pub enum SearchOutcome<'a> {
+    NotFound,
+    Unique(&'a GeocodeCandidate),
+    /// Candidates in more than one state/province or country, none of
+    /// them obviously the one meant.
+    Ambiguous(Vec<&'a GeocodeCandidate>),
+}
+
+/// Settles `candidates` without asking the user when it safely can: keeps
+/// those whose `admin1` matches `state` (if any do), then accepts the first
+/// if every remaining one is in the same state/province and country --
+/// Open-Meteo ranks the larger of two same-named towns in one state first,
+/// and that's the one meant far more often than not. Anything wider (e.g.
+/// "Springfield" with no state) is `Ambiguous`.
+pub fn narrow<'a>(candidates: &'a [GeocodeCandidate], state: &str) -> SearchOutcome<'a> {
+    let target_state = expand_state(state);
+    let mut remaining: Vec<&GeocodeCandidate> = candidates
+        .iter()
+        .filter(|c| {
+            c.admin1
+                .as_deref()
+                .is_some_and(|admin1| admin1.eq_ignore_ascii_case(target_state))
+        })
+        .collect();
+    if target_state.is_empty() || remaining.is_empty() {
+        remaining = candidates.iter().collect();
+    }
+    let Some(first) = remaining.first().copied() else {
+        return SearchOutcome::NotFound;
+    };
+    if remaining
+        .iter()
+        .all(|c| c.admin1 == first.admin1 && c.country_code == first.country_code)
+    {
+        SearchOutcome::Unique(first)
+    } else {
+        SearchOutcome::Ambiguous(remaining)
+    }
+}
+
+/// U.S. postal abbreviation -> full state name, used only to translate a
+/// `LocationConfig.state` like `"IL"` into the `"Illinois"` Open-Meteo
+/// returns as `admin1` -- `LocationConfig.state` is otherwise passed through
+/// as-is (e.g. for non-US provinces already given in full, like "Ontario").
+const US_STATE_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("AL", "Alabama"),
+    ("AK", "Alaska"),
+    ("AZ", "Arizona"),
+    ("AR", "Arkansas"),
+    ("CA", "California"),
+    ("CO", "Colorado"),
+    ("CT", "Connecticut"),
+    ("DE", "Delaware"),
+    ("FL", "Florida"),
+    ("GA", "Georgia"),
+    ("HI", "Hawaii"),
+    ("ID", "Idaho"),
+    ("IL", "Illinois"),
+    ("IN", "Indiana"),
+    ("IA", "Iowa"),
+    ("KS", "Kansas"),
+    ("KY", "Kentucky"),
+    ("LA", "Louisiana"),
+    ("ME", "Maine"),
+    ("MD", "Maryland"),
+    ("MA", "Massachusetts"),
+    ("MI", "Michigan"),
+    ("MN", "Minnesota"),
+    ("MS", "Mississippi"),
+    ("MO", "Missouri"),
+    ("MT", "Montana"),
+    ("NE", "Nebraska"),
+    ("NV", "Nevada"),
+    ("NH", "New Hampshire"),
+    ("NJ", "New Jersey"),
+    ("NM", "New Mexico"),
+    ("NY", "New York"),
+    ("NC", "North Carolina"),
+    ("ND", "North Dakota"),
+    ("OH", "Ohio"),
+    ("OK", "Oklahoma"),
+    ("OR", "Oregon"),
+    ("PA", "Pennsylvania"),
+    ("RI", "Rhode Island"),
+    ("SC", "South Carolina"),
+    ("SD", "South Dakota"),
+    ("TN", "Tennessee"),
+    ("TX", "Texas"),
+    ("UT", "Utah"),
+    ("VT", "Vermont"),
+    ("VA", "Virginia"),
+    ("WA", "Washington"),
+    ("WV", "West Virginia"),
+    ("WI", "Wisconsin"),
+    ("WY", "Wyoming"),
+    ("DC", "District of Columbia"),
+];
+
+/// Expands a U.S. postal abbreviation to the full state name Open-Meteo
+/// reports as `admin1`; anything else is returned trimmed but otherwise
+/// unchanged.
+fn expand_state(state: &str) -> &str {
+    let state = state.trim();
+    US_STATE_ABBREVIATIONS
+        .iter()
+        .find(|(abbr, _)| abbr.eq_ignore_ascii_case(state))
+        .map(|(_, full)| *full)
+        .unwrap_or(state)
+}
+
+/// Picks the best candidate out of an Open-Meteo response: the first whose
+/// `admin1` matches `state` (when one was given), otherwise the first
+/// result overall. Split out of `geocode` so the selection rule is
+/// unit-testable without a live network call.
+fn select_result<'a>(results: &'a [GeocodeResult], state: &str) -> Option<&'a GeocodeResult> {
+    let target_state = expand_state(state);
+    if !target_state.is_empty()
+        && let Some(matched) = results.iter().find(|r| {
+            r.admin1
+                .as_deref()
+                .is_some_and(|admin1| admin1.eq_ignore_ascii_case(target_state))
+        })
+    {
+        return Some(matched);
+    }
+    results.first()
+}
+
+/// `location`'s saved coordinates and timezone when it has them, otherwise
+/// `geocode_with_timezone`'s answer -- returned as a `Coord` so
+/// `get_weather` can hand it back for the app to save.
+pub(crate) async fn resolve(
+    client: &reqwest::Client,
+    base: &str,
+    location: &LocationConfig,
+) -> Result<Coord, ApiError> {
+    if let Some((lat, lon)) = location.coordinates() {
+        return Ok(Coord {
+            lat,
+            lon,
+            timezone: location.timezone.clone(),
+        });
+    }
+    let (lat, lon, timezone) = geocode_with_timezone(client, base, location).await?;
+    Ok(Coord { lat, lon, timezone })
+}
+
+/// Resolves a `LocationConfig` to coordinates, plus the matched place's
+/// IANA timezone when Open-Meteo knows it, via the free, keyless Open-Meteo
+/// Geocoding API. Requests several candidates and, when a state/province
+/// was given, prefers the one whose `admin1` matches it -- plain
+/// `name`-only search can't tell "Peoria, IL" from "Peoria, AZ" apart, and
+/// picking the wrong one silently returns a real, plausible-looking, but
+/// entirely wrong forecast. `base` is `Endpoints::geocoding`.
+pub(crate) async fn geocode_with_timezone(
+    client: &reqwest::Client,
+    base: &str,
+    location: &LocationConfig,
+) -> Result<(f64, f64, Option<String>), ApiError> {
+    let results = fetch_results(client, base, location).await?;
+    select_result(&results, &location.state)
+        .map(|r| (r.latitude, r.longitude, r.timezone.clone()))
+        .ok_or(ApiError::CityNotFound)
+}
+
+/// Every place matching `location`'s city (and country, when given), most
+/// relevant first -- unlike `resolve`, never picks one. An empty list
+/// means nothing matched. `base` is `Endpoints::geocoding`.
+pub async fn search(
+    client: &reqwest::Client,
+    base: &str,
+    location: &LocationConfig,
+) -> Result<Vec<GeocodeCandidate>, ApiError> {
+    let results = fetch_results(client, base, location).await?;
+    Ok(results.iter().map(GeocodeCandidate::from).collect())
+}
+
+/// The raw Open-Meteo candidates for `location`'s city.
+async fn fetch_results(
+    client: &reqwest::Client,
+    base: &str,
+    location: &LocationConfig,
+) -> Result<Vec<GeocodeResult>, ApiError> {
+    let mut query = vec![
+        ("name", location.city.clone()),
+        ("count", "10".to_string()),
+        ("language", "en".to_string()),
+        ("format", "json".to_string()),
+    ];
+    if !location.country.is_empty() {
+        query.push(("countryCode", location.country.clone()));
+    }
+
+    let response = client
+        .get(format!("{base}/search"))
+        .query(&query)
+        .send()
+        .await
+        .map_err(ApiError::RequestFailed)?;
+
+    if !response.status().is_success() {
+        log::error!("Open-Meteo geocoding request failed: {}", response.status());
+        return Err(ApiError::from_response(response).await);
+    }
+
+    let parsed = response
+        .json::<GeocodeResponse>()
+        .await
+        .map_err(|_| ApiError::InvalidResponse)?;
+    Ok(parsed.results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geocode_response_with_results() {
+        let json = r#"{"results":[{"latitude":37.422,"longitude":-122.0841,
+            "timezone":"America/Los_Angeles"}]}"#;
+        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.results.len(), 1);
+        assert_eq!(parsed.results[0].latitude, 37.422);
+        assert_eq!(
+            parsed.results[0].timezone.as_deref(),
+            Some("America/Los_Angeles")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolve_uses_saved_coordinates_without_geocoding() {
+        let location = LocationConfig {
+            lat: Some(40.6936),
+            lon: Some(-89.589),
+            timezone: Some("America/Chicago".to_string()),
+            ..LocationConfig::default()
+        };
+        // Returns before ever touching the client.
+        let client = reqwest::Client::new();
+        let coord = resolve(&client, "http://unused.invalid", &location)
+            .await
+            .unwrap();
+        assert_eq!((coord.lat, coord.lon), (40.6936, -89.589));
+        assert_eq!(coord.timezone.as_deref(), Some("America/Chicago"));
+    }
+
+    #[tokio::test]
+    async fn test_search_asks_the_configured_base() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/v1", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let read = socket.read(&mut request).await.unwrap();
+            let body = r#"{"results":[{"name":"Peoria","latitude":40.69,"longitude":-89.59}]}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request[..read]).into_owned()
+        });
+
+        let location = LocationConfig::new("Peoria".to_string(), String::new(), String::new());
+        let candidates = search(&reqwest::Client::new(), &base, &location)
+            .await
+            .unwrap();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].name, "Peoria");
+        let request = server.await.unwrap();
+        assert!(
+            request.starts_with("GET /v1/search?name=Peoria"),
+            "{request}"
+        );
+    }
+
+    #[test]
+    fn test_geocode_response_missing_results_key() {
+        // Open-Meteo omits `results` entirely (rather than `[]`) when
+        // nothing matches -- this must not fail to deserialize.
+        let json = r#"{"generationtime_ms":0.5}"#;
+        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
+        assert!(parsed.results.is_empty());
+    }
+
+    /// Regression test for a real bug caught by the live smoke test: with
+    /// only `count=1` and no state disambiguation, "Peoria" resolved to
+    /// Peoria, AZ instead of Peoria, IL. Verifies the `admin1`-matching
+    /// selects the right same-named city out of several candidates.
+    #[test]
+    fn test_geocode_disambiguates_same_named_city_by_state() {
+        let json = r#"{"results":[
+            {"latitude":33.5806,"longitude":-112.2374,"admin1":"Arizona"},
+            {"latitude":40.6936,"longitude":-89.5890,"admin1":"Illinois"}
+        ]}"#;
+        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(expand_state("IL"), "Illinois");
+        let matched = select_result(&parsed.results, "IL").unwrap();
+        assert_eq!((matched.latitude, matched.longitude), (40.6936, -89.5890));
+    }
+
+    #[test]
+    fn test_select_result_falls_back_to_first_without_state_match() {
+        let json = r#"{"results":[
+            {"latitude":33.5806,"longitude":-112.2374,"admin1":"Arizona"},
+            {"latitude":40.6936,"longitude":-89.5890,"admin1":"Illinois"}
+        ]}"#;
+        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
+        let matched = select_result(&parsed.results, "").unwrap();
+        assert_eq!(matched.latitude, 33.5806);
+        let matched = select_result(&parsed.results, "Ontario").unwrap();
+        assert_eq!(matched.latitude, 33.5806);
+        assert!(select_result(&[], "IL").is_none());
+    }
+
+    fn springfields() -> Vec<GeocodeCandidate> {
+        let json = r#"{"results":[
+            {"name":"Springfield","country_code":"US","admin1":"Illinois",
+             "latitude":39.8017,"longitude":-89.6437},
+            {"name":"Springfield","country_code":"US","admin1":"Missouri",
+             "latitude":37.2153,"longitude":-93.2982},
+            {"name":"Springfield","country_code":"US","admin1":"Massachusetts",
+             "latitude":42.1015,"longitude":-72.5898}
+        ]}"#;
+        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
+        parsed.results.iter().map(GeocodeCandidate::from).collect()
+    }
+
+    #[test]
+    fn test_narrow_refuses_to_guess_across_states() {
+        let candidates = springfields();
+        let SearchOutcome::Ambiguous(listed) = narrow(&candidates, "") else {
+            panic!("expected an ambiguous result");
+        };
+        assert_eq!(listed.len(), 3);
+        assert_eq!(
+            listed[1].to_string(),
+            "Springfield, Missouri, US (37.2153, -93.2982)"
+        );
+        // An unmatched state narrows nothing.
+        assert!(matches!(
+            narrow(&candidates, "Ontario"),
+            SearchOutcome::Ambiguous(_)
+        ));
+        assert_eq!(narrow(&[], ""), SearchOutcome::NotFound);
+    }
+
+    #[test]
+    fn test_narrow_settles_on_state_match() {
+        let candidates = springfields();
+        let SearchOutcome::Unique(picked) = narrow(&candidates, "MO") else {
+            panic!("expected a unique result");
+        };
+        let location = picked.to_location();
+        assert_eq!(location.city, "Springfield");
+        assert_eq!(location.state, "Missouri");
+        assert_eq!(location.coordinates(), Some((37.2153, -93.2982)));
+        assert!(matches!(
+            narrow(&candidates[..1], ""),
+            SearchOutcome::Unique(_)
+        ));
+    }
+}
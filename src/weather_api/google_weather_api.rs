@@ -9,11 +9,12 @@
 //! endpoint does:
 //!
 //! - **Geocoding.** Google's endpoints take `location.latitude`/
-//!   `location.longitude` only -- there's no city-name lookup. Resolved here
-//!   via the free, keyless Open-Meteo Geocoding API rather than Google's own
-//!   (billable, separately-enabled) Geocoding API, so this provider stays
-//!   independent of any other provider's key and doesn't require enabling a
-//!   second Google Cloud API.
+//!   `location.longitude` only -- there's no city-name lookup. Resolved via
+//!   the free, keyless Open-Meteo Geocoding API (`geocoding.rs`, shared with
+//!   `open_meteo_api.rs`) rather than Google's own (billable,
+//!   separately-enabled) Geocoding API, so this provider stays independent
+//!   of any other provider's key and doesn't require enabling a second
+//!   Google Cloud API.
 //! - **Sunrise/sunset in `currentConditions`.** Those live in the daily
 //!   forecast's `sunEvents` instead, so `get_weather` makes a supplementary
 //!   `forecast/days:lookup?days=1` call purely to read today's sun events
@@ -27,156 +28,26 @@
 
 use crate::config::{Language, LocationConfig};
 use crate::weather_api::alerts::{AlertSeverity, WeatherAlert};
-use crate::weather_api::forecast::{ForecastDay, ForecastResponse};
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::{
+    ForecastDay, ForecastHour, ForecastResponse, HourlyForecast, MAX_FORECAST_HOURS,
+};
+use crate::weather_api::geocoding::resolve;
 use crate::weather_api::openweather_api::{
-    ApiError, ApiResponse, Main, Sys, Weather, Wind, get_weather_symbol,
+    ApiError, ApiResponse, Coord, Main, Sys, Weather, Wind, get_weather_symbol,
 };
+use crate::weather_api::retry::RetryPolicy;
 use crate::weather_api::weather_provider::WeatherProvider;
 use async_trait::async_trait;
 use serde::Deserialize;
 
-const WEATHER_API_BASE: &str = "https://weather.googleapis.com/v1";
-const GEOCODING_API_BASE: &str = "https://geocoding-api.open-meteo.com/v1/search";
 /// Matches `forecast::MAX_FORECAST_DAYS` -- no point requesting more days
 /// from Google than the UI will ever show.
 const FORECAST_DAYS: u8 = 5;
-
-// --- Open-Meteo geocoding -----------------------------------------------
-
-#[derive(Deserialize, Debug)]
-struct GeocodeResult {
-    latitude: f64,
-    longitude: f64,
-    /// Full admin-1 (state/province) name, e.g. "Illinois" -- present when
-    /// the location has one. Used to disambiguate same-named cities in
-    /// different states/provinces (e.g. Peoria, IL vs. Peoria, AZ), since
-    /// Open-Meteo's `name` search has no state/province filter parameter.
-    #[serde(default)]
-    admin1: Option<String>,
-}
-
-/// Open-Meteo omits the `results` key entirely (rather than returning `[]`)
-/// when nothing matches, hence `#[serde(default)]`.
-#[derive(Deserialize, Debug, Default)]
-struct GeocodeResponse {
-    #[serde(default)]
-    results: Vec<GeocodeResult>,
-}
-
-/// U.S. postal abbreviation -> full state name, used only to translate a
-/// `LocationConfig.state` like `"IL"` into the `"Illinois"` Open-Meteo
-/// returns as `admin1` -- `LocationConfig.state` is otherwise passed through
-/// as-is (e.g. for non-US provinces already given in full, like "Ontario").
-const US_STATE_ABBREVIATIONS: &[(&str, &str)] = &[
-    ("AL", "Alabama"),
-    ("AK", "Alaska"),
-    ("AZ", "Arizona"),
-    ("AR", "Arkansas"),
-    ("CA", "California"),
-    ("CO", "Colorado"),
-    ("CT", "Connecticut"),
-    ("DE", "Delaware"),
-    ("FL", "Florida"),
-    ("GA", "Georgia"),
-    ("HI", "Hawaii"),
-    ("ID", "Idaho"),
-    ("IL", "Illinois"),
-    ("IN", "Indiana"),
-    ("IA", "Iowa"),
-    ("KS", "Kansas"),
-    ("KY", "Kentucky"),
-    ("LA", "Louisiana"),
-    ("ME", "Maine"),
-    ("MD", "Maryland"),
-    ("MA", "Massachusetts"),
-    ("MI", "Michigan"),
-    ("MN", "Minnesota"),
-    ("MS", "Mississippi"),
-    ("MO", "Missouri"),
-    ("MT", "Montana"),
-    ("NE", "Nebraska"),
-    ("NV", "Nevada"),
-    ("NH", "New Hampshire"),
-    ("NJ", "New Jersey"),
-    ("NM", "New Mexico"),
-    ("NY", "New York"),
-    ("NC", "North Carolina"),
-    ("ND", "North Dakota"),
-    ("OH", "Ohio"),
-    ("OK", "Oklahoma"),
-    ("OR", "Oregon"),
-    ("PA", "Pennsylvania"),
-    ("RI", "Rhode Island"),
-    ("SC", "South Carolina"),
-    ("SD", "South Dakota"),
-    ("TN", "Tennessee"),
-    ("TX", "Texas"),
-    ("UT", "Utah"),
-    ("VT", "Vermont"),
-    ("VA", "Virginia"),
-    ("WA", "Washington"),
-    ("WV", "West Virginia"),
-    ("WI", "Wisconsin"),
-    ("WY", "Wyoming"),
-    ("DC", "District of Columbia"),
-];
-
-/// Resolves a `LocationConfig` to coordinates via the free, keyless
-/// Open-Meteo Geocoding API. Requests several candidates and, when a state/
-/// province was given, prefers the one whose `admin1` matches it -- plain
-/// `name`-only search can't tell "Peoria, IL" from "Peoria, AZ" apart, and
-/// picking the wrong one silently returns a real, plausible-looking, but
-/// entirely wrong forecast.
-async fn geocode(
-    client: &reqwest::Client,
-    location: &LocationConfig,
-) -> Result<(f64, f64), ApiError> {
-    let mut query = vec![
-        ("name", location.city.clone()),
-        ("count", "10".to_string()),
-        ("language", "en".to_string()),
-        ("format", "json".to_string()),
-    ];
-    if !location.country.is_empty() {
-        query.push(("countryCode", location.country.clone()));
-    }
-
-    let response = client
-        .get(GEOCODING_API_BASE)
-        .query(&query)
-        .send()
-        .await
-        .map_err(ApiError::RequestFailed)?;
-
-    let parsed = response
-        .json::<GeocodeResponse>()
-        .await
-        .map_err(|_| ApiError::InvalidResponse)?;
-
-    let target_state = location.state.trim();
-    let target_state = US_STATE_ABBREVIATIONS
-        .iter()
-        .find(|(abbr, _)| abbr.eq_ignore_ascii_case(target_state))
-        .map(|(_, full)| *full)
-        .unwrap_or(target_state);
-
-    let results = parsed.results;
-    if !target_state.is_empty()
-        && let Some(matched) = results.iter().find(|r| {
-            r.admin1
-                .as_deref()
-                .is_some_and(|admin1| admin1.eq_ignore_ascii_case(target_state))
-        })
-    {
-        return Ok((matched.latitude, matched.longitude));
-    }
-
-    results
-        .into_iter()
-        .next()
-        .map(|r| (r.latitude, r.longitude))
-        .ok_or(ApiError::CityNotFound)
-}
+/// `forecast/hours:lookup` returns at most this many hours per page,
+/// whatever `pageSize` asks for -- `get_hourly` follows `nextPageToken`
+/// for the rest.
+const HOURS_PAGE_SIZE: usize = 24;
 
 // --- Google Weather API response types ----------------------------------
 
@@ -293,6 +164,32 @@ struct ForecastDaysResponse {
     time_zone: GTimeZone,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct GInterval {
+    start_time: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ForecastHourItem {
+    interval: GInterval,
+    weather_condition: GWeatherCondition,
+    temperature: GDegrees,
+    precipitation: GPrecipitation,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ForecastHoursResponse {
+    #[serde(default)]
+    forecast_hours: Vec<ForecastHourItem>,
+    time_zone: GTimeZone,
+    /// Absent (or empty) on the last page.
+    #[serde(default)]
+    next_page_token: Option<String>,
+}
+
 // --- Google Weather Alerts types ----------------------------------------
 
 #[derive(Deserialize, Debug)]
@@ -449,102 +346,45 @@ fn forecast_days_query(
     ]
 }
 
-/// Builds the `publicAlerts:lookup` query params. See
-/// `current_conditions_query`'s docs.
-fn public_alerts_query(
+/// Builds the `forecast/hours:lookup` query params for one page -- the
+/// first when `page_token` is `None`. See `current_conditions_query`'s
+/// docs.
+fn forecast_hours_query(
     api_key: &str,
     lat: f64,
     lon: f64,
+    page_token: Option<&str>,
     language_code: &str,
 ) -> Vec<(&'static str, String)> {
-    vec![
+    let mut query = vec![
         ("key", api_key.to_string()),
         ("location.latitude", lat.to_string()),
         ("location.longitude", lon.to_string()),
+        ("unitsSystem", "METRIC".to_string()),
+        ("hours", MAX_FORECAST_HOURS.to_string()),
+        ("pageSize", HOURS_PAGE_SIZE.to_string()),
         ("languageCode", language_code.to_string()),
-    ]
-}
-
-async fn fetch_current_conditions(
-    client: &reqwest::Client,
-    api_key: &str,
-    lat: f64,
-    lon: f64,
-    language_code: &str,
-) -> Result<CurrentConditionsResponse, ApiError> {
-    let response = client
-        .get(format!("{WEATHER_API_BASE}/currentConditions:lookup"))
-        .query(&current_conditions_query(api_key, lat, lon, language_code))
-        .send()
-        .await
-        .map_err(ApiError::RequestFailed)?;
-
-    if !response.status().is_success() {
-        log::error!(
-            "Google currentConditions request failed: {}",
-            response.status()
-        );
-        return Err(ApiError::CityNotFound);
-    }
-
-    response
-        .json::<CurrentConditionsResponse>()
-        .await
-        .map_err(|e| {
-            log::error!("Failed to parse Google currentConditions response: {e}");
-            ApiError::InvalidResponse
-        })
-}
-
-async fn fetch_forecast_days(
-    client: &reqwest::Client,
-    api_key: &str,
-    lat: f64,
-    lon: f64,
-    days: u8,
-    language_code: &str,
-) -> Result<ForecastDaysResponse, ApiError> {
-    let response = client
-        .get(format!("{WEATHER_API_BASE}/forecast/days:lookup"))
-        .query(&forecast_days_query(api_key, lat, lon, days, language_code))
-        .send()
-        .await
-        .map_err(ApiError::RequestFailed)?;
-
-    if !response.status().is_success() {
-        log::error!("Google forecast/days request failed: {}", response.status());
-        return Err(ApiError::CityNotFound);
+    ];
+    if let Some(token) = page_token {
+        query.push(("pageToken", token.to_string()));
     }
-
-    response.json::<ForecastDaysResponse>().await.map_err(|e| {
-        log::error!("Failed to parse Google forecast/days response: {e}");
-        ApiError::InvalidResponse
-    })
+    query
 }
 
-async fn fetch_public_alerts(
-    client: &reqwest::Client,
+/// Builds the `publicAlerts:lookup` query params. See
+/// `current_conditions_query`'s docs.
+fn public_alerts_query(
     api_key: &str,
     lat: f64,
     lon: f64,
     language_code: &str,
-) -> Result<PublicAlertsResponse, ApiError> {
-    let response = client
-        .get(format!("{WEATHER_API_BASE}/publicAlerts:lookup"))
-        .query(&public_alerts_query(api_key, lat, lon, language_code))
-        .send()
-        .await
-        .map_err(ApiError::RequestFailed)?;
-
-    if !response.status().is_success() {
-        log::error!("Google publicAlerts request failed: {}", response.status());
-        return Err(ApiError::CityNotFound); // Keep uniform error mapping for now
-    }
-
-    response.json::<PublicAlertsResponse>().await.map_err(|e| {
-        log::error!("Failed to parse Google publicAlerts response: {e}");
-        ApiError::InvalidResponse
-    })
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("key", api_key.to_string()),
+        ("location.latitude", lat.to_string()),
+        ("location.longitude", lon.to_string()),
+        ("languageCode", language_code.to_string()),
+    ]
 }
 
 fn map_forecast_day(item: &ForecastDayItem) -> ForecastDay {
@@ -574,44 +414,216 @@ fn map_forecast_day(item: &ForecastDayItem) -> ForecastDay {
     }
 }
 
+fn map_forecast_hour(item: &ForecastHourItem, iana_zone_id: &str) -> ForecastHour {
+    let (dt, _) = resolve_epoch_and_offset(&item.interval.start_time, iana_zone_id);
+    ForecastHour {
+        dt,
+        temp: item.temperature.degrees,
+        description: item.weather_condition.description.text.clone(),
+        symbol: get_weather_symbol(google_condition_to_owm_main(
+            &item.weather_condition.condition_type,
+        )),
+        pop: item.precipitation.probability.percent as f64 / 100.0,
+    }
+}
+
 /// A real implementation of the `WeatherProvider` trait for Google Maps
 /// Platform's Weather API.
 pub struct GoogleWeatherProvider {
     api_key: String,
     language: Language,
-    /// Reused across every request this provider makes (geocoding, current
-    /// conditions, forecast) rather than building a fresh `reqwest::Client`
-    /// per call -- a `Client` holds a connection pool internally, so
-    /// constructing a new one per request throws away keep-alive/TLS-session
-    /// reuse for no benefit.
+    retry: RetryPolicy,
+    /// Shared with every other provider rather than built per request --
+    /// see `http`.
     client: reqwest::Client,
+    /// Where the Weather API and the geocoding API live.
+    endpoints: Endpoints,
 }
 
 impl GoogleWeatherProvider {
     /// Creates a new `GoogleWeatherProvider` with the given Google Cloud API
     /// key (must have the Weather API enabled on its project) and the
-    /// language to request weather descriptions in.
-    pub fn new(api_key: String, language: Language) -> Self {
+    /// language to request weather descriptions in, sending requests
+    /// through `client` (see `http`) to `endpoints` and retrying failed
+    /// ones per `retry`.
+    pub fn new(
+        api_key: String,
+        language: Language,
+        retry: RetryPolicy,
+        client: reqwest::Client,
+        endpoints: Endpoints,
+    ) -> Self {
         Self {
             api_key,
             language,
-            client: reqwest::Client::new(),
+            retry,
+            client,
+            endpoints,
+        }
+    }
+
+    async fn fetch_current_conditions(
+        &self,
+        lat: f64,
+        lon: f64,
+        language_code: &str,
+    ) -> Result<CurrentConditionsResponse, ApiError> {
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(format!(
+                        "{}/currentConditions:lookup",
+                        self.endpoints.google_weather()
+                    ))
+                    .query(&current_conditions_query(
+                        &self.api_key,
+                        lat,
+                        lon,
+                        language_code,
+                    ))
+            })
+            .await
+            .map_err(ApiError::RequestFailed)?;
+
+        if !response.status().is_success() {
+            log::error!(
+                "Google currentConditions request failed: {}",
+                response.status()
+            );
+            return Err(ApiError::from_response(response).await);
+        }
+
+        response
+            .json::<CurrentConditionsResponse>()
+            .await
+            .map_err(|e| {
+                log::error!("Failed to parse Google currentConditions response: {e}");
+                ApiError::InvalidResponse
+            })
+    }
+
+    async fn fetch_forecast_days(
+        &self,
+        lat: f64,
+        lon: f64,
+        days: u8,
+        language_code: &str,
+    ) -> Result<ForecastDaysResponse, ApiError> {
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(format!(
+                        "{}/forecast/days:lookup",
+                        self.endpoints.google_weather()
+                    ))
+                    .query(&forecast_days_query(
+                        &self.api_key,
+                        lat,
+                        lon,
+                        days,
+                        language_code,
+                    ))
+            })
+            .await
+            .map_err(ApiError::RequestFailed)?;
+
+        if !response.status().is_success() {
+            log::error!("Google forecast/days request failed: {}", response.status());
+            return Err(ApiError::from_response(response).await);
         }
+
+        response.json::<ForecastDaysResponse>().await.map_err(|e| {
+            log::error!("Failed to parse Google forecast/days response: {e}");
+            ApiError::InvalidResponse
+        })
+    }
+
+    async fn fetch_forecast_hours(
+        &self,
+        lat: f64,
+        lon: f64,
+        page_token: Option<&str>,
+        language_code: &str,
+    ) -> Result<ForecastHoursResponse, ApiError> {
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(format!(
+                        "{}/forecast/hours:lookup",
+                        self.endpoints.google_weather()
+                    ))
+                    .query(&forecast_hours_query(
+                        &self.api_key,
+                        lat,
+                        lon,
+                        page_token,
+                        language_code,
+                    ))
+            })
+            .await
+            .map_err(ApiError::RequestFailed)?;
+
+        if !response.status().is_success() {
+            log::error!(
+                "Google forecast/hours request failed: {}",
+                response.status()
+            );
+            return Err(ApiError::from_response(response).await);
+        }
+
+        response.json::<ForecastHoursResponse>().await.map_err(|e| {
+            log::error!("Failed to parse Google forecast/hours response: {e}");
+            ApiError::InvalidResponse
+        })
+    }
+
+    async fn fetch_public_alerts(
+        &self,
+        lat: f64,
+        lon: f64,
+        language_code: &str,
+    ) -> Result<PublicAlertsResponse, ApiError> {
+        let response = self
+            .retry
+            .send(|| {
+                self.client
+                    .get(format!(
+                        "{}/publicAlerts:lookup",
+                        self.endpoints.google_weather()
+                    ))
+                    .query(&public_alerts_query(&self.api_key, lat, lon, language_code))
+            })
+            .await
+            .map_err(ApiError::RequestFailed)?;
+
+        if !response.status().is_success() {
+            log::error!("Google publicAlerts request failed: {}", response.status());
+            return Err(ApiError::from_response(response).await);
+        }
+
+        response.json::<PublicAlertsResponse>().await.map_err(|e| {
+            log::error!("Failed to parse Google publicAlerts response: {e}");
+            ApiError::InvalidResponse
+        })
     }
 }
 
 #[async_trait]
 impl WeatherProvider for GoogleWeatherProvider {
     async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
-        let (lat, lon) = geocode(&self.client, location).await?;
+        let coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let (lat, lon) = (coord.lat, coord.lon);
         let language_code = self.language.google_code();
 
-        let current =
-            fetch_current_conditions(&self.client, &self.api_key, lat, lon, language_code).await?;
+        let current = self
+            .fetch_current_conditions(lat, lon, language_code)
+            .await?;
         // Sunrise/sunset and today's min/max only come from the daily
         // forecast, not currentConditions -- see the module doc.
-        let forecast =
-            fetch_forecast_days(&self.client, &self.api_key, lat, lon, 1, language_code).await?;
+        let forecast = self.fetch_forecast_days(lat, lon, 1, language_code).await?;
         let today = forecast
             .forecast_days
             .first()
@@ -643,42 +655,80 @@ impl WeatherProvider for GoogleWeatherProvider {
             visibility: km_to_meters(current.visibility.distance) as i64,
             sys: Sys { sunrise, sunset },
             timezone: sunrise_offset,
-            name: location.city.clone(),
+            name: location.display_name(),
+            coord: Some(coord),
+            provider: None,
         })
     }
 
     async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
-        let (lat, lon) = geocode(&self.client, location).await?;
-        let forecast = fetch_forecast_days(
-            &self.client,
-            &self.api_key,
-            lat,
-            lon,
-            FORECAST_DAYS,
-            self.language.google_code(),
-        )
-        .await?;
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let forecast = self
+            .fetch_forecast_days(lat, lon, FORECAST_DAYS, self.language.google_code())
+            .await?;
 
         Ok(ForecastResponse {
-            location_name: location.city.clone(),
+            location_name: location.display_name(),
             days: forecast
                 .forecast_days
                 .iter()
                 .map(map_forecast_day)
                 .collect(),
+            provider: None,
+        })
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let language_code = self.language.google_code();
+
+        let mut hours = Vec::new();
+        let mut zone_id;
+        let mut page_token: Option<String> = None;
+        loop {
+            let page = self
+                .fetch_forecast_hours(lat, lon, page_token.as_deref(), language_code)
+                .await?;
+            zone_id = page.time_zone.id;
+            hours.extend(
+                page.forecast_hours
+                    .iter()
+                    .map(|item| map_forecast_hour(item, &zone_id)),
+            );
+            match page.next_page_token {
+                Some(token) if !token.is_empty() && hours.len() < MAX_FORECAST_HOURS => {
+                    page_token = Some(token);
+                }
+                _ => break,
+            }
+        }
+        hours.truncate(MAX_FORECAST_HOURS);
+
+        let timezone = hours
+            .first()
+            .and_then(|hour| jiff::Timestamp::from_second(hour.dt).ok())
+            .and_then(|ts| ts.in_tz(&zone_id).ok())
+            .map_or(0, |zoned| zoned.offset().seconds() as i64);
+        Ok(HourlyForecast {
+            location_name: location.display_name(),
+            timezone,
+            hours,
+            provider: None,
         })
     }
 
+    fn supports_alerts(&self) -> bool {
+        true
+    }
+
     async fn get_alerts(&self, location: &LocationConfig) -> Result<Vec<WeatherAlert>, ApiError> {
-        let (lat, lon) = geocode(&self.client, location).await?;
-        let alerts_response = fetch_public_alerts(
-            &self.client,
-            &self.api_key,
-            lat,
-            lon,
-            self.language.google_code(),
-        )
-        .await?;
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let alerts_response = self
+            .fetch_public_alerts(lat, lon, self.language.google_code())
+            .await?;
 
         let alerts = alerts_response
             .public_alerts
@@ -719,6 +769,7 @@ impl WeatherProvider for GoogleWeatherProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::weather_api::openweather_api::WeatherSymbol;
 
     #[test]
     fn test_condition_mapping() {
@@ -759,53 +810,6 @@ mod tests {
         assert!(query.contains(&("languageCode", "hi".to_string())));
     }
 
-    #[test]
-    fn test_geocode_response_with_results() {
-        let json = r#"{"results":[{"latitude":37.422,"longitude":-122.0841}]}"#;
-        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
-        assert_eq!(parsed.results.len(), 1);
-        assert_eq!(parsed.results[0].latitude, 37.422);
-    }
-
-    #[test]
-    fn test_geocode_response_missing_results_key() {
-        // Open-Meteo omits `results` entirely (rather than `[]`) when
-        // nothing matches -- this must not fail to deserialize.
-        let json = r#"{"generationtime_ms":0.5}"#;
-        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
-        assert!(parsed.results.is_empty());
-    }
-
-    /// Regression test for a real bug caught by the live smoke test: with
-    /// only `count=1` and no state disambiguation, "Peoria" resolved to
-    /// Peoria, AZ instead of Peoria, IL. Verifies the `admin1`-matching
-    /// selects the right same-named city out of several candidates.
-    #[test]
-    fn test_geocode_disambiguates_same_named_city_by_state() {
-        let json = r#"{"results":[
-            {"latitude":33.5806,"longitude":-112.2374,"admin1":"Arizona"},
-            {"latitude":40.6936,"longitude":-89.5890,"admin1":"Illinois"}
-        ]}"#;
-        let parsed: GeocodeResponse = serde_json::from_str(json).unwrap();
-        let location = LocationConfig {
-            city: "Peoria".to_string(),
-            state: "IL".to_string(),
-            country: "US".to_string(),
-        };
-        let target_state = US_STATE_ABBREVIATIONS
-            .iter()
-            .find(|(abbr, _)| abbr.eq_ignore_ascii_case(&location.state))
-            .map(|(_, full)| *full)
-            .unwrap();
-        assert_eq!(target_state, "Illinois");
-        let matched = parsed
-            .results
-            .iter()
-            .find(|r| r.admin1.as_deref() == Some(target_state))
-            .unwrap();
-        assert_eq!((matched.latitude, matched.longitude), (40.6936, -89.5890));
-    }
-
     #[test]
     fn test_current_conditions_deserialize_and_map() {
         let json = r#"{
@@ -878,6 +882,50 @@ mod tests {
         assert!((day.pop - 0.3).abs() < 1e-9);
     }
 
+    #[test]
+    fn test_forecast_hours_query_pages_and_map() {
+        let query = forecast_hours_query("test-key", 1.0, 2.0, None, "en");
+        assert!(query.contains(&("hours", "48".to_string())));
+        assert!(!query.iter().any(|(key, _)| *key == "pageToken"));
+        let query = forecast_hours_query("test-key", 1.0, 2.0, Some("page-2"), "en");
+        assert!(query.contains(&("pageToken", "page-2".to_string())));
+
+        let json = r#"{
+            "forecastHours": [
+                {
+                    "interval": {
+                        "startTime": "2026-07-04T16:00:00Z",
+                        "endTime": "2026-07-04T17:00:00Z"
+                    },
+                    "displayDateTime": { "hours": 11, "utcOffset": "-18000s" },
+                    "weatherCondition": {
+                        "description": { "text": "Light rain", "languageCode": "en" },
+                        "type": "LIGHT_RAIN"
+                    },
+                    "temperature": { "degrees": 21.5, "unit": "CELSIUS" },
+                    "precipitation": {
+                        "probability": { "percent": 65, "type": "RAIN" }
+                    }
+                }
+            ],
+            "timeZone": { "id": "America/Chicago" },
+            "nextPageToken": "page-2"
+        }"#;
+        let parsed: ForecastHoursResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.next_page_token.as_deref(), Some("page-2"));
+        let hour = map_forecast_hour(&parsed.forecast_hours[0], &parsed.time_zone.id);
+        assert_eq!(
+            hour.dt,
+            "2026-07-04T16:00:00Z"
+                .parse::<jiff::Timestamp>()
+                .unwrap()
+                .as_second()
+        );
+        assert_eq!(hour.description, "Light rain");
+        assert_eq!(hour.symbol, WeatherSymbol::Rain);
+        assert!((hour.pop - 0.65).abs() < 1e-9);
+    }
+
     #[test]
     fn test_resolve_epoch_and_offset_valid() {
         let (epoch, offset) = resolve_epoch_and_offset("2026-07-04T11:00:00Z", "America/Chicago");
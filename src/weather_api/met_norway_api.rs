@@ -0,0 +1,936 @@
+//! # MET Norway Provider
+//!
+//! Real implementation of `WeatherProvider` against the Norwegian
+//! Meteorological Institute's Locationforecast 2.0 API
+//! (`https://api.met.no/weatherapi/locationforecast/2.0`) -- keyless and
+//! global, and the source behind yr.no. Free to use under its terms of
+//! service (<https://api.met.no/doc/TermsOfService>), which come with two
+//! hard requirements this module exists to honour:
+//!
+//! - **An identifying `User-Agent`.** Requests without one are answered
+//!   with `403 Forbidden` -- `http`'s shared client sends
+//!   `weather_api::USER_AGENT`.
+//! - **Respect for `Expires`/`Last-Modified`.** A forecast is only
+//!   recomputed every half hour or so, and clients are expected to not ask
+//!   again before its `Expires`, then revalidate with `If-Modified-Since`
+//!   rather than re-downloading an unchanged body. See `ResponseCache`.
+//!
+//! The response is one `timeseries` of UTC instants: hourly for the first
+//! ~2.5 days, then 6-hourly. Each entry has instantaneous readings plus
+//! `next_1_hours`/`next_6_hours`/`next_12_hours` period summaries carrying
+//! the symbol code. Current conditions are the first entry; daily cards
+//! are aggregated from the whole series the same way `forecast.rs` does for
+//! OpenWeatherMap's 3-hourly list, and the hourly strip is the hourly
+//! stretch as-is. There are no local-time fields at all,
+//! so day buckets use the location's IANA zone -- the one Open-Meteo's
+//! geocoder returns, or for saved coordinates without one, the one
+//! Open-Meteo's forecast API picks for them (UTC if neither has one). No visibility, feels-like, or sun times either: visibility
+//! stays `0`, feels-like falls back to the air temperature, and
+//! sunrise/sunset are computed locally (`sun.rs`).
+
+use crate::config::LocationConfig;
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::{
+    ForecastDay, ForecastHour, ForecastResponse, HourlyForecast, MAX_FORECAST_HOURS,
+};
+use crate::weather_api::geocoding::resolve;
+use crate::weather_api::open_meteo_api::time_zone_at;
+use crate::weather_api::openweather_api::{
+    ApiError, ApiResponse, Coord, Main, Sys, Weather, Wind, get_weather_symbol,
+};
+use crate::weather_api::sun::sunrise_sunset;
+use crate::weather_api::weather_provider::WeatherProvider;
+use async_trait::async_trait;
+use jiff::Timestamp;
+use jiff::tz::TimeZone;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, MutexGuard, PoisonError};
+
+/// Matches `forecast::MAX_FORECAST_DAYS`. Locationforecast covers about
+/// nine days; the rest are dropped after aggregation.
+const FORECAST_DAYS: usize = 5;
+
+// --- Locationforecast response types --------------------------------------
+
+#[derive(Deserialize, Debug, Default)]
+struct InstantDetails {
+    #[serde(default)]
+    air_pressure_at_sea_level: Option<f64>,
+    air_temperature: f64,
+    #[serde(default)]
+    relative_humidity: Option<f64>,
+    #[serde(default)]
+    wind_from_direction: Option<f64>,
+    #[serde(default)]
+    wind_speed: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Instant {
+    details: InstantDetails,
+}
+
+#[derive(Deserialize, Debug)]
+struct PeriodSummary {
+    symbol_code: String,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct PeriodDetails {
+    #[serde(default)]
+    air_temperature_max: Option<f64>,
+    #[serde(default)]
+    air_temperature_min: Option<f64>,
+    /// Only in the `complete` product, and not for every region or range.
+    #[serde(default)]
+    probability_of_precipitation: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+struct Period {
+    summary: PeriodSummary,
+    #[serde(default)]
+    details: PeriodDetails,
+}
+
+/// One timeseries step. The last few entries of every response carry only
+/// `instant` -- no period summaries -- hence the `Option`s.
+#[derive(Deserialize, Debug)]
+struct TimeseriesData {
+    instant: Instant,
+    #[serde(default)]
+    next_1_hours: Option<Period>,
+    #[serde(default)]
+    next_6_hours: Option<Period>,
+    #[serde(default)]
+    next_12_hours: Option<Period>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TimeseriesEntry {
+    /// UTC, e.g. `"2026-07-04T10:00:00Z"`.
+    time: String,
+    data: TimeseriesData,
+}
+
+#[derive(Deserialize, Debug)]
+struct ForecastProperties {
+    #[serde(default)]
+    timeseries: Vec<TimeseriesEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LocationforecastResponse {
+    properties: ForecastProperties,
+}
+
+impl TimeseriesData {
+    /// The shortest period's symbol -- what "right now" looks like.
+    fn current_symbol(&self) -> Option<&str> {
+        [&self.next_1_hours, &self.next_6_hours, &self.next_12_hours]
+            .into_iter()
+            .flatten()
+            .map(|p| p.summary.symbol_code.as_str())
+            .next()
+    }
+
+    /// The 6-hour period's symbol first -- what a day card should show,
+    /// rather than one hour's passing shower or clear spell.
+    fn day_symbol(&self) -> Option<&str> {
+        [&self.next_6_hours, &self.next_12_hours, &self.next_1_hours]
+            .into_iter()
+            .flatten()
+            .map(|p| p.summary.symbol_code.as_str())
+            .next()
+    }
+
+    /// Chance of precipitation, 0.0-1.0, over the shorter periods.
+    fn pop(&self) -> f64 {
+        [&self.next_1_hours, &self.next_6_hours]
+            .into_iter()
+            .flatten()
+            .filter_map(|p| p.details.probability_of_precipitation)
+            .fold(0.0, f64::max)
+            / 100.0
+    }
+}
+
+// --- Symbol code mapping --------------------------------------------------
+
+/// Strips the `_day`/`_night`/`_polartwilight` variant (and any other
+/// underscores) from a symbol code: `"rainshowers_day"` -> `"rainshowers"`.
+fn base_symbol(symbol_code: &str) -> String {
+    let base = symbol_code
+        .strip_suffix("_day")
+        .or_else(|| symbol_code.strip_suffix("_night"))
+        .or_else(|| symbol_code.strip_suffix("_polartwilight"))
+        .unwrap_or(symbol_code);
+    base.replace('_', "")
+}
+
+/// Maps a MET Norway symbol code onto the OpenWeatherMap "main" condition
+/// strings `get_weather_symbol` understands -- same approach as
+/// `open_meteo_api::wmo_code_to_owm_main`. Sleet counts as snow, as it
+/// does in OpenWeatherMap's own condition groups.
+fn symbol_to_owm_main(symbol_code: &str) -> &'static str {
+    let base = base_symbol(symbol_code);
+    if base.contains("thunder") {
+        "Thunderstorm"
+    } else if base.contains("snow") || base.contains("sleet") {
+        "Snow"
+    } else if base.contains("rain") {
+        "Rain"
+    } else if base == "fog" {
+        "Fog"
+    } else if base.contains("cloudy") {
+        "Clouds"
+    } else if base == "clearsky" || base == "fair" {
+        "Clear"
+    } else {
+        ""
+    }
+}
+
+/// English description for a symbol code, lowercased like
+/// OpenWeatherMap's `description`: `"lightrainshowersandthunder_day"` ->
+/// `"light rain showers and thunder"`. Built from the code's parts rather
+/// than a 40-row table, since every code is an intensity + precipitation +
+/// "showers" + "andthunder" combination.
+fn symbol_description(symbol_code: &str) -> String {
+    let base = base_symbol(symbol_code);
+    match base.as_str() {
+        "clearsky" => return "clear sky".to_string(),
+        "partlycloudy" => return "partly cloudy".to_string(),
+        _ => {}
+    }
+    let (intensity, rest) = if let Some(rest) = base.strip_prefix("light") {
+        // MET's own typo'd codes: "lightssleetshowersandthunder".
+        let rest = if rest.starts_with("ss") {
+            &rest[1..]
+        } else {
+            rest
+        };
+        ("light ", rest)
+    } else if let Some(rest) = base.strip_prefix("heavy") {
+        ("heavy ", rest)
+    } else {
+        ("", base.as_str())
+    };
+    let rest = rest
+        .replace("showers", " showers")
+        .replace("andthunder", " and thunder");
+    format!("{intensity}{rest}")
+}
+
+// --- Response cache -------------------------------------------------------
+
+/// A previously fetched body, kept for MET's caching rules.
+struct CachedResponse {
+    body: String,
+    /// Echoed back verbatim as `If-Modified-Since`, as MET asks.
+    last_modified: Option<String>,
+    expires: Option<Timestamp>,
+    /// The location's IANA zone, when it had to be looked up -- see
+    /// `MetNorwayProvider::fetch_forecast`. Kept across refetches of the
+    /// same URL, since the coordinates in it haven't moved.
+    timezone: Option<String>,
+}
+
+/// What `ResponseCache::lookup` says to do for a URL.
+#[derive(Debug, PartialEq)]
+enum CacheLookup {
+    /// Not yet expired -- use it without asking MET at all.
+    Fresh(String),
+    /// Expired but revalidatable: send `If-Modified-Since` with this.
+    Revalidate(String),
+    /// Nothing usable cached -- plain request.
+    Miss,
+}
+
+/// How many URLs `ResponseCache` keeps -- one per location, so this is
+/// plenty for anyone's saved locations, while a long-running `--watch` or
+/// GUI session doesn't grow without bound.
+const MAX_CACHED_RESPONSES: usize = 32;
+
+/// Responses keyed by request URL. Coordinates are rounded to four
+/// decimals before building the URL (as MET asks, for the same reason), so
+/// repeat fetches for the same location hit the same key.
+#[derive(Default)]
+struct ResponseCache {
+    entries: HashMap<String, CachedResponse>,
+}
+
+/// Parses an HTTP-date header (`"Sat, 04 Jul 2026 10:30:12 GMT"`).
+fn parse_http_date(headers: &reqwest::header::HeaderMap, name: &str) -> Option<Timestamp> {
+    let value = headers.get(name)?.to_str().ok()?;
+    jiff::fmt::rfc2822::DateTimeParser::new()
+        .parse_timestamp(value)
+        .ok()
+}
+
+impl ResponseCache {
+    fn lookup(&self, url: &str, now: Timestamp) -> CacheLookup {
+        match self.entries.get(url) {
+            Some(cached) if cached.expires.is_some_and(|expires| now < expires) => {
+                CacheLookup::Fresh(cached.body.clone())
+            }
+            Some(CachedResponse {
+                last_modified: Some(last_modified),
+                ..
+            }) => CacheLookup::Revalidate(last_modified.clone()),
+            _ => CacheLookup::Miss,
+        }
+    }
+
+    fn store(
+        &mut self,
+        url: &str,
+        body: String,
+        headers: &reqwest::header::HeaderMap,
+        now: Timestamp,
+    ) {
+        if !self.entries.contains_key(url) && self.entries.len() >= MAX_CACHED_RESPONSES {
+            self.evict(now);
+        }
+        let last_modified = headers
+            .get(reqwest::header::LAST_MODIFIED)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let timezone = self.timezone(url);
+        self.entries.insert(
+            url.to_string(),
+            CachedResponse {
+                body,
+                last_modified,
+                expires: parse_http_date(headers, "expires"),
+                timezone,
+            },
+        );
+    }
+
+    /// The zone remembered for `url` by `set_timezone`, if any.
+    fn timezone(&self, url: &str) -> Option<String> {
+        self.entries.get(url)?.timezone.clone()
+    }
+
+    /// Remembers `url`'s zone alongside its response, once there is one.
+    fn set_timezone(&mut self, url: &str, timezone: &str) {
+        if let Some(cached) = self.entries.get_mut(url) {
+            cached.timezone = Some(timezone.to_string());
+        }
+    }
+
+    /// Makes room for one more entry: drops the ones that would need a full
+    /// download anyway (expired, nothing to revalidate with), then, if
+    /// that wasn't enough, the one that expires (or expired) first.
+    fn evict(&mut self, now: Timestamp) {
+        self.entries.retain(|_, cached| {
+            cached.last_modified.is_some() || cached.expires.is_some_and(|expires| now < expires)
+        });
+        if self.entries.len() >= MAX_CACHED_RESPONSES
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.expires)
+                .map(|(url, _)| url.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+    }
+
+    /// Handles a `304 Not Modified`: the cached body is still current, so
+    /// take the new `Expires` and hand the body back. `None` if the entry
+    /// vanished in between (then the caller just refetches).
+    fn revalidated(&mut self, url: &str, headers: &reqwest::header::HeaderMap) -> Option<String> {
+        let cached = self.entries.get_mut(url)?;
+        cached.expires = parse_http_date(headers, "expires");
+        Some(cached.body.clone())
+    }
+}
+
+/// Process-wide rather than on `MetNorwayProvider`: the app builds a fresh
+/// provider for every fetch (see `app::fetch_weather_task`), so a per-
+/// instance cache would never see a second request. Also lets
+/// `get_weather` and `get_forecast` -- the same URL -- share one download
+/// per refresh.
+static RESPONSE_CACHE: LazyLock<Mutex<ResponseCache>> = LazyLock::new(Default::default);
+
+/// `RESPONSE_CACHE`, locked. A panic elsewhere while it was held can't
+/// leave it half-updated (every change is a single map operation), so a
+/// poisoned lock is used as-is rather than failing every later fetch.
+fn response_cache() -> MutexGuard<'static, ResponseCache> {
+    RESPONSE_CACHE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+// --- Mapping --------------------------------------------------------------
+
+/// Aggregates the timeseries into one `ForecastDay` per local date, like
+/// `forecast::aggregate_daily`: min/max across every instant reading and
+/// 6-hour min/max in the day (a 6-hour period is credited to the day it
+/// starts in), and conditions from the entry nearest local noon.
+fn map_forecast_days(entries: &[TimeseriesEntry], tz: &TimeZone) -> Vec<ForecastDay> {
+    let mut buckets: Vec<(jiff::civil::Date, Vec<(i8, &TimeseriesEntry)>)> = Vec::new();
+    for entry in entries {
+        let Ok(time) = entry.time.parse::<Timestamp>() else {
+            log::warn!("Skipping MET Norway entry with bad time {}", entry.time);
+            continue;
+        };
+        let local = time.to_zoned(tz.clone());
+        let date = local.date();
+        match buckets.last_mut() {
+            Some((last, items)) if *last == date => items.push((local.hour(), entry)),
+            _ => buckets.push((date, vec![(local.hour(), entry)])),
+        }
+    }
+
+    buckets
+        .into_iter()
+        .take(FORECAST_DAYS)
+        .map(|(date, items)| {
+            let temps = items.iter().flat_map(|(_, entry)| {
+                let six_hours = entry.data.next_6_hours.as_ref().map(|p| &p.details);
+                [
+                    Some(entry.data.instant.details.air_temperature),
+                    six_hours.and_then(|d| d.air_temperature_min),
+                    six_hours.and_then(|d| d.air_temperature_max),
+                ]
+                .into_iter()
+                .flatten()
+            });
+            let (temp_min, temp_max) = temps.fold((f64::INFINITY, f64::NEG_INFINITY), |acc, t| {
+                (acc.0.min(t), acc.1.max(t))
+            });
+
+            let representative = items
+                .iter()
+                .filter(|(_, entry)| entry.data.day_symbol().is_some())
+                .min_by_key(|(hour, _)| (hour - 12).abs())
+                .or_else(|| items.first())
+                .map(|(_, entry)| *entry)
+                .expect("buckets are never empty");
+            let symbol = representative.data.day_symbol().unwrap_or_default();
+            let details = &representative.data.instant.details;
+
+            ForecastDay {
+                date: date.to_string(),
+                temp_min,
+                temp_max,
+                description: if symbol.is_empty() {
+                    String::new()
+                } else {
+                    symbol_description(symbol)
+                },
+                symbol: get_weather_symbol(symbol_to_owm_main(symbol)),
+                feels_like: details.air_temperature,
+                humidity: details.relative_humidity.unwrap_or(0.0).round() as i64,
+                wind_speed: details.wind_speed.unwrap_or(0.0),
+                wind_deg: details.wind_from_direction.unwrap_or(0.0).round() as i64,
+                pressure: details.air_pressure_at_sea_level.unwrap_or(0.0).round() as i64,
+                visibility: 0,
+                pop: items
+                    .iter()
+                    .map(|(_, entry)| entry.data.pop())
+                    .fold(0.0, f64::max),
+            }
+        })
+        .collect()
+}
+
+/// The hourly stretch at the start of the timeseries -- the entries with a
+/// `next_1_hours` period -- as `ForecastHour`s. Rain chance is that one
+/// hour's alone, unlike `TimeseriesData::pop`.
+fn map_hours(entries: &[TimeseriesEntry]) -> Vec<ForecastHour> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let next_hour = entry.data.next_1_hours.as_ref()?;
+            let Ok(time) = entry.time.parse::<Timestamp>() else {
+                log::warn!("Skipping MET Norway entry with bad time {}", entry.time);
+                return None;
+            };
+            let symbol = next_hour.summary.symbol_code.as_str();
+            Some(ForecastHour {
+                dt: time.as_second(),
+                temp: entry.data.instant.details.air_temperature,
+                description: symbol_description(symbol),
+                symbol: get_weather_symbol(symbol_to_owm_main(symbol)),
+                pop: next_hour
+                    .details
+                    .probability_of_precipitation
+                    .unwrap_or(0.0)
+                    / 100.0,
+            })
+        })
+        .take(MAX_FORECAST_HOURS)
+        .collect()
+}
+
+fn map_current(
+    response: &LocationforecastResponse,
+    tz: &TimeZone,
+    lat: f64,
+    lon: f64,
+    location_name: &str,
+) -> Result<ApiResponse, ApiError> {
+    let entries = &response.properties.timeseries;
+    let now = entries.first().ok_or(ApiError::InvalidResponse)?;
+    let details = &now.data.instant.details;
+    let symbol = now.data.current_symbol().unwrap_or_default();
+    let today = map_forecast_days(entries, tz).into_iter().next();
+
+    let local = now
+        .time
+        .parse::<Timestamp>()
+        .map_err(|_| ApiError::InvalidResponse)?
+        .to_zoned(tz.clone());
+    let (sunrise, sunset) = sunrise_sunset(lat, lon, local.date()).unwrap_or((0, 0));
+
+    Ok(ApiResponse {
+        weather: vec![Weather {
+            main: symbol_to_owm_main(symbol).to_string(),
+            description: symbol_description(symbol),
+        }],
+        main: Main {
+            temp: details.air_temperature,
+            feels_like: details.air_temperature,
+            temp_min: today
+                .as_ref()
+                .map_or(details.air_temperature, |d| d.temp_min),
+            temp_max: today
+                .as_ref()
+                .map_or(details.air_temperature, |d| d.temp_max),
+            pressure: details.air_pressure_at_sea_level.unwrap_or(0.0).round() as i64,
+            humidity: details.relative_humidity.unwrap_or(0.0).round() as i64,
+        },
+        wind: Wind {
+            speed: details.wind_speed.unwrap_or(0.0),
+            deg: details.wind_from_direction.unwrap_or(0.0).round() as i64,
+        },
+        visibility: 0,
+        sys: Sys { sunrise, sunset },
+        timezone: local.offset().seconds() as i64,
+        name: location_name.to_string(),
+        coord: None,
+        provider: None,
+    })
+}
+
+/// A real implementation of the `WeatherProvider` trait for MET Norway.
+/// Needs no API key -- see the module docs.
+pub struct MetNorwayProvider {
+    /// Shared with every other provider -- see `http`.
+    client: reqwest::Client,
+    /// Where Locationforecast and the geocoding API live.
+    endpoints: Endpoints,
+}
+
+impl MetNorwayProvider {
+    /// Creates a new `MetNorwayProvider` sending its requests through `client`
+    /// (see `http`) to `endpoints`. Takes no `Language`: MET returns
+    /// symbol codes only, described locally in English.
+    pub fn new(client: reqwest::Client, endpoints: Endpoints) -> Self {
+        Self { client, endpoints }
+    }
+
+    /// Fetches `url` through `RESPONSE_CACHE`: a fresh entry is used as-is,
+    /// an expired one is revalidated with `If-Modified-Since`, and a `304`
+    /// reuses the cached body. The lock is never held across an `.await`.
+    async fn get_cached(&self, url: &str) -> Result<String, ApiError> {
+        let lookup = response_cache().lookup(url, Timestamp::now());
+        let mut request = self.client.get(url);
+        match lookup {
+            CacheLookup::Fresh(body) => return Ok(body),
+            CacheLookup::Revalidate(last_modified) => {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+            CacheLookup::Miss => {}
+        }
+
+        let response = request.send().await.map_err(ApiError::RequestFailed)?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_MODIFIED {
+            let revalidated = response_cache().revalidated(url, response.headers());
+            if let Some(body) = revalidated {
+                return Ok(body);
+            }
+            log::warn!("MET Norway answered 304 for an uncached URL: {url}");
+            return Err(ApiError::InvalidResponse);
+        }
+        if !status.is_success() {
+            log::error!("MET Norway forecast request failed: {status}");
+            return Err(ApiError::from_response(response).await);
+        }
+
+        let headers = response.headers().clone();
+        let body = response.text().await.map_err(ApiError::RequestFailed)?;
+        response_cache().store(url, body.clone(), &headers, Timestamp::now());
+        Ok(body)
+    }
+
+    async fn fetch_forecast(
+        &self,
+        location: &LocationConfig,
+    ) -> Result<(LocationforecastResponse, TimeZone, Coord), ApiError> {
+        let mut coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let (lat, lon) = (coord.lat, coord.lon);
+        let url = format!(
+            "{}/complete?lat={lat:.4}&lon={lon:.4}",
+            self.endpoints.met_norway()
+        );
+        // Saved coordinates can come without a zone (OpenWeatherMap never
+        // reports one, and a coordinate-only location has no name to
+        // geocode), and day buckets need it -- one lookup by coordinates
+        // fills it in. It's remembered next to the response, and handed
+        // back in `coord` for the app to save, so later refreshes skip it.
+        let needs_timezone = coord.timezone.is_none();
+        if needs_timezone {
+            // Bound first: a guard in the `match` would live across the
+            // `.await`.
+            let cached = response_cache().timezone(&url);
+            coord.timezone = match cached {
+                Some(timezone) => Some(timezone),
+                None => time_zone_at(&self.client, self.endpoints.open_meteo(), lat, lon)
+                    .await
+                    .inspect_err(|e| log::warn!("Timezone lookup failed, using UTC: {e}"))
+                    .ok()
+                    .flatten(),
+            };
+        }
+        let body = self.get_cached(&url).await?;
+        if needs_timezone && let Some(timezone) = &coord.timezone {
+            response_cache().set_timezone(&url, timezone);
+        }
+        let response = serde_json::from_str(&body).map_err(|e| {
+            log::error!("Failed to parse MET Norway forecast response: {e}");
+            ApiError::InvalidResponse
+        })?;
+        let tz = coord
+            .timezone
+            .as_deref()
+            .and_then(|name| TimeZone::get(name).ok())
+            .unwrap_or(TimeZone::UTC);
+        Ok((response, tz, coord))
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for MetNorwayProvider {
+    async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+        let (response, tz, coord) = self.fetch_forecast(location).await?;
+        let mut weather = map_current(
+            &response,
+            &tz,
+            coord.lat,
+            coord.lon,
+            &location.display_name(),
+        )?;
+        weather.coord = Some(coord);
+        Ok(weather)
+    }
+
+    async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
+        let (response, tz, _) = self.fetch_forecast(location).await?;
+        Ok(ForecastResponse {
+            location_name: location.display_name(),
+            days: map_forecast_days(&response.properties.timeseries, &tz),
+            provider: None,
+        })
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        let (response, tz, _) = self.fetch_forecast(location).await?;
+        let hours = map_hours(&response.properties.timeseries);
+        let timezone = hours
+            .first()
+            .and_then(|hour| Timestamp::from_second(hour.dt).ok())
+            .map_or(0, |ts| tz.to_offset(ts).seconds() as i64);
+        Ok(HourlyForecast {
+            location_name: location.display_name(),
+            timezone,
+            hours,
+            provider: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::openweather_api::WeatherSymbol;
+    use reqwest::header::{EXPIRES, HeaderMap, HeaderValue, LAST_MODIFIED};
+
+    /// A trimmed real response for Oslo (`Europe/Oslo`, CEST): hourly
+    /// entries, then 6-hourly ones, ending with an instant-only entry.
+    const FIXTURE: &str = include_str!("../../tests/fixtures/met_norway/complete.json");
+
+    fn oslo() -> TimeZone {
+        TimeZone::get("Europe/Oslo").unwrap()
+    }
+
+    #[test]
+    fn test_symbol_code_mapping() {
+        assert_eq!(symbol_to_owm_main("clearsky_day"), "Clear");
+        assert_eq!(symbol_to_owm_main("fair_polartwilight"), "Clear");
+        assert_eq!(symbol_to_owm_main("partlycloudy_night"), "Clouds");
+        assert_eq!(symbol_to_owm_main("cloudy"), "Clouds");
+        assert_eq!(symbol_to_owm_main("rainshowers_day"), "Rain");
+        assert_eq!(symbol_to_owm_main("rain_showers_day"), "Rain");
+        assert_eq!(symbol_to_owm_main("heavysleet"), "Snow");
+        assert_eq!(symbol_to_owm_main("lightsnowshowers_night"), "Snow");
+        assert_eq!(
+            symbol_to_owm_main("lightssleetshowersandthunder_day"),
+            "Thunderstorm"
+        );
+        assert_eq!(symbol_to_owm_main("fog"), "Fog");
+        assert_eq!(symbol_to_owm_main("something_new"), "");
+
+        assert_eq!(
+            get_weather_symbol(symbol_to_owm_main("rainshowers_day")),
+            WeatherSymbol::Rain
+        );
+    }
+
+    #[test]
+    fn test_symbol_description() {
+        assert_eq!(symbol_description("clearsky_day"), "clear sky");
+        assert_eq!(symbol_description("partlycloudy_night"), "partly cloudy");
+        assert_eq!(symbol_description("rainshowers_day"), "rain showers");
+        assert_eq!(
+            symbol_description("lightrainshowersandthunder_day"),
+            "light rain showers and thunder"
+        );
+        assert_eq!(
+            symbol_description("lightssleetshowersandthunder_day"),
+            "light sleet showers and thunder"
+        );
+        assert_eq!(symbol_description("heavysnow"), "heavy snow");
+    }
+
+    #[test]
+    fn test_current_conditions_from_fixture() {
+        let response: LocationforecastResponse = serde_json::from_str(FIXTURE).unwrap();
+        let weather = map_current(&response, &oslo(), 59.9133, 10.7389, "Oslo").unwrap();
+        assert_eq!(weather.name, "Oslo");
+        assert_eq!(weather.weather[0].main, "Clouds");
+        assert_eq!(weather.weather[0].description, "partly cloudy");
+        assert_eq!(weather.main.temp, 19.4);
+        assert_eq!(weather.main.temp_min, 12.1);
+        assert_eq!(weather.main.temp_max, 21.3);
+        assert_eq!(weather.main.humidity, 62);
+        assert_eq!(weather.main.pressure, 1016);
+        assert_eq!(weather.wind.deg, 212);
+        assert_eq!(weather.timezone, 2 * 3600);
+        assert!(weather.sys.sunrise > 0 && weather.sys.sunset > weather.sys.sunrise);
+    }
+
+    #[test]
+    fn test_hours_cover_only_the_hourly_stretch() {
+        let parsed: LocationforecastResponse = serde_json::from_str(FIXTURE).unwrap();
+        let hours = map_hours(&parsed.properties.timeseries);
+        assert_eq!(hours.len(), 5);
+        let rainy = &hours[4];
+        assert_eq!(
+            rainy.dt,
+            "2026-07-05T10:00:00Z"
+                .parse::<Timestamp>()
+                .unwrap()
+                .as_second()
+        );
+        assert!((rainy.pop - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forecast_buckets_by_local_date() {
+        let response: LocationforecastResponse = serde_json::from_str(FIXTURE).unwrap();
+        let days = map_forecast_days(&response.properties.timeseries, &oslo());
+        assert_eq!(days.len(), 3);
+
+        assert_eq!(days[0].date, "2026-07-04");
+        assert_eq!(days[0].symbol, WeatherSymbol::Rain);
+        assert_eq!(days[0].description, "rain showers");
+        assert!((days[0].pop - 0.35).abs() < 1e-9);
+
+        // 22:00Z is already midnight in Oslo, so that entry's 11.8 low
+        // belongs to the 5th, not the 4th.
+        assert_eq!(days[1].date, "2026-07-05");
+        assert_eq!(days[1].temp_min, 11.8);
+        assert_eq!(days[1].temp_max, 18.2);
+        assert_eq!(days[1].symbol, WeatherSymbol::Thunderstorm);
+        assert!((days[1].pop - 0.8).abs() < 1e-9);
+
+        // Instant-only trailing entry: no symbol, but still a day.
+        assert_eq!(days[2].symbol, WeatherSymbol::Default);
+        assert_eq!(days[2].description, "");
+    }
+
+    #[test]
+    fn test_response_cache_fresh_then_revalidate() {
+        let url = "https://api.met.no/test";
+        let now: Timestamp = "2026-07-04T10:00:00Z".parse().unwrap();
+        let mut cache = ResponseCache::default();
+        assert_eq!(cache.lookup(url, now), CacheLookup::Miss);
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Sat, 04 Jul 2026 09:41:22 GMT"),
+        );
+        headers.insert(
+            EXPIRES,
+            HeaderValue::from_static("Sat, 04 Jul 2026 10:30:00 GMT"),
+        );
+        cache.store(url, "body".to_string(), &headers, now);
+        assert_eq!(
+            cache.lookup(url, now),
+            CacheLookup::Fresh("body".to_string())
+        );
+
+        let later: Timestamp = "2026-07-04T10:31:00Z".parse().unwrap();
+        assert_eq!(
+            cache.lookup(url, later),
+            CacheLookup::Revalidate("Sat, 04 Jul 2026 09:41:22 GMT".to_string())
+        );
+
+        // A 304 pushes `Expires` forward and keeps the old body.
+        let mut not_modified = HeaderMap::new();
+        not_modified.insert(
+            EXPIRES,
+            HeaderValue::from_static("Sat, 04 Jul 2026 11:00:00 GMT"),
+        );
+        assert_eq!(
+            cache.revalidated(url, &not_modified),
+            Some("body".to_string())
+        );
+        assert_eq!(
+            cache.lookup(url, later),
+            CacheLookup::Fresh("body".to_string())
+        );
+    }
+
+    #[test]
+    fn test_response_cache_without_validators_is_a_miss_once_expired() {
+        let url = "https://api.met.no/test";
+        let mut cache = ResponseCache::default();
+        cache.store(url, "body".to_string(), &HeaderMap::new(), Timestamp::now());
+        assert_eq!(cache.lookup(url, Timestamp::now()), CacheLookup::Miss);
+        assert_eq!(
+            cache.revalidated("https://api.met.no/other", &HeaderMap::new()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_response_cache_stays_bounded() {
+        let now: Timestamp = "2026-07-04T10:00:00Z".parse().unwrap();
+        let expires = |time: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(EXPIRES, HeaderValue::from_static(time));
+            headers
+        };
+        let mut cache = ResponseCache::default();
+        cache.store(
+            "https://api.met.no/soonest",
+            "body".to_string(),
+            &expires("Sat, 04 Jul 2026 10:05:00 GMT"),
+            now,
+        );
+        for index in 1..MAX_CACHED_RESPONSES {
+            cache.store(
+                &format!("https://api.met.no/{index}"),
+                "body".to_string(),
+                &expires("Sat, 04 Jul 2026 10:30:00 GMT"),
+                now,
+            );
+        }
+        assert_eq!(cache.entries.len(), MAX_CACHED_RESPONSES);
+
+        cache.store(
+            "https://api.met.no/new",
+            "body".to_string(),
+            &expires("Sat, 04 Jul 2026 10:30:00 GMT"),
+            now,
+        );
+        assert_eq!(cache.entries.len(), MAX_CACHED_RESPONSES);
+        assert_eq!(
+            cache.lookup("https://api.met.no/soonest", now),
+            CacheLookup::Miss
+        );
+        assert!(matches!(
+            cache.lookup("https://api.met.no/new", now),
+            CacheLookup::Fresh(_)
+        ));
+    }
+
+    #[test]
+    fn test_response_cache_keeps_the_timezone_across_refetches() {
+        let url = "https://api.met.no/test";
+        let now = Timestamp::now();
+        let mut cache = ResponseCache::default();
+        cache.set_timezone(url, "Europe/Oslo");
+        assert_eq!(cache.timezone(url), None, "nothing to keep it next to yet");
+
+        cache.store(url, "body".to_string(), &HeaderMap::new(), now);
+        cache.set_timezone(url, "Europe/Oslo");
+        cache.store(url, "newer body".to_string(), &HeaderMap::new(), now);
+        assert_eq!(cache.timezone(url).as_deref(), Some("Europe/Oslo"));
+    }
+
+    #[tokio::test]
+    async fn test_coordinate_only_location_gets_its_time_zone() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut paths = Vec::new();
+            for _ in 0..2 {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 1024];
+                let read = socket.read(&mut request).await.unwrap();
+                let request = String::from_utf8_lossy(&request[..read]).into_owned();
+                let body = if request.starts_with("GET /open-meteo/forecast?") {
+                    r#"{"timezone": "Europe/Oslo", "utc_offset_seconds": 7200}"#
+                } else {
+                    FIXTURE
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                paths.push(request.lines().next().unwrap_or_default().to_string());
+            }
+            paths
+        });
+
+        let endpoints = Endpoints {
+            open_meteo: Some(format!("{base}/open-meteo")),
+            met_norway: Some(format!("{base}/met")),
+            ..Endpoints::default()
+        };
+        let provider = MetNorwayProvider::new(reqwest::Client::new(), endpoints);
+        let summit = LocationConfig {
+            city: String::new(),
+            lat: Some(59.9133),
+            lon: Some(10.7389),
+            ..LocationConfig::default()
+        };
+        assert!(summit.is_coordinate_only());
+
+        let weather = provider.get_weather(&summit).await.unwrap();
+        assert_eq!(weather.timezone, 2 * 3600);
+        assert_eq!(weather.main.temp_max, 21.3);
+        assert_eq!(
+            weather.coord.and_then(|coord| coord.timezone).as_deref(),
+            Some("Europe/Oslo"),
+            "handed back for the app to save"
+        );
+        let paths = server.await.unwrap();
+        assert!(paths[0].contains("latitude=59.9133"), "{paths:?}");
+        assert!(paths[1].starts_with("GET /met/complete?"), "{paths:?}");
+    }
+}
@@ -7,10 +7,40 @@
 //! ## Sub-modules
 //! - `weather_provider`: Defines the core `WeatherProvider` trait and a factory for creating provider instances.
 //! - `openweather_api`: Contains the implementation for the real OpenWeatherMap API.
-//! - `google_weather_api`: Contains a mock implementation for a "Google Weather" API, used for testing and demonstration.
+//! - `google_weather_api`: Contains the implementation for Google Maps Platform's Weather API.
+//! - `open_meteo_api`: Contains the implementation for Open-Meteo's keyless forecast API.
+//! - `nws_api`: Contains the implementation for the US National Weather Service's keyless API.
+//! - `met_norway_api`: Contains the implementation for MET Norway's keyless Locationforecast API.
+//! - `geocoding`: Open-Meteo city-name geocoding, shared by the coordinate-only providers above.
+//! - `sun`: Local sunrise/sunset calculation for providers that don't report them.
+//! - `fallback`: `FallbackProvider`, which tries a chain of providers in order.
+//! - `cache`: `ResponseCache`, the last responses per location on disk, and
+//!   `CachingProvider`, which saves to and answers from it.
+//! - `endpoints`: `Endpoints`, the base URL of every service, each overridable.
+//! - `http`: `HttpSettings`, which builds the `reqwest::Client` every request goes through.
+//! - `retry`: `RetryPolicy`, which resends requests that timed out or hit a `429`/`5xx`.
 //! - `forecast`: Data model and aggregation logic for multi-day forecasts.
 pub mod alerts;
+pub mod cache;
+pub mod endpoints;
+pub mod fallback;
 pub mod forecast;
+pub mod geocoding;
 pub mod google_weather_api;
+pub mod http;
+pub mod met_norway_api;
+pub mod nws_api;
+pub mod open_meteo_api;
 pub mod openweather_api;
+pub mod retry;
+pub mod sun;
 pub mod weather_provider;
+
+/// Sent with every request (see `http`). Services whose usage policies
+/// require a descriptive User-Agent (Nominatim, api.weather.gov, api.met.no)
+/// rate-limit or block an unidentified or generic one outright.
+pub(crate) const USER_AGENT: &str = concat!(
+    "open-weather-wizard/",
+    env!("CARGO_PKG_VERSION"),
+    " (github.com/arunkumar-mourougappane/open-weather-wizard)"
+);
@@ -0,0 +1,805 @@
+//! # National Weather Service Provider
+//!
+//! Real implementation of `WeatherProvider` against the US National Weather
+//! Service's public API (`https://api.weather.gov`) -- keyless, and the one
+//! provider besides Google that publishes real weather alerts (CAP
+//! warnings/watches/advisories via `/alerts/active`). US locations only:
+//! the `/points` lookup 404s for anywhere NWS doesn't forecast.
+//!
+//! Forecasts are a two-step lookup:
+//!
+//! 1. `/points/{lat},{lon}` resolves coordinates (from `geocoding.rs`, same
+//!    as Google/Open-Meteo) to the issuing office's forecast gridpoint, and
+//!    returns the absolute URLs of that gridpoint's `forecast` (12-hour
+//!    day/night periods) and `forecastHourly` endpoints plus its IANA zone.
+//! 2. Those URLs are fetched with `units=si`.
+//!
+//! Current conditions are the first hourly period (the hourly strip is the
+//! next 48 of them), with today's high/low
+//! taken from the next 24 hourly periods. NWS forecasts carry no pressure,
+//! visibility, feels-like temperature, or sun times: pressure/visibility
+//! stay `0` (the same gap Google's per-day forecast has), feels-like falls
+//! back to the air temperature, and sunrise/sunset are computed locally
+//! (`sun.rs`) rather than shown as midnight.
+//!
+//! api.weather.gov requires an identifying `User-Agent` on every request --
+//! `http`'s shared client sends `weather_api::USER_AGENT`.
+
+use crate::config::LocationConfig;
+use crate::weather_api::alerts::{AlertSeverity, WeatherAlert};
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::{
+    ForecastDay, ForecastHour, ForecastResponse, HourlyForecast, MAX_FORECAST_HOURS,
+};
+use crate::weather_api::geocoding::resolve;
+use crate::weather_api::openweather_api::{
+    ApiError, ApiResponse, Coord, Main, Sys, Weather, Wind, get_weather_symbol,
+};
+use crate::weather_api::sun::sunrise_sunset;
+use crate::weather_api::weather_provider::WeatherProvider;
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+/// Matches `forecast::MAX_FORECAST_DAYS`. NWS always returns seven days
+/// (14 periods); the extra days are dropped while folding.
+const FORECAST_DAYS: usize = 5;
+
+// --- api.weather.gov response types -------------------------------------
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct PointProperties {
+    forecast: String,
+    forecast_hourly: String,
+    time_zone: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct PointsResponse {
+    properties: PointProperties,
+}
+
+/// NWS's `{ "unitCode": ..., "value": ... }` wrapper. `value` is `null`
+/// whenever the forecaster left it unset (most often precipitation
+/// probability on dry periods).
+#[derive(Deserialize, Debug, Default)]
+struct QuantitativeValue {
+    #[serde(default)]
+    value: Option<f64>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ForecastPeriod {
+    /// Local time with a UTC offset, e.g. `"2026-07-04T12:00:00-05:00"`.
+    start_time: String,
+    is_daytime: bool,
+    temperature: f64,
+    #[serde(default)]
+    temperature_unit: String,
+    #[serde(default)]
+    probability_of_precipitation: QuantitativeValue,
+    #[serde(default)]
+    relative_humidity: QuantitativeValue,
+    /// Free text: `"15 km/h"`, or a range like `"10 to 15 km/h"`.
+    #[serde(default)]
+    wind_speed: String,
+    /// A 16-point compass direction, e.g. `"SW"`.
+    #[serde(default)]
+    wind_direction: String,
+    #[serde(default)]
+    short_forecast: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ForecastProperties {
+    #[serde(default)]
+    periods: Vec<ForecastPeriod>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GridpointForecastResponse {
+    properties: ForecastProperties,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct AlertProperties {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    area_desc: String,
+    #[serde(default)]
+    effective: Option<String>,
+    #[serde(default)]
+    onset: Option<String>,
+    #[serde(default)]
+    expires: Option<String>,
+    #[serde(default)]
+    ends: Option<String>,
+    #[serde(default)]
+    severity: String,
+    #[serde(default)]
+    certainty: String,
+    #[serde(default)]
+    urgency: String,
+    #[serde(default)]
+    event: String,
+    #[serde(default)]
+    headline: Option<String>,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    instruction: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct AlertFeature {
+    properties: AlertProperties,
+}
+
+#[derive(Deserialize, Debug)]
+struct AlertCollection {
+    #[serde(default)]
+    features: Vec<AlertFeature>,
+}
+
+// --- Mapping helpers ------------------------------------------------------
+
+/// Maps NWS's free-text `shortForecast` ("Chance Showers And
+/// Thunderstorms", "Mostly Sunny", ...) onto the OpenWeatherMap "main"
+/// condition strings `get_weather_symbol` understands. Checked in order of
+/// severity, so a mixed forecast shows its most significant condition.
+fn short_forecast_to_owm_main(short_forecast: &str) -> &'static str {
+    let text = short_forecast.to_ascii_lowercase();
+    let has = |needle: &str| text.contains(needle);
+    if has("tornado") {
+        "Tornado"
+    } else if has("thunder") {
+        "Thunderstorm"
+    } else if has("snow") || has("flurr") || has("sleet") || has("blizzard") || has("ice") {
+        "Snow"
+    } else if has("drizzle") {
+        "Drizzle"
+    } else if has("rain") || has("shower") {
+        "Rain"
+    } else if has("fog") {
+        "Fog"
+    } else if has("smoke") {
+        "Smoke"
+    } else if has("haze") {
+        "Haze"
+    } else if has("dust") {
+        "Dust"
+    } else if has("partly sunny") || has("cloud") || has("overcast") {
+        "Clouds"
+    } else if has("sunny") || has("clear") || has("fair") {
+        "Clear"
+    } else {
+        ""
+    }
+}
+
+/// Parses NWS's wind speed text into m/s, taking the upper end of a range
+/// (`"10 to 15 km/h"` -> 15 km/h). Handles `mph` too, in case a response
+/// ever comes back in US customary units despite `units=si`.
+fn parse_wind_speed_mps(wind_speed: &str) -> f64 {
+    let value = wind_speed
+        .split_whitespace()
+        .filter_map(|word| word.parse::<f64>().ok())
+        .fold(0.0, f64::max);
+    if wind_speed.contains("mph") {
+        value * 0.447_04
+    } else {
+        value / 3.6
+    }
+}
+
+/// Converts a 16-point compass direction (`"SW"`) to degrees -- the
+/// inverse of `ui::temperature::compass_direction`. Unknown text maps to
+/// `0` (north), same fallback the other providers use for missing data.
+fn compass_to_degrees(direction: &str) -> i64 {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    POINTS
+        .iter()
+        .position(|point| point.eq_ignore_ascii_case(direction.trim()))
+        .map(|index| (index as f64 * 22.5).round() as i64)
+        .unwrap_or(0)
+}
+
+fn to_celsius(temperature: f64, unit: &str) -> f64 {
+    if unit.eq_ignore_ascii_case("F") {
+        (temperature - 32.0) * 5.0 / 9.0
+    } else {
+        temperature
+    }
+}
+
+/// Parses an RFC 3339 timestamp with offset (as every NWS time is) into a
+/// Unix epoch, `0` on failure -- same "don't fail the fetch over a
+/// display-only field" fallback as `google_weather_api::
+/// resolve_epoch_and_offset`.
+fn parse_epoch(rfc3339: &str) -> i64 {
+    rfc3339
+        .parse::<jiff::Timestamp>()
+        .map(|ts| ts.as_second())
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to parse NWS timestamp {rfc3339}: {e}");
+            0
+        })
+}
+
+/// `start_time`'s local calendar date -- the first ten characters, since
+/// NWS already expresses every period time in the gridpoint's local offset.
+fn local_date(start_time: &str) -> &str {
+    start_time.get(..10).unwrap_or(start_time)
+}
+
+fn map_severity(severity: &str) -> AlertSeverity {
+    match severity {
+        "Extreme" => AlertSeverity::Extreme,
+        "Severe" => AlertSeverity::Severe,
+        "Moderate" => AlertSeverity::Moderate,
+        "Minor" => AlertSeverity::Minor,
+        _ => AlertSeverity::UnknownSeverity,
+    }
+}
+
+fn map_current(
+    hourly: &GridpointForecastResponse,
+    time_zone: &str,
+    lat: f64,
+    lon: f64,
+    location_name: &str,
+) -> Result<ApiResponse, ApiError> {
+    let periods = &hourly.properties.periods;
+    let now = periods.first().ok_or(ApiError::InvalidResponse)?;
+    let temp = to_celsius(now.temperature, &now.temperature_unit);
+
+    let next_day = periods
+        .iter()
+        .take(24)
+        .map(|p| to_celsius(p.temperature, &p.temperature_unit));
+    let (temp_min, temp_max) = next_day.fold((temp, temp), |(lo, hi), t| (lo.min(t), hi.max(t)));
+
+    let start = parse_epoch(&now.start_time);
+    let zoned = jiff::Timestamp::from_second(start)
+        .ok()
+        .and_then(|ts| ts.in_tz(time_zone).ok());
+    let timezone = zoned
+        .as_ref()
+        .map(|z| z.offset().seconds() as i64)
+        .unwrap_or(0);
+    let (sunrise, sunset) = zoned
+        .and_then(|z| sunrise_sunset(lat, lon, z.date()))
+        .unwrap_or((0, 0));
+
+    Ok(ApiResponse {
+        weather: vec![Weather {
+            main: short_forecast_to_owm_main(&now.short_forecast).to_string(),
+            description: now.short_forecast.clone(),
+        }],
+        main: Main {
+            temp,
+            feels_like: temp,
+            temp_min,
+            temp_max,
+            pressure: 0,
+            humidity: now.relative_humidity.value.unwrap_or(0.0).round() as i64,
+        },
+        wind: Wind {
+            speed: parse_wind_speed_mps(&now.wind_speed),
+            deg: compass_to_degrees(&now.wind_direction),
+        },
+        visibility: 0,
+        sys: Sys { sunrise, sunset },
+        timezone,
+        name: location_name.to_string(),
+        coord: None,
+        provider: None,
+    })
+}
+
+/// The hourly periods as `ForecastHour`s, plus the UTC offset at the first
+/// one (for `HourlyForecast::timezone`).
+fn map_hours(hourly: &GridpointForecastResponse, time_zone: &str) -> (Vec<ForecastHour>, i64) {
+    let hours: Vec<ForecastHour> = hourly
+        .properties
+        .periods
+        .iter()
+        .take(MAX_FORECAST_HOURS)
+        .map(|period| ForecastHour {
+            dt: parse_epoch(&period.start_time),
+            temp: to_celsius(period.temperature, &period.temperature_unit),
+            description: period.short_forecast.clone(),
+            symbol: get_weather_symbol(short_forecast_to_owm_main(&period.short_forecast)),
+            pop: period.probability_of_precipitation.value.unwrap_or(0.0) / 100.0,
+        })
+        .collect();
+    let offset = hours
+        .first()
+        .and_then(|hour| jiff::Timestamp::from_second(hour.dt).ok())
+        .and_then(|ts| ts.in_tz(time_zone).ok())
+        .map_or(0, |zoned| zoned.offset().seconds() as i64);
+    (hours, offset)
+}
+
+/// Folds NWS's alternating 12-hour day/night periods into one
+/// `ForecastDay` per local date: the daytime period supplies the high,
+/// condition, wind, and humidity; the night that *starts* on the same date
+/// supplies the low (NWS pairs "Sunday" with "Sunday Night" the same way).
+/// A date with only a night period -- "Tonight", when fetched after 6pm --
+/// uses that period for everything. One whose night comes first --
+/// "Overnight", when fetched before 6am -- starts out that way, and the
+/// daytime period replaces everything but the low when it arrives.
+fn map_forecast_days(daily: &GridpointForecastResponse) -> Vec<ForecastDay> {
+    let mut days: Vec<ForecastDay> = Vec::new();
+    let mut dates: Vec<&str> = Vec::new();
+
+    for period in &daily.properties.periods {
+        let date = local_date(&period.start_time);
+        let temp = to_celsius(period.temperature, &period.temperature_unit);
+        let pop = period.probability_of_precipitation.value.unwrap_or(0.0) / 100.0;
+
+        if let Some(index) = dates.iter().position(|d| *d == date) {
+            let day = &mut days[index];
+            if period.is_daytime {
+                day.temp_max = temp;
+                day.description = period.short_forecast.clone();
+                day.symbol = get_weather_symbol(short_forecast_to_owm_main(&period.short_forecast));
+                day.feels_like = temp;
+                day.humidity = period.relative_humidity.value.unwrap_or(0.0).round() as i64;
+                day.wind_speed = parse_wind_speed_mps(&period.wind_speed);
+                day.wind_deg = compass_to_degrees(&period.wind_direction);
+            } else {
+                day.temp_min = temp;
+            }
+            day.pop = day.pop.max(pop);
+            continue;
+        }
+        if days.len() == FORECAST_DAYS {
+            break;
+        }
+
+        dates.push(date);
+        days.push(ForecastDay {
+            date: date.to_string(),
+            temp_min: temp,
+            temp_max: temp,
+            description: period.short_forecast.clone(),
+            symbol: get_weather_symbol(short_forecast_to_owm_main(&period.short_forecast)),
+            feels_like: temp,
+            humidity: period.relative_humidity.value.unwrap_or(0.0).round() as i64,
+            wind_speed: parse_wind_speed_mps(&period.wind_speed),
+            wind_deg: compass_to_degrees(&period.wind_direction),
+            pressure: 0,
+            visibility: 0,
+            pop,
+        });
+    }
+
+    days
+}
+
+fn map_alert(properties: AlertProperties) -> WeatherAlert {
+    let start = properties.onset.or(properties.effective);
+    let end = properties.ends.or(properties.expires);
+    WeatherAlert {
+        id: properties.id,
+        title: properties
+            .headline
+            .unwrap_or_else(|| properties.event.clone()),
+        description: properties.description,
+        event_type: properties.event,
+        severity: map_severity(&properties.severity),
+        start_time: start.as_deref().map(parse_epoch).unwrap_or(0),
+        end_time: end.as_deref().map(parse_epoch).unwrap_or(0),
+        // Upper-cased to match the CAP enum spelling Google returns
+        // ("IMMEDIATE", "OBSERVED"), so both alert sources read the same.
+        urgency: properties.urgency.to_ascii_uppercase(),
+        certainty: properties.certainty.to_ascii_uppercase(),
+        area_name: properties.area_desc,
+        // NWS sends one free-text block with paragraphs separated by blank
+        // lines; split so each reads as its own instruction.
+        instruction: properties
+            .instruction
+            .unwrap_or_default()
+            .split("\n\n")
+            .map(|paragraph| paragraph.replace('\n', " ").trim().to_string())
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect(),
+        safety_recommendations: vec![],
+    }
+}
+
+/// A real implementation of the `WeatherProvider` trait for the US National
+/// Weather Service. Needs no API key -- see the module docs.
+pub struct NwsProvider {
+    /// Shared with every other provider -- see `http`.
+    client: reqwest::Client,
+    /// Where the NWS API and the geocoding API live.
+    endpoints: Endpoints,
+}
+
+impl NwsProvider {
+    /// Creates a new `NwsProvider` sending its requests through `client`
+    /// (see `http`) to `endpoints`. Takes no `Language`: NWS forecasts are
+    /// published in English only.
+    pub fn new(client: reqwest::Client, endpoints: Endpoints) -> Self {
+        Self { client, endpoints }
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, url: &str, what: &str) -> Result<T, ApiError> {
+        let response = self
+            .client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/geo+json")
+            .send()
+            .await
+            .map_err(ApiError::RequestFailed)?;
+
+        if !response.status().is_success() {
+            log::error!("NWS {what} request failed: {}", response.status());
+            return Err(ApiError::from_response(response).await);
+        }
+
+        response.json::<T>().await.map_err(|e| {
+            log::error!("Failed to parse NWS {what} response: {e}");
+            ApiError::InvalidResponse
+        })
+    }
+
+    /// `/points` rejects more than four decimal places with a redirect, so
+    /// coordinates are rounded to match. Its `404` means the place is
+    /// outside NWS's coverage, not that it doesn't exist -- the geocoder
+    /// already found it -- so a fallback provider gets to answer instead.
+    async fn lookup_point(&self, lat: f64, lon: f64) -> Result<PointProperties, ApiError> {
+        let url = format!("{}/points/{lat:.4},{lon:.4}", self.endpoints.nws());
+        let points: PointsResponse = self.get_json(&url, "points").await.map_err(|e| match e {
+            ApiError::CityNotFound => ApiError::UnsupportedLocation,
+            e => e,
+        })?;
+        Ok(points.properties)
+    }
+
+    /// `/points` links its gridpoint forecasts by absolute URL, always on
+    /// api.weather.gov -- only the path is kept, on `Endpoints::nws`, so a
+    /// configured mirror isn't bypassed for the forecasts themselves.
+    fn gridpoint_url(&self, link: &str) -> String {
+        let path = reqwest::Url::parse(link)
+            .map_or_else(|_| link.to_string(), |url| url.path().to_string());
+        format!("{}{path}?units=si", self.endpoints.nws())
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for NwsProvider {
+    async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+        let coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let point = self.lookup_point(coord.lat, coord.lon).await?;
+        let hourly: GridpointForecastResponse = self
+            .get_json(
+                &self.gridpoint_url(&point.forecast_hourly),
+                "hourly forecast",
+            )
+            .await?;
+        let mut weather = map_current(
+            &hourly,
+            &point.time_zone,
+            coord.lat,
+            coord.lon,
+            &location.display_name(),
+        )?;
+        // NWS's own zone is at least as good as the geocoder's.
+        weather.coord = Some(Coord {
+            timezone: Some(point.time_zone.clone()),
+            ..coord
+        });
+        Ok(weather)
+    }
+
+    async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let point = self.lookup_point(lat, lon).await?;
+        let daily: GridpointForecastResponse = self
+            .get_json(&self.gridpoint_url(&point.forecast), "forecast")
+            .await?;
+
+        Ok(ForecastResponse {
+            location_name: location.display_name(),
+            days: map_forecast_days(&daily),
+            provider: None,
+        })
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let point = self.lookup_point(lat, lon).await?;
+        let hourly: GridpointForecastResponse = self
+            .get_json(
+                &self.gridpoint_url(&point.forecast_hourly),
+                "hourly forecast",
+            )
+            .await?;
+        let (hours, timezone) = map_hours(&hourly, &point.time_zone);
+        Ok(HourlyForecast {
+            location_name: location.display_name(),
+            timezone,
+            hours,
+            provider: None,
+        })
+    }
+
+    fn supports_alerts(&self) -> bool {
+        true
+    }
+
+    async fn get_alerts(&self, location: &LocationConfig) -> Result<Vec<WeatherAlert>, ApiError> {
+        let Coord { lat, lon, .. } =
+            resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let url = format!(
+            "{}/alerts/active?point={lat:.4},{lon:.4}",
+            self.endpoints.nws()
+        );
+        let alerts: AlertCollection = self.get_json(&url, "alerts").await?;
+        Ok(alerts
+            .features
+            .into_iter()
+            .map(|feature| map_alert(feature.properties))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::openweather_api::WeatherSymbol;
+
+    const POINTS: &str = include_str!("../../tests/fixtures/nws/points.json");
+    const FORECAST: &str = include_str!("../../tests/fixtures/nws/forecast.json");
+    const FORECAST_OVERNIGHT: &str =
+        include_str!("../../tests/fixtures/nws/forecast_overnight.json");
+    const FORECAST_HOURLY: &str = include_str!("../../tests/fixtures/nws/forecast_hourly.json");
+    const ALERTS: &str = include_str!("../../tests/fixtures/nws/alerts.json");
+
+    #[test]
+    fn test_short_forecast_mapping() {
+        assert_eq!(short_forecast_to_owm_main("Sunny"), "Clear");
+        assert_eq!(short_forecast_to_owm_main("Mostly Clear"), "Clear");
+        assert_eq!(short_forecast_to_owm_main("Partly Sunny"), "Clouds");
+        assert_eq!(short_forecast_to_owm_main("Mostly Cloudy"), "Clouds");
+        assert_eq!(short_forecast_to_owm_main("Rain Showers Likely"), "Rain");
+        assert_eq!(
+            short_forecast_to_owm_main("Chance Showers And Thunderstorms"),
+            "Thunderstorm"
+        );
+        assert_eq!(short_forecast_to_owm_main("Light Snow"), "Snow");
+        assert_eq!(short_forecast_to_owm_main("Patchy Fog"), "Fog");
+        assert_eq!(short_forecast_to_owm_main("Something New"), "");
+    }
+
+    #[test]
+    fn test_wind_parsing() {
+        assert!((parse_wind_speed_mps("18 km/h") - 5.0).abs() < 1e-9);
+        assert!((parse_wind_speed_mps("10 to 18 km/h") - 5.0).abs() < 1e-9);
+        assert!((parse_wind_speed_mps("10 mph") - 4.4704).abs() < 1e-9);
+        assert_eq!(parse_wind_speed_mps(""), 0.0);
+        assert_eq!(compass_to_degrees("N"), 0);
+        assert_eq!(compass_to_degrees("SW"), 225);
+        assert_eq!(compass_to_degrees("WNW"), 293);
+        assert_eq!(compass_to_degrees("??"), 0);
+    }
+
+    #[test]
+    fn test_points_fixture() {
+        let points: PointsResponse = serde_json::from_str(POINTS).unwrap();
+        assert_eq!(
+            points.properties.forecast_hourly,
+            "https://api.weather.gov/gridpoints/ILX/28,64/forecast/hourly"
+        );
+        assert_eq!(points.properties.time_zone, "America/Chicago");
+    }
+
+    #[test]
+    fn test_hourly_fixture_maps_to_current_conditions() {
+        let hourly: GridpointForecastResponse = serde_json::from_str(FORECAST_HOURLY).unwrap();
+        let weather = map_current(&hourly, "America/Chicago", 40.6936, -89.5890, "Peoria").unwrap();
+        assert_eq!(weather.name, "Peoria");
+        assert_eq!(weather.weather[0].main, "Clear");
+        assert_eq!(weather.weather[0].description, "Sunny");
+        assert_eq!(weather.main.temp, 27.0);
+        assert_eq!(weather.main.temp_max, 29.0);
+        assert_eq!(weather.main.temp_min, 19.0);
+        assert_eq!(weather.main.humidity, 58);
+        assert_eq!(weather.wind.deg, 225);
+        assert_eq!(weather.timezone, -5 * 3600);
+        // Computed locally -- see `sun.rs`.
+        assert!(weather.sys.sunrise > 0 && weather.sys.sunset > weather.sys.sunrise);
+    }
+
+    #[test]
+    fn test_hourly_fixture_maps_to_hours() {
+        let hourly: GridpointForecastResponse = serde_json::from_str(FORECAST_HOURLY).unwrap();
+        let (hours, offset) = map_hours(&hourly, "America/Chicago");
+        assert_eq!(hours.len(), 3);
+        assert_eq!(offset, -5 * 3600);
+        assert_eq!(hours[0].dt, parse_epoch("2026-07-04T17:00:00Z"));
+        let storms = &hours[2];
+        assert_eq!(storms.symbol, WeatherSymbol::Thunderstorm);
+        assert!((storms.pop - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forecast_fixture_pairs_day_and_night_periods() {
+        let daily: GridpointForecastResponse = serde_json::from_str(FORECAST).unwrap();
+        let days = map_forecast_days(&daily);
+        assert_eq!(days.len(), 2);
+
+        assert_eq!(days[0].date, "2026-07-04");
+        assert_eq!(days[0].temp_max, 31.0);
+        assert_eq!(days[0].temp_min, 19.0);
+        assert_eq!(days[0].symbol, WeatherSymbol::Clear);
+        // The max over both periods -- tonight's storms count for today.
+        assert!((days[0].pop - 0.4).abs() < 1e-9);
+
+        assert_eq!(days[1].date, "2026-07-05");
+        assert_eq!(days[1].temp_max, 28.0);
+        assert_eq!(days[1].temp_min, 16.0);
+        assert_eq!(days[1].symbol, WeatherSymbol::Rain);
+        assert_eq!(days[1].wind_deg, 315);
+    }
+
+    #[test]
+    fn test_overnight_period_gives_way_to_the_day() {
+        let daily: GridpointForecastResponse = serde_json::from_str(FORECAST_OVERNIGHT).unwrap();
+        let days = map_forecast_days(&daily);
+        assert_eq!(days.len(), 1);
+
+        let today = &days[0];
+        assert_eq!(today.date, "2026-07-05");
+        assert_eq!(today.description, "Sunny");
+        assert_eq!(today.symbol, WeatherSymbol::Clear);
+        assert_eq!(today.temp_max, 29.0);
+        assert_eq!(today.feels_like, 29.0);
+        assert_eq!(today.humidity, 55);
+        assert_eq!(today.wind_deg, 315);
+        assert!((today.wind_speed - 20.0 / 3.6).abs() < 1e-9);
+        // The low is still the night that starts today, not the overnight.
+        assert_eq!(today.temp_min, 16.0);
+        assert!((today.pop - 0.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_alerts_fixture_maps_cap_fields() {
+        let alerts: AlertCollection = serde_json::from_str(ALERTS).unwrap();
+        let alerts: Vec<WeatherAlert> = alerts
+            .features
+            .into_iter()
+            .map(|feature| map_alert(feature.properties))
+            .collect();
+        assert_eq!(alerts.len(), 2);
+
+        let warning = &alerts[0];
+        assert_eq!(warning.event_type, "Severe Thunderstorm Warning");
+        assert_eq!(warning.severity, AlertSeverity::Severe);
+        assert_eq!(warning.urgency, "IMMEDIATE");
+        assert_eq!(warning.certainty, "OBSERVED");
+        assert_eq!(warning.area_name, "Peoria, IL; Tazewell, IL");
+        assert_eq!(warning.start_time, parse_epoch("2026-07-04T21:12:00-05:00"));
+        assert_eq!(warning.end_time, parse_epoch("2026-07-04T22:00:00-05:00"));
+        assert_eq!(warning.instruction.len(), 2);
+        assert!(
+            warning
+                .title
+                .starts_with("Severe Thunderstorm Warning issued")
+        );
+
+        // No headline/onset/ends/instruction: falls back to event name,
+        // `effective`, and `expires`.
+        let advisory = &alerts[1];
+        assert_eq!(advisory.severity, AlertSeverity::Moderate);
+        assert_eq!(advisory.certainty, "LIKELY");
+        assert_eq!(advisory.title, "Heat Advisory");
+        assert_eq!(
+            advisory.start_time,
+            parse_epoch("2026-07-04T03:45:00-05:00")
+        );
+        assert_eq!(advisory.end_time, parse_epoch("2026-07-04T20:00:00-05:00"));
+        assert!(advisory.instruction.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gridpoint_requests_use_the_configured_base() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}/nws", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in [POINTS, FORECAST_HOURLY] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 1024];
+                let read = socket.read(&mut request).await.unwrap();
+                requests.push(String::from_utf8_lossy(&request[..read]).into_owned());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/geo+json\r\n\
+                     content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+            requests
+        });
+
+        let endpoints = Endpoints {
+            nws: Some(base),
+            ..Endpoints::default()
+        };
+        let provider = NwsProvider::new(reqwest::Client::new(), endpoints);
+        let location = LocationConfig {
+            lat: Some(40.6936),
+            lon: Some(-89.589),
+            ..LocationConfig::default()
+        };
+        let hourly = provider.get_hourly(&location).await.unwrap();
+        assert!(!hourly.hours.is_empty());
+        let requests = server.await.unwrap();
+        assert!(
+            requests[0].starts_with("GET /nws/points/40.6936,-89.5890 "),
+            "{}",
+            requests[0]
+        );
+        assert!(
+            requests[1].starts_with("GET /nws/gridpoints/ILX/28,64/forecast/hourly?units=si "),
+            "{}",
+            requests[1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_points_404_is_an_unsupported_location() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let read = socket.read(&mut request).await.unwrap();
+            assert!(
+                String::from_utf8_lossy(&request[..read])
+                    .starts_with("GET /points/59.9139,10.7522 ")
+            );
+            let body = r#"{"title": "Data Unavailable For Requested Point", "status": 404}"#;
+            let response = format!(
+                "HTTP/1.1 404 Not Found\r\ncontent-type: application/problem+json\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let endpoints = Endpoints {
+            nws: Some(base),
+            ..Endpoints::default()
+        };
+        let provider = NwsProvider::new(reqwest::Client::new(), endpoints);
+        let oslo = LocationConfig {
+            lat: Some(59.9139),
+            lon: Some(10.7522),
+            ..LocationConfig::default()
+        };
+        let result = provider.get_weather(&oslo).await;
+        assert!(
+            matches!(result, Err(ApiError::UnsupportedLocation)),
+            "{result:?}"
+        );
+        server.await.unwrap();
+    }
+}
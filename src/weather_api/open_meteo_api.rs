@@ -0,0 +1,568 @@
+//! # Open-Meteo Provider
+//!
+//! Real implementation of `WeatherProvider` against Open-Meteo's free
+//! forecast API (`https://api.open-meteo.com/v1/forecast`) -- the one
+//! provider that needs no API key at all, so a fresh install can show real
+//! weather before anyone has signed up for anything. Free for
+//! non-commercial use at up to 10,000 calls/day; see
+//! <https://open-meteo.com/en/terms>.
+//!
+//! A few ways this API differs from the keyed providers:
+//!
+//! - **Geocoding.** Like Google, the forecast endpoint only takes
+//!   coordinates. City names are resolved via Open-Meteo's own geocoding
+//!   API -- see `geocoding.rs`.
+//! - **No condition text.** Conditions come back as numeric WMO weather
+//!   interpretation codes only (`weather_code`), so both the icon and the
+//!   description are derived locally from that code. The descriptions are
+//!   English regardless of the configured `Language` -- there's nothing to
+//!   translate on the server side.
+//! - **No alerts.** Open-Meteo doesn't publish weather warnings, so
+//!   `get_alerts` keeps the trait's empty default.
+//!
+//! The hourly strip (`get_hourly`) is a separate request for the `hourly`
+//! block alone, with `forecast_hours` counting from the current hour.
+//!
+//! Units are requested to match the shared `ApiResponse`/`ForecastDay`
+//! shapes directly (Celsius, m/s, meters, hPa), and `timeformat=unixtime`
+//! plus `timezone=auto` gives Unix timestamps alongside the location's own
+//! UTC offset, so daily buckets line up with the location's local days.
+
+use crate::config::LocationConfig;
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::{
+    ForecastDay, ForecastHour, ForecastResponse, HourlyForecast, MAX_FORECAST_HOURS,
+};
+use crate::weather_api::geocoding::resolve;
+use crate::weather_api::openweather_api::{
+    ApiError, ApiResponse, Main, Sys, Weather, Wind, get_weather_symbol,
+};
+use crate::weather_api::weather_provider::WeatherProvider;
+use async_trait::async_trait;
+use serde::Deserialize;
+
+/// Matches `forecast::MAX_FORECAST_DAYS` -- see `google_weather_api`'s
+/// constant of the same name.
+const FORECAST_DAYS: u8 = 5;
+
+const CURRENT_FIELDS: &str = "temperature_2m,apparent_temperature,relative_humidity_2m,\
+weather_code,pressure_msl,wind_speed_10m,wind_direction_10m,visibility";
+const DAILY_FIELDS: &str = "weather_code,temperature_2m_max,temperature_2m_min,\
+apparent_temperature_max,sunrise,sunset,precipitation_probability_max,\
+wind_speed_10m_max,wind_direction_10m_dominant,relative_humidity_2m_mean";
+const HOURLY_FIELDS: &str = "temperature_2m,weather_code,precipitation_probability";
+
+// --- Open-Meteo response types ------------------------------------------
+
+#[derive(Deserialize, Debug)]
+struct CurrentBlock {
+    temperature_2m: f64,
+    apparent_temperature: f64,
+    relative_humidity_2m: f64,
+    weather_code: i64,
+    pressure_msl: f64,
+    wind_speed_10m: f64,
+    wind_direction_10m: f64,
+    /// Not every model behind Open-Meteo's "best match" blend reports
+    /// visibility -- missing or `null` degrades to `0` rather than failing
+    /// the whole fetch over one stat chip.
+    #[serde(default)]
+    visibility: Option<f64>,
+}
+
+/// Open-Meteo returns daily data column-wise: one array per requested
+/// variable, all indexed by `time`. Individual entries can be `null` when a
+/// model has no value for that day (most often `precipitation_probability_
+/// max` near the end of the range), hence the `Option`s.
+#[derive(Deserialize, Debug, Default)]
+struct DailyBlock {
+    #[serde(default)]
+    time: Vec<i64>,
+    #[serde(default)]
+    weather_code: Vec<Option<i64>>,
+    #[serde(default)]
+    temperature_2m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    temperature_2m_min: Vec<Option<f64>>,
+    #[serde(default)]
+    apparent_temperature_max: Vec<Option<f64>>,
+    #[serde(default)]
+    sunrise: Vec<Option<i64>>,
+    #[serde(default)]
+    sunset: Vec<Option<i64>>,
+    #[serde(default)]
+    precipitation_probability_max: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_speed_10m_max: Vec<Option<f64>>,
+    #[serde(default)]
+    wind_direction_10m_dominant: Vec<Option<f64>>,
+    #[serde(default)]
+    relative_humidity_2m_mean: Vec<Option<f64>>,
+}
+
+/// Column-wise like `DailyBlock`, one entry per hour.
+#[derive(Deserialize, Debug, Default)]
+struct HourlyBlock {
+    #[serde(default)]
+    time: Vec<i64>,
+    #[serde(default)]
+    temperature_2m: Vec<Option<f64>>,
+    #[serde(default)]
+    weather_code: Vec<Option<i64>>,
+    #[serde(default)]
+    precipitation_probability: Vec<Option<f64>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ForecastApiResponse {
+    #[serde(default)]
+    utc_offset_seconds: i64,
+    /// The IANA zone `timezone=auto` picked, e.g. "Europe/Oslo".
+    #[serde(default)]
+    timezone: Option<String>,
+    #[serde(default)]
+    current: Option<CurrentBlock>,
+    #[serde(default)]
+    daily: DailyBlock,
+    #[serde(default)]
+    hourly: HourlyBlock,
+}
+
+/// Reads `column[index]`, treating both a short column and a `null` entry
+/// as missing.
+fn daily_value<T: Copy>(column: &[Option<T>], index: usize) -> Option<T> {
+    column.get(index).copied().flatten()
+}
+
+/// Maps a WMO weather interpretation code onto the OpenWeatherMap "main"
+/// condition strings `get_weather_symbol` already knows how to turn into an
+/// icon -- same approach as `google_weather_api::
+/// google_condition_to_owm_main`. Unknown codes fall through to `""`
+/// (`WeatherSymbol::Default`).
+fn wmo_code_to_owm_main(code: i64) -> &'static str {
+    match code {
+        0 | 1 => "Clear",
+        2 | 3 => "Clouds",
+        45 | 48 => "Fog",
+        51..=57 => "Drizzle",
+        61..=67 | 80..=82 => "Rain",
+        71..=77 | 85 | 86 => "Snow",
+        95..=99 => "Thunderstorm",
+        _ => "",
+    }
+}
+
+/// English description for a WMO weather interpretation code, worded (and
+/// lowercased) like OpenWeatherMap's own `description` field so the hero
+/// card reads the same whichever provider is active.
+fn wmo_code_description(code: i64) -> &'static str {
+    match code {
+        0 => "clear sky",
+        1 => "mainly clear",
+        2 => "partly cloudy",
+        3 => "overcast",
+        45 => "fog",
+        48 => "depositing rime fog",
+        51 => "light drizzle",
+        53 => "moderate drizzle",
+        55 => "dense drizzle",
+        56 => "light freezing drizzle",
+        57 => "dense freezing drizzle",
+        61 => "slight rain",
+        63 => "moderate rain",
+        65 => "heavy rain",
+        66 => "light freezing rain",
+        67 => "heavy freezing rain",
+        71 => "slight snow fall",
+        73 => "moderate snow fall",
+        75 => "heavy snow fall",
+        77 => "snow grains",
+        80 => "slight rain showers",
+        81 => "moderate rain showers",
+        82 => "violent rain showers",
+        85 => "slight snow showers",
+        86 => "heavy snow showers",
+        95 => "thunderstorm",
+        96 => "thunderstorm with slight hail",
+        99 => "thunderstorm with heavy hail",
+        _ => "unknown",
+    }
+}
+
+/// Formats a daily `time` entry (the Unix timestamp of local midnight) as
+/// the `YYYY-MM-DD` date `ForecastDay::date` expects, in the location's own
+/// calendar rather than UTC's.
+fn local_date(unix: i64, utc_offset_seconds: i64) -> String {
+    jiff::Timestamp::from_second(unix + utc_offset_seconds)
+        .map(|ts| ts.to_zoned(jiff::tz::TimeZone::UTC).date().to_string())
+        .unwrap_or_default()
+}
+
+/// Builds the forecast endpoint's query params -- a pure function so the
+/// requested fields/units can be unit-tested without a live network call.
+/// `current` is only requested when `include_current` is set; the daily
+/// block is always requested, since even `get_weather` needs today's
+/// min/max and sun events from it.
+fn forecast_query(
+    lat: f64,
+    lon: f64,
+    days: u8,
+    include_current: bool,
+) -> Vec<(&'static str, String)> {
+    let mut query = vec![
+        ("latitude", lat.to_string()),
+        ("longitude", lon.to_string()),
+        ("daily", DAILY_FIELDS.to_string()),
+        ("forecast_days", days.to_string()),
+        ("timezone", "auto".to_string()),
+        ("timeformat", "unixtime".to_string()),
+        ("wind_speed_unit", "ms".to_string()),
+    ];
+    if include_current {
+        query.push(("current", CURRENT_FIELDS.to_string()));
+    }
+    query
+}
+
+/// Builds the query params for `get_hourly`'s request. See
+/// `forecast_query`'s docs.
+fn hourly_query(lat: f64, lon: f64) -> Vec<(&'static str, String)> {
+    vec![
+        ("latitude", lat.to_string()),
+        ("longitude", lon.to_string()),
+        ("hourly", HOURLY_FIELDS.to_string()),
+        ("forecast_hours", MAX_FORECAST_HOURS.to_string()),
+        ("timezone", "auto".to_string()),
+        ("timeformat", "unixtime".to_string()),
+    ]
+}
+
+async fn fetch_forecast(
+    client: &reqwest::Client,
+    base: &str,
+    query: &[(&'static str, String)],
+) -> Result<ForecastApiResponse, ApiError> {
+    let response = client
+        .get(format!("{base}/forecast"))
+        .query(query)
+        .send()
+        .await
+        .map_err(ApiError::RequestFailed)?;
+
+    if !response.status().is_success() {
+        log::error!("Open-Meteo forecast request failed: {}", response.status());
+        return Err(ApiError::from_response(response).await);
+    }
+
+    response.json::<ForecastApiResponse>().await.map_err(|e| {
+        log::error!("Failed to parse Open-Meteo forecast response: {e}");
+        ApiError::InvalidResponse
+    })
+}
+
+/// The IANA timezone at `lat`/`lon` -- a forecast request for no data at
+/// all, just the zone `timezone=auto` picks. For providers whose responses
+/// are UTC-only (MET Norway) and locations with no name to geocode.
+/// `base` is `Endpoints::open_meteo`.
+pub(crate) async fn time_zone_at(
+    client: &reqwest::Client,
+    base: &str,
+    lat: f64,
+    lon: f64,
+) -> Result<Option<String>, ApiError> {
+    let query = [
+        ("latitude", lat.to_string()),
+        ("longitude", lon.to_string()),
+        ("forecast_days", "1".to_string()),
+        ("timezone", "auto".to_string()),
+    ];
+    let response = fetch_forecast(client, base, &query).await?;
+    Ok(response.timezone)
+}
+
+fn map_current(
+    response: &ForecastApiResponse,
+    location_name: &str,
+) -> Result<ApiResponse, ApiError> {
+    let current = response.current.as_ref().ok_or(ApiError::InvalidResponse)?;
+    let daily = &response.daily;
+
+    Ok(ApiResponse {
+        weather: vec![Weather {
+            main: wmo_code_to_owm_main(current.weather_code).to_string(),
+            description: wmo_code_description(current.weather_code).to_string(),
+        }],
+        main: Main {
+            temp: current.temperature_2m,
+            feels_like: current.apparent_temperature,
+            temp_min: daily_value(&daily.temperature_2m_min, 0).unwrap_or(current.temperature_2m),
+            temp_max: daily_value(&daily.temperature_2m_max, 0).unwrap_or(current.temperature_2m),
+            pressure: current.pressure_msl.round() as i64,
+            humidity: current.relative_humidity_2m.round() as i64,
+        },
+        wind: Wind {
+            speed: current.wind_speed_10m,
+            deg: current.wind_direction_10m.round() as i64,
+        },
+        visibility: current.visibility.unwrap_or(0.0) as i64,
+        sys: Sys {
+            sunrise: daily_value(&daily.sunrise, 0).unwrap_or(0),
+            sunset: daily_value(&daily.sunset, 0).unwrap_or(0),
+        },
+        timezone: response.utc_offset_seconds,
+        name: location_name.to_string(),
+        coord: None,
+        provider: None,
+    })
+}
+
+fn map_forecast_days(response: &ForecastApiResponse) -> Vec<ForecastDay> {
+    let daily = &response.daily;
+    daily
+        .time
+        .iter()
+        .enumerate()
+        .map(|(i, &time)| {
+            let code = daily_value(&daily.weather_code, i).unwrap_or(-1);
+            let temp_max = daily_value(&daily.temperature_2m_max, i).unwrap_or(0.0);
+            ForecastDay {
+                date: local_date(time, response.utc_offset_seconds),
+                temp_min: daily_value(&daily.temperature_2m_min, i).unwrap_or(0.0),
+                temp_max,
+                description: wmo_code_description(code).to_string(),
+                symbol: get_weather_symbol(wmo_code_to_owm_main(code)),
+                feels_like: daily_value(&daily.apparent_temperature_max, i).unwrap_or(temp_max),
+                humidity: daily_value(&daily.relative_humidity_2m_mean, i)
+                    .map(|h| h.round() as i64)
+                    .unwrap_or(0),
+                wind_speed: daily_value(&daily.wind_speed_10m_max, i).unwrap_or(0.0),
+                wind_deg: daily_value(&daily.wind_direction_10m_dominant, i)
+                    .map(|d| d.round() as i64)
+                    .unwrap_or(0),
+                // Not part of Open-Meteo's daily aggregates -- same gap as
+                // Google's per-day forecast (see its `map_forecast_day`).
+                pressure: 0,
+                visibility: 0,
+                pop: daily_value(&daily.precipitation_probability_max, i).unwrap_or(0.0) / 100.0,
+            }
+        })
+        .collect()
+}
+
+fn map_hours(response: &ForecastApiResponse) -> Vec<ForecastHour> {
+    let hourly = &response.hourly;
+    hourly
+        .time
+        .iter()
+        .enumerate()
+        .map(|(i, &dt)| {
+            let code = daily_value(&hourly.weather_code, i).unwrap_or(-1);
+            ForecastHour {
+                dt,
+                temp: daily_value(&hourly.temperature_2m, i).unwrap_or(0.0),
+                description: wmo_code_description(code).to_string(),
+                symbol: get_weather_symbol(wmo_code_to_owm_main(code)),
+                pop: daily_value(&hourly.precipitation_probability, i).unwrap_or(0.0) / 100.0,
+            }
+        })
+        .collect()
+}
+
+/// A real implementation of the `WeatherProvider` trait for Open-Meteo.
+/// Needs no API key -- see the module docs.
+pub struct OpenMeteoProvider {
+    /// Shared with every other provider -- see `http`.
+    client: reqwest::Client,
+    /// Where Open-Meteo's forecast and geocoding APIs live.
+    endpoints: Endpoints,
+}
+
+impl OpenMeteoProvider {
+    /// Creates a new `OpenMeteoProvider` sending its requests through `client`
+    /// (see `http`) to `endpoints`. Takes no `Language`: Open-Meteo
+    /// returns no condition text to localize (see the module docs).
+    pub fn new(client: reqwest::Client, endpoints: Endpoints) -> Self {
+        Self { client, endpoints }
+    }
+}
+
+#[async_trait]
+impl WeatherProvider for OpenMeteoProvider {
+    async fn get_weather(&self, location: &LocationConfig) -> Result<ApiResponse, ApiError> {
+        let coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let response = fetch_forecast(
+            &self.client,
+            self.endpoints.open_meteo(),
+            &forecast_query(coord.lat, coord.lon, 1, true),
+        )
+        .await?;
+        let mut weather = map_current(&response, &location.display_name())?;
+        weather.coord = Some(coord);
+        Ok(weather)
+    }
+
+    async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError> {
+        let coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let query = forecast_query(coord.lat, coord.lon, FORECAST_DAYS, false);
+        let response = fetch_forecast(&self.client, self.endpoints.open_meteo(), &query).await?;
+
+        Ok(ForecastResponse {
+            location_name: location.display_name(),
+            days: map_forecast_days(&response),
+            provider: None,
+        })
+    }
+
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError> {
+        let coord = resolve(&self.client, self.endpoints.geocoding(), location).await?;
+        let response = fetch_forecast(
+            &self.client,
+            self.endpoints.open_meteo(),
+            &hourly_query(coord.lat, coord.lon),
+        )
+        .await?;
+        Ok(HourlyForecast {
+            location_name: location.display_name(),
+            timezone: response.utc_offset_seconds,
+            hours: map_hours(&response),
+            provider: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather_api::openweather_api::WeatherSymbol;
+
+    /// A trimmed real response for Peoria, IL (`America/Chicago`, CDT) with
+    /// both `current` and a two-day `daily` block -- the second day's
+    /// precipitation probability is `null`, as Open-Meteo sometimes
+    /// returns near the end of a model's range.
+    const FIXTURE: &str = r#"{
+        "latitude": 40.69,
+        "longitude": -89.59,
+        "utc_offset_seconds": -18000,
+        "timezone": "America/Chicago",
+        "current": {
+            "time": 1783184400,
+            "interval": 900,
+            "temperature_2m": 24.3,
+            "apparent_temperature": 25.1,
+            "relative_humidity_2m": 61,
+            "weather_code": 2,
+            "pressure_msl": 1014.6,
+            "wind_speed_10m": 3.4,
+            "wind_direction_10m": 203,
+            "visibility": 24140.0
+        },
+        "daily": {
+            "time": [1783141200, 1783227600],
+            "weather_code": [2, 95],
+            "temperature_2m_max": [29.1, 27.4],
+            "temperature_2m_min": [18.2, 19.0],
+            "apparent_temperature_max": [30.4, 28.8],
+            "sunrise": [1783161780, 1783248210],
+            "sunset": [1783215600, 1783301990],
+            "precipitation_probability_max": [15, null],
+            "wind_speed_10m_max": [5.2, 7.9],
+            "wind_direction_10m_dominant": [210, 245],
+            "relative_humidity_2m_mean": [64, 71]
+        }
+    }"#;
+
+    #[test]
+    fn test_wmo_code_mapping() {
+        assert_eq!(wmo_code_to_owm_main(0), "Clear");
+        assert_eq!(wmo_code_to_owm_main(3), "Clouds");
+        assert_eq!(wmo_code_to_owm_main(48), "Fog");
+        assert_eq!(wmo_code_to_owm_main(55), "Drizzle");
+        assert_eq!(wmo_code_to_owm_main(66), "Rain");
+        assert_eq!(wmo_code_to_owm_main(81), "Rain");
+        assert_eq!(wmo_code_to_owm_main(86), "Snow");
+        assert_eq!(wmo_code_to_owm_main(99), "Thunderstorm");
+        assert_eq!(wmo_code_to_owm_main(42), "");
+        assert_eq!(wmo_code_description(0), "clear sky");
+        assert_eq!(wmo_code_description(42), "unknown");
+    }
+
+    #[test]
+    fn test_forecast_query_units_and_current_toggle() {
+        let query = forecast_query(1.0, 2.0, 5, false);
+        assert!(query.contains(&("forecast_days", "5".to_string())));
+        assert!(query.contains(&("wind_speed_unit", "ms".to_string())));
+        assert!(query.contains(&("timeformat", "unixtime".to_string())));
+        assert!(!query.iter().any(|(key, _)| *key == "current"));
+
+        let query = forecast_query(1.0, 2.0, 1, true);
+        assert!(query.contains(&("current", CURRENT_FIELDS.to_string())));
+    }
+
+    #[test]
+    fn test_current_deserialize_and_map() {
+        let parsed: ForecastApiResponse = serde_json::from_str(FIXTURE).unwrap();
+        let weather = map_current(&parsed, "Peoria").unwrap();
+        assert_eq!(weather.name, "Peoria");
+        assert_eq!(weather.weather[0].main, "Clouds");
+        assert_eq!(weather.weather[0].description, "partly cloudy");
+        assert_eq!(weather.main.temp, 24.3);
+        assert_eq!(weather.main.temp_max, 29.1);
+        assert_eq!(weather.main.temp_min, 18.2);
+        assert_eq!(weather.main.humidity, 61);
+        assert_eq!(weather.main.pressure, 1015);
+        assert_eq!(weather.wind.deg, 203);
+        assert_eq!(weather.visibility, 24140);
+        assert_eq!(weather.sys.sunrise, 1783161780);
+        assert_eq!(weather.timezone, -5 * 3600);
+    }
+
+    #[test]
+    fn test_current_missing_is_invalid_response() {
+        let parsed: ForecastApiResponse =
+            serde_json::from_str(r#"{"utc_offset_seconds":0}"#).unwrap();
+        assert!(matches!(
+            map_current(&parsed, "Nowhere"),
+            Err(ApiError::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn test_forecast_days_deserialize_and_map() {
+        let parsed: ForecastApiResponse = serde_json::from_str(FIXTURE).unwrap();
+        let days = map_forecast_days(&parsed);
+        assert_eq!(days.len(), 2);
+        // Local midnight in Chicago, not the UTC date of that instant.
+        assert_eq!(days[0].date, "2026-07-04");
+        assert_eq!(days[1].date, "2026-07-05");
+        assert_eq!(days[0].temp_max, 29.1);
+        assert_eq!(days[0].humidity, 64);
+        assert!((days[0].pop - 0.15).abs() < 1e-9);
+        assert_eq!(days[1].symbol, WeatherSymbol::Thunderstorm);
+        assert_eq!(days[1].description, "thunderstorm");
+        // `null` precipitation probability degrades to 0 rather than
+        // failing the whole day.
+        assert_eq!(days[1].pop, 0.0);
+    }
+
+    #[test]
+    fn test_hourly_query_and_map() {
+        let query = hourly_query(1.0, 2.0);
+        assert!(query.contains(&("forecast_hours", "48".to_string())));
+        assert!(!query.iter().any(|(key, _)| *key == "daily"));
+
+        let parsed: ForecastApiResponse = serde_json::from_str(
+            r#"{"utc_offset_seconds":-18000,"hourly":{
+                "time":[1783184400,1783188000],
+                "temperature_2m":[24.3,22.0],
+                "weather_code":[2,61],
+                "precipitation_probability":[null,70]}}"#,
+        )
+        .unwrap();
+        let hours = map_hours(&parsed);
+        assert_eq!(hours.len(), 2);
+        assert_eq!(hours[0].pop, 0.0);
+        assert_eq!(hours[1].dt, 1783188000);
+        assert_eq!(hours[1].symbol, WeatherSymbol::Rain);
+        assert!((hours[1].pop - 0.7).abs() < 1e-9);
+    }
+}
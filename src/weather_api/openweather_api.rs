@@ -16,11 +16,15 @@
 //! - **Provider Implementation**: Implements the `WeatherProvider` trait for seamless
 //!   integration into the application's provider factory.
 //!
-use crate::config::{Language, LocationConfig};
+use crate::config::{Language, LocationConfig, WeatherApiProvider};
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::retry::RetryPolicy;
 use crate::weather_api::weather_provider::{WeatherProvider, location_config_to_location};
 use async_trait::async_trait;
 use reqwest;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
 
 /// Represents weather conditions returned by the OpenWeatherMap API.
 ///
@@ -59,14 +63,29 @@ pub struct Sys {
     pub sunset: i64,
 }
 
+/// Where the queried location resolved to. Part of OpenWeatherMap's own
+/// response; every other provider fills it in from its geocoding step
+/// (`geocoding::resolve`). The app saves it back onto the `LocationConfig`
+/// (`app::Message::LocationResolved`) so later fetches skip geocoding.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Coord {
+    pub lat: f64,
+    pub lon: f64,
+    /// IANA zone, when the geocoder reported one -- never part of
+    /// OpenWeatherMap's JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
 /// Represents the top-level structure of the JSON response from the OpenWeatherMap API.
 ///
 /// This struct aggregates the most relevant weather information, including a list of weather
 /// conditions, the main meteorological data like temperature and humidity, and the name of the city.
 ///
 /// Derives `Serialize` (in addition to `Deserialize`) so the headless CLI mode
-/// (`src/cli.rs`, bin-only) can emit this directly as `--json` output --
-/// nothing about parsing provider responses needs it, only that output path.
+/// (`src/cli/`, bin-only) can emit this directly as `--json` output, and so
+/// `cache::ResponseCache` can save it to disk -- nothing about parsing
+/// provider responses needs it.
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ApiResponse {
     pub weather: Vec<Weather>,
@@ -79,24 +98,218 @@ pub struct ApiResponse {
     /// sunrise/sunset in local time rather than UTC.
     pub timezone: i64,
     pub name: String,
+    /// See `Coord`. `#[serde(default)]` only for robustness -- every
+    /// provider sets it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coord: Option<Coord>,
+    /// Which provider actually answered -- stamped by `FallbackProvider`
+    /// after the fact, never part of any provider's own JSON. Shown in the
+    /// main window's "Powered by" ribbon and the CLI's `--json` output, since
+    /// with fallbacks configured it isn't necessarily the configured one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<WeatherApiProvider>,
 }
 
-/// Represents possible errors that can occur when interacting with the OpenWeatherMap API.
+/// Represents possible errors that can occur when interacting with a weather provider's API.
 ///
 /// This enum provides detailed error variants to distinguish between different failure modes:
 /// - `RequestFailed`: Indicates a network or HTTP error occurred during the API request.
 /// - `CityNotFound`: Returned when the requested city does not exist or cannot be found by the API.
+/// - `UnsupportedLocation`: The place exists, but the provider doesn't cover it.
 /// - `InvalidResponse`: Indicates that the response from the API could not be parsed or was malformed.
+/// - `Unauthorized`, `RateLimited`, `ServerError`: The API answered with an
+///   error status that says nothing about the city -- see `from_parts`.
+/// - `NotCached`: Offline, with no usable cached response (see `cache`).
+///
+/// `Display` is the user-facing text, shown as-is in the main window, the
+/// Preferences connection test, and the CLI; `Debug` is for logs.
 #[derive(Debug)]
-#[allow(dead_code)]
 pub enum ApiError {
     RequestFailed(reqwest::Error),
     CityNotFound,
+    /// A regional provider (NWS, US only) has no data for a place that
+    /// does exist -- unlike `CityNotFound`, another provider may well.
+    UnsupportedLocation,
     InvalidResponse,
+    /// `401`/`403`, or Google's `400 API_KEY_INVALID`: the API key is
+    /// missing, wrong, expired, or not enabled for this API.
+    Unauthorized {
+        status: u16,
+        message: Option<String>,
+    },
+    /// `429`: the key's (or, for keyless providers, the app's) quota is
+    /// used up. `retry_after` is the response's `Retry-After`, if it sent one.
+    RateLimited {
+        message: Option<String>,
+        retry_after: Option<Duration>,
+    },
+    /// `5xx`: the provider itself is failing.
+    ServerError {
+        status: u16,
+        message: Option<String>,
+    },
+    /// An offline `cache::CachingProvider` had nothing saved for the
+    /// location, or nothing recent enough -- no request was made.
+    NotCached,
+}
+
+impl ApiError {
+    /// Classifies a non-2xx response -- see `from_parts`.
+    pub(crate) async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = response.text().await.unwrap_or_default();
+        Self::from_parts(status, retry_after.as_deref(), &body)
+    }
+
+    /// Maps a non-2xx status, its `Retry-After` header, and its body to an
+    /// error. Auth failures, quota hits (`429`), and server-side failures
+    /// (`5xx`) get their own variants -- none of them says anything about
+    /// the city, and the last two also let `FallbackProvider` move on to the
+    /// next provider. Everything else is still `CityNotFound`: it's what
+    /// `404`/`400` mean for every provider's location-based endpoints.
+    pub(crate) fn from_parts(
+        status: reqwest::StatusCode,
+        retry_after: Option<&str>,
+        body: &str,
+    ) -> Self {
+        let json = serde_json::from_str::<serde_json::Value>(body).ok();
+        let message = json.as_ref().and_then(provider_message);
+        // Google answers a bad key with a generic 400 INVALID_ARGUMENT; only
+        // the `details` entry's reason tells it apart from a bad request.
+        let invalid_google_key = json
+            .as_ref()
+            .and_then(|json| json.pointer("/error/details"))
+            .and_then(serde_json::Value::as_array)
+            .is_some_and(|details| {
+                details
+                    .iter()
+                    .any(|detail| detail["reason"] == "API_KEY_INVALID")
+            });
+
+        if status == reqwest::StatusCode::UNAUTHORIZED
+            || status == reqwest::StatusCode::FORBIDDEN
+            || invalid_google_key
+        {
+            ApiError::Unauthorized {
+                status: status.as_u16(),
+                message,
+            }
+        } else if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            ApiError::RateLimited {
+                message,
+                retry_after: retry_after
+                    .and_then(|value| parse_retry_after(value, jiff::Timestamp::now())),
+            }
+        } else if status.is_server_error() {
+            ApiError::ServerError {
+                status: status.as_u16(),
+                message,
+            }
+        } else {
+            ApiError::CityNotFound
+        }
+    }
+}
+
+/// The human-readable message in a provider's error body: Google's
+/// `error.message`, OpenWeatherMap's `message`, NWS's `detail`, or
+/// Open-Meteo's `reason`.
+fn provider_message(json: &serde_json::Value) -> Option<String> {
+    let message = json
+        .pointer("/error/message")
+        .or_else(|| json.get("message"))
+        .or_else(|| json.get("detail"))
+        .or_else(|| json.get("reason"))
+        .and_then(serde_json::Value::as_str)?
+        .trim();
+    (!message.is_empty()).then(|| message.to_string())
+}
+
+/// `Retry-After` is either a number of seconds or an HTTP date; a date
+/// already in the past means "now".
+pub(crate) fn parse_retry_after(value: &str, now: jiff::Timestamp) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = jiff::fmt::rfc2822::DateTimeParser::new()
+        .parse_timestamp(value)
+        .ok()?;
+    Some(Duration::from_secs(
+        u64::try_from(at.as_second() - now.as_second()).unwrap_or(0),
+    ))
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Not `reqwest::Error`'s own text: it includes the request URL, and
+        // with it OpenWeatherMap's and Google's API key.
+        let detail = |message: &Option<String>| {
+            message
+                .as_deref()
+                .map(|message| format!(": {message}"))
+                .unwrap_or_default()
+        };
+        match self {
+            ApiError::RequestFailed(e) if e.is_timeout() => {
+                write!(f, "The weather service didn't respond in time")
+            }
+            ApiError::RequestFailed(e) if e.is_connect() => write!(
+                f,
+                "Couldn't connect to the weather service -- check your network connection"
+            ),
+            ApiError::RequestFailed(_) => write!(f, "Network error talking to the weather service"),
+            ApiError::CityNotFound => write!(f, "City not found"),
+            ApiError::UnsupportedLocation => {
+                write!(f, "This weather service doesn't cover this location")
+            }
+            ApiError::InvalidResponse => {
+                write!(
+                    f,
+                    "The weather service sent a response that couldn't be read"
+                )
+            }
+            ApiError::Unauthorized { status, message } => write!(
+                f,
+                "The weather service rejected the API key (HTTP {status}){} -- check your API token",
+                detail(message)
+            ),
+            ApiError::RateLimited {
+                message,
+                retry_after,
+            } => {
+                write!(f, "Rate limit reached (HTTP 429){}", detail(message))?;
+                match retry_after {
+                    Some(wait) => write!(f, " -- try again in {}s", wait.as_secs()),
+                    None => write!(f, " -- try again later"),
+                }
+            }
+            ApiError::ServerError { status, message } => write!(
+                f,
+                "The weather service is having problems (HTTP {status}){}",
+                detail(message)
+            ),
+            ApiError::NotCached => write!(f, "Offline, and nothing saved for this location"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::RequestFailed(e) => Some(e),
+            _ => None,
+        }
+    }
 }
 
 /// Represents a symbolic representation of a weather condition.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WeatherSymbol {
     Clear,
     Clouds,
@@ -135,6 +348,9 @@ pub struct Location {
 pub enum GeocodeError {
     RequestFailed(reqwest::Error),
     LocationNotFound,
+    /// A non-2xx answer, already classified by `ApiError::from_response` --
+    /// with a bad key this is the first request to find out.
+    Rejected(ApiError),
 }
 
 /// Fetches geographic coordinates for a given location using the OpenWeatherMap Geocoding API.
@@ -148,11 +364,17 @@ pub enum GeocodeError {
 /// * `state` - The state or region (can be empty).
 /// * `country` - The country code (e.g., "US", "CA").
 /// * `api_key` - Your OpenWeatherMap API key.
+/// * `client` - The shared client to send the request with (see `http`).
+/// * `retry` - How to retry a failed request (see `retry`).
+/// * `base` - Where the API lives (`Endpoints::openweather`).
 async fn get_coords(
     city: &str,
     state: &str,
     country: &str,
     api_key: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
 ) -> Result<Location, GeocodeError> {
     // Build the query string, joining non-empty parts with commas.
     let location_query = [city, state, country]
@@ -164,14 +386,22 @@ async fn get_coords(
 
     // Construct the full API URL. `limit=1` ensures we get only the most relevant result.
     let url = format!(
-        "http://api.openweathermap.org/geo/1.0/direct?q={}&limit=1&appid={}",
+        "{base}/geo/1.0/direct?q={}&limit=1&appid={}",
         location_query, api_key
     );
     // Make the request and parse the JSON response into a Vec of Locations.
     // The API returns an array, even if it's empty or has one item.
-    let locations = reqwest::get(&url)
+    let response = retry
+        .send(|| client.get(&url))
         .await
-        .map_err(GeocodeError::RequestFailed)?
+        .map_err(GeocodeError::RequestFailed)?;
+    if !response.status().is_success() {
+        log::error!("Geocoding request failed: {}", response.status());
+        return Err(GeocodeError::Rejected(
+            ApiError::from_response(response).await,
+        ));
+    }
+    let locations = response
         .json::<Vec<Location>>()
         .await
         .map_err(GeocodeError::RequestFailed)?;
@@ -193,12 +423,21 @@ fn select_location(locations: Vec<Location>) -> Result<Location, GeocodeError> {
 
 /// Resolves a `Location`'s coordinates via `get_coords`, mapping `GeocodeError`
 /// into the `ApiError` variants shared by both current-weather and forecast fetches.
-async fn resolve_location(location: &Location, api_key: &str) -> Result<Location, ApiError> {
+async fn resolve_location(
+    location: &Location,
+    api_key: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
+) -> Result<Location, ApiError> {
     get_coords(
         &location.name,
         location.state.as_deref().unwrap_or(""),
         &location.country.clone().unwrap_or("".to_string()),
         api_key,
+        client,
+        retry,
+        base,
     )
     .await
     .map_err(|e| match e {
@@ -210,48 +449,77 @@ async fn resolve_location(location: &Location, api_key: &str) -> Result<Location
             log::warn!("Location not found");
             ApiError::CityNotFound
         }
+        GeocodeError::Rejected(err) => err,
     })
 }
 
+/// `coordinates` when given (a location's saved ones), otherwise
+/// `resolve_location`'s.
+async fn resolve_coordinates(
+    location: &Location,
+    coordinates: Option<(f64, f64)>,
+    api_key: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
+) -> Result<(f64, f64), ApiError> {
+    match coordinates {
+        Some(coordinates) => Ok(coordinates),
+        None => resolve_location(location, api_key, client, retry, base)
+            .await
+            .map(|resolved| (resolved.lat, resolved.lon)),
+    }
+}
+
 /// Builds the `data/2.5/weather` request URL -- a pure function so the
 /// `lang` query param can be unit-tested without a live network call.
-fn weather_url(lat: f64, lon: f64, api_key: &str, lang: &str) -> String {
-    format!(
-        "https://api.openweathermap.org/data/2.5/weather?lat={lat}&lon={lon}&appid={api_key}&units=metric&lang={lang}"
-    )
+/// `base` is `Endpoints::openweather`.
+fn weather_url(base: &str, lat: f64, lon: f64, api_key: &str, lang: &str) -> String {
+    format!("{base}/data/2.5/weather?lat={lat}&lon={lon}&appid={api_key}&units=metric&lang={lang}")
 }
 
 /// Builds the `data/2.5/forecast` request URL. See `weather_url`'s docs.
-fn forecast_url(lat: f64, lon: f64, api_key: &str, lang: &str) -> String {
-    format!(
-        "https://api.openweathermap.org/data/2.5/forecast?lat={lat}&lon={lon}&appid={api_key}&units=metric&lang={lang}"
-    )
+fn forecast_url(base: &str, lat: f64, lon: f64, api_key: &str, lang: &str) -> String {
+    format!("{base}/data/2.5/forecast?lat={lat}&lon={lon}&appid={api_key}&units=metric&lang={lang}")
 }
 
 /// Fetches weather data for a given location using the OpenWeatherMap API.
 ///
 /// This is a two-step process:
-/// 1. It first calls `get_coords` to convert the location name into latitude and longitude.
+/// 1. It first calls `get_coords` to convert the location name into latitude and
+///    longitude, unless `coordinates` (the location's saved ones) are given.
 /// 2. It then uses these coordinates to fetch the current weather data.
 ///
 /// # Arguments
 /// * `location` - The location to fetch weather for.
+/// * `coordinates` - Already-known `(lat, lon)` for `location`, skipping step 1.
 /// * `api_key` - Your personal OpenWeatherMap API key.
 /// * `lang` - The OpenWeatherMap `lang` code (see `Language::openweather_code`)
 ///   to request the `description` field in. Only that field is translated;
 ///   numeric fields are unaffected.
+/// * `client` - The shared client to send requests with (see `http`).
+/// * `retry` - How to retry a failed request (see `retry`).
+/// * `base` - Where the API lives (`Endpoints::openweather`).
 pub async fn get_weather(
     location: &Location,
+    coordinates: Option<(f64, f64)>,
     api_key: &str,
     lang: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
 ) -> Result<ApiResponse, ApiError> {
     // Get coordinates for the location
-    let weather_location = resolve_location(location, api_key).await?;
+    let (lat, lon) =
+        resolve_coordinates(location, coordinates, api_key, client, retry, base).await?;
 
-    let url = weather_url(weather_location.lat, weather_location.lon, api_key, lang);
+    let url = weather_url(base, lat, lon, api_key, lang);
 
     // Make the asynchronous GET request
-    let response = reqwest::get(&url).await.map_err(ApiError::RequestFailed)?;
+    let response = retry
+        .send(|| client.get(&url))
+        .await
+        .map_err(ApiError::RequestFailed)?;
     log::debug!("Weather API response: {}", response.status());
     // Check if the request was successful (e.g., status 200 OK)
     if response.status().is_success() {
@@ -262,8 +530,12 @@ pub async fn get_weather(
         })
     } else {
         // If the city is not found, the API returns a 404 status
-        log::error!("City not found: {}", location.name);
-        Err(ApiError::CityNotFound)
+        log::error!(
+            "Weather request for {} failed: {}",
+            location.name,
+            response.status()
+        );
+        Err(ApiError::from_response(response).await)
     }
 }
 
@@ -272,31 +544,75 @@ pub async fn get_weather(
 ///
 /// # Arguments
 /// * `location` - The location to fetch a forecast for.
+/// * `coordinates` - See `get_weather`'s docs.
 /// * `api_key` - Your personal OpenWeatherMap API key.
 /// * `lang` - See `get_weather`'s docs.
+/// * `client`, `retry`, `base` - See `get_weather`'s docs.
 pub async fn get_forecast(
     location: &Location,
+    coordinates: Option<(f64, f64)>,
     api_key: &str,
     lang: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
 ) -> Result<crate::weather_api::forecast::ForecastResponse, ApiError> {
-    let weather_location = resolve_location(location, api_key).await?;
+    let raw = get_raw_forecast(location, coordinates, api_key, lang, client, retry, base).await?;
+    Ok(crate::weather_api::forecast::aggregate_daily(raw))
+}
 
-    let url = forecast_url(weather_location.lat, weather_location.lon, api_key, lang);
+/// Fetches the same 5-day/3-hour forecast as `get_forecast`, kept as
+/// 3-hourly steps by `forecast::hourly_from_list` instead of aggregated.
+/// Arguments as for `get_forecast`.
+pub async fn get_hourly(
+    location: &Location,
+    coordinates: Option<(f64, f64)>,
+    api_key: &str,
+    lang: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
+) -> Result<crate::weather_api::forecast::HourlyForecast, ApiError> {
+    let raw = get_raw_forecast(location, coordinates, api_key, lang, client, retry, base).await?;
+    Ok(crate::weather_api::forecast::hourly_from_list(&raw))
+}
+
+/// The `data/2.5/forecast` response behind both `get_forecast` and
+/// `get_hourly`.
+async fn get_raw_forecast(
+    location: &Location,
+    coordinates: Option<(f64, f64)>,
+    api_key: &str,
+    lang: &str,
+    client: &reqwest::Client,
+    retry: &RetryPolicy,
+    base: &str,
+) -> Result<crate::weather_api::forecast::RawForecastResponse, ApiError> {
+    let (lat, lon) =
+        resolve_coordinates(location, coordinates, api_key, client, retry, base).await?;
 
-    let response = reqwest::get(&url).await.map_err(ApiError::RequestFailed)?;
+    let url = forecast_url(base, lat, lon, api_key, lang);
+
+    let response = retry
+        .send(|| client.get(&url))
+        .await
+        .map_err(ApiError::RequestFailed)?;
     log::debug!("Forecast API response: {}", response.status());
     if response.status().is_success() {
-        let raw = response
+        response
             .json::<crate::weather_api::forecast::RawForecastResponse>()
             .await
             .map_err(|e| {
                 log::error!("Failed to parse forecast API response: {e}");
                 ApiError::InvalidResponse
-            })?;
-        Ok(crate::weather_api::forecast::aggregate_daily(raw))
+            })
     } else {
-        log::error!("Forecast not found for: {}", location.name);
-        Err(ApiError::CityNotFound)
+        log::error!(
+            "Forecast request for {} failed: {}",
+            location.name,
+            response.status()
+        );
+        Err(ApiError::from_response(response).await)
     }
 }
 
@@ -332,6 +648,11 @@ pub fn get_weather_symbol(weather_condition: &str) -> WeatherSymbol {
 pub struct OpenWeatherProvider {
     api_key: String,
     language: Language,
+    retry: RetryPolicy,
+    /// Shared with every other provider -- see `http`.
+    client: reqwest::Client,
+    /// Where the API lives.
+    endpoints: Endpoints,
 }
 
 impl OpenWeatherProvider {
@@ -340,8 +661,23 @@ impl OpenWeatherProvider {
     /// # Arguments
     /// * `api_key` - The API key for the OpenWeatherMap service.
     /// * `language` - The language to request weather descriptions in.
-    pub fn new(api_key: String, language: Language) -> Self {
-        Self { api_key, language }
+    /// * `retry` - How to retry a failed request (see `retry`).
+    /// * `client` - The shared client to send requests with (see `http`).
+    /// * `endpoints` - Where to send them (see `endpoints`).
+    pub fn new(
+        api_key: String,
+        language: Language,
+        retry: RetryPolicy,
+        client: reqwest::Client,
+        endpoints: Endpoints,
+    ) -> Self {
+        Self {
+            api_key,
+            language,
+            retry,
+            client,
+            endpoints,
+        }
     }
 }
 
@@ -355,8 +691,12 @@ impl WeatherProvider for OpenWeatherProvider {
         let api_location = location_config_to_location(location);
         get_weather(
             &api_location,
+            location.coordinates(),
             &self.api_key,
             self.language.openweather_code(),
+            &self.client,
+            &self.retry,
+            self.endpoints.openweather(),
         )
         .await
     }
@@ -369,8 +709,30 @@ impl WeatherProvider for OpenWeatherProvider {
         let api_location = location_config_to_location(location);
         get_forecast(
             &api_location,
+            location.coordinates(),
             &self.api_key,
             self.language.openweather_code(),
+            &self.client,
+            &self.retry,
+            self.endpoints.openweather(),
+        )
+        .await
+    }
+
+    /// Fetches an hourly forecast by implementing the `WeatherProvider` trait.
+    async fn get_hourly(
+        &self,
+        location: &LocationConfig,
+    ) -> Result<crate::weather_api::forecast::HourlyForecast, ApiError> {
+        let api_location = location_config_to_location(location);
+        get_hourly(
+            &api_location,
+            location.coordinates(),
+            &self.api_key,
+            self.language.openweather_code(),
+            &self.client,
+            &self.retry,
+            self.endpoints.openweather(),
         )
         .await
     }
@@ -382,7 +744,14 @@ mod tests {
 
     #[test]
     fn test_weather_url_includes_language_code() {
-        let url = weather_url(1.0, 2.0, "test-key", "es");
+        let url = weather_url(
+            Endpoints::default().openweather(),
+            1.0,
+            2.0,
+            "test-key",
+            "es",
+        );
+        assert!(url.starts_with("https://api.openweathermap.org/data/2.5/weather?"));
         assert!(url.contains("lang=es"));
         assert!(url.contains("units=metric"));
     }
@@ -391,10 +760,76 @@ mod tests {
     fn test_forecast_url_includes_language_code() {
         // Korean uses OpenWeatherMap's own "kr" code, not ISO 639-1's "ko" --
         // see `Language::openweather_code`'s docs.
-        let url = forecast_url(1.0, 2.0, "test-key", "kr");
+        let url = forecast_url("http://localhost:8080", 1.0, 2.0, "test-key", "kr");
+        assert!(url.starts_with("http://localhost:8080/data/2.5/forecast?"));
         assert!(url.contains("lang=kr"));
     }
 
+    #[test]
+    fn test_error_from_status_separates_quota_and_outages() {
+        use reqwest::StatusCode;
+        assert!(matches!(
+            ApiError::from_parts(StatusCode::TOO_MANY_REQUESTS, Some("120"), ""),
+            ApiError::RateLimited {
+                message: None,
+                retry_after: Some(wait),
+            } if wait == Duration::from_secs(120)
+        ));
+        assert!(matches!(
+            ApiError::from_parts(StatusCode::SERVICE_UNAVAILABLE, None, ""),
+            ApiError::ServerError { status: 503, .. }
+        ));
+        assert!(matches!(
+            ApiError::from_parts(StatusCode::NOT_FOUND, None, ""),
+            ApiError::CityNotFound
+        ));
+    }
+
+    #[test]
+    fn test_error_from_parts_recognizes_rejected_keys_and_provider_messages() {
+        use reqwest::StatusCode;
+        let owm = r#"{"cod":401, "message": "Invalid API key. Please see https://openweathermap.org/faq#error401 for more info."}"#;
+        let error = ApiError::from_parts(StatusCode::UNAUTHORIZED, None, owm);
+        assert!(matches!(
+            &error,
+            ApiError::Unauthorized { status: 401, message: Some(message) }
+                if message.starts_with("Invalid API key.")
+        ));
+        assert!(error.to_string().contains("Invalid API key."));
+
+        // Google's bad-key answer is a 400, told apart only by its reason.
+        let google = r#"{"error": {"code": 400, "message": "API key not valid. Please pass a valid API key.", "status": "INVALID_ARGUMENT",
+            "details": [{"@type": "type.googleapis.com/google.rpc.ErrorInfo", "reason": "API_KEY_INVALID"}]}}"#;
+        assert!(matches!(
+            ApiError::from_parts(StatusCode::BAD_REQUEST, None, google),
+            ApiError::Unauthorized { status: 400, message: Some(message) }
+                if message == "API key not valid. Please pass a valid API key."
+        ));
+
+        let nws =
+            r#"{"title": "Unexpected Problem", "detail": "An unexpected problem has occurred."}"#;
+        let error = ApiError::from_parts(StatusCode::INTERNAL_SERVER_ERROR, None, nws);
+        assert_eq!(
+            error.to_string(),
+            "The weather service is having problems (HTTP 500): An unexpected problem has occurred."
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = "2026-07-02T12:00:00Z".parse::<jiff::Timestamp>().unwrap();
+        assert_eq!(parse_retry_after("30", now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after("Thu, 02 Jul 2026 12:01:30 GMT", now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            parse_retry_after("Thu, 02 Jul 2026 11:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
     #[test]
     fn test_get_weather_symbol_known_conditions() {
         assert_eq!(get_weather_symbol("Clear"), WeatherSymbol::Clear);
@@ -0,0 +1,271 @@
+//! # Retrying Failed Requests
+//!
+//! `RetryPolicy` sends a request again when it failed in a way the next
+//! attempt might not: it timed out, or the provider answered `429` or
+//! `5xx`. Anything else -- a bad key, an unknown city, a refused connection
+//! -- fails straight away, since asking again wouldn't change the answer.
+//! `openweather_api` and `google_weather_api` send every request through
+//! it; `AppConfig::retry` configures it.
+//!
+//! Waits double from `base_delay_ms` per retry, with up to `jitter` of each
+//! one randomized so several instances that failed together don't all come
+//! back at the same moment. A `Retry-After` on the response replaces the
+//! computed wait -- unless it asks for longer than `max_delay_ms`, in which
+//! case the response is handed back as-is, for `ApiError::from_response` to
+//! report (and `FallbackProvider` to move on from) rather than stalling the
+//! refresh.
+
+use std::hash::{BuildHasher, RandomState};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::weather_api::openweather_api::parse_retry_after;
+
+/// How many times, and how patiently, to retry a request -- see the module
+/// docs. `#[serde(default)]` per field, so a config file can set just the
+/// ones it cares about.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    /// Attempts in total, the first included -- `1` (or `0`) never retries.
+    pub max_attempts: u32,
+    /// The wait before the first retry; doubled before each one after it.
+    pub base_delay_ms: u64,
+    /// The longest wait between two attempts, whether computed or asked
+    /// for by `Retry-After`.
+    pub max_delay_ms: u64,
+    /// The fraction of each computed wait that's random, from `0.0` (none)
+    /// to `1.0` (anywhere between no wait and the full one).
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 10_000,
+            jitter: 0.5,
+        }
+    }
+}
+
+/// Why a failed attempt is worth repeating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Retry {
+    /// Wait the policy's own backoff.
+    Backoff,
+    /// Wait what the response's `Retry-After` asked for.
+    After(Duration),
+}
+
+impl RetryPolicy {
+    /// Sends the request `request` builds -- called again for each attempt,
+    /// since a `RequestBuilder` is used up by sending it -- until it
+    /// succeeds, fails in a way not worth retrying, or runs out of
+    /// attempts. The last response is returned whatever its status; the
+    /// caller still checks it.
+    ///
+    /// # Errors
+    /// The last attempt's `reqwest::Error`, if it didn't get a response.
+    pub async fn send(
+        &self,
+        request: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let mut attempt = 1;
+        loop {
+            let result = request().send().await;
+            let Some(delay) =
+                retry_for(&result).and_then(|retry| self.delay(retry, attempt, random_fraction()))
+            else {
+                return result;
+            };
+            match &result {
+                Ok(response) => log::warn!(
+                    "Request failed with {} (attempt {attempt}/{}), retrying in {delay:?}",
+                    response.status(),
+                    self.max_attempts
+                ),
+                Err(_) => log::warn!(
+                    "Request timed out (attempt {attempt}/{}), retrying in {delay:?}",
+                    self.max_attempts
+                ),
+            }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// How long to wait after failed attempt number `attempt` (from `1`)
+    /// before the next, or `None` to stop. `random` is in `[0, 1)`.
+    fn delay(&self, retry: Retry, attempt: u32, random: f64) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let max_delay = Duration::from_millis(self.max_delay_ms);
+        match retry {
+            Retry::After(wait) => (wait <= max_delay).then_some(wait),
+            Retry::Backoff => {
+                let backoff = Duration::from_millis(self.base_delay_ms)
+                    .saturating_mul(2u32.saturating_pow(attempt - 1))
+                    .min(max_delay);
+                Some(backoff.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * random))
+            }
+        }
+    }
+}
+
+/// Whether an attempt's outcome is worth retrying: a timeout, or a `429`
+/// or `5xx` response.
+fn retry_for(result: &Result<reqwest::Response, reqwest::Error>) -> Option<Retry> {
+    match result {
+        Ok(response) => retry_for_status(
+            response.status(),
+            response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()),
+        ),
+        Err(error) => error.is_timeout().then_some(Retry::Backoff),
+    }
+}
+
+/// `retry_for`'s answer for a response -- split out so it's testable
+/// without one.
+fn retry_for_status(status: reqwest::StatusCode, retry_after: Option<&str>) -> Option<Retry> {
+    if status != reqwest::StatusCode::TOO_MANY_REQUESTS && !status.is_server_error() {
+        return None;
+    }
+    Some(
+        match retry_after.and_then(|value| parse_retry_after(value, jiff::Timestamp::now())) {
+            Some(wait) => Retry::After(wait),
+            None => Retry::Backoff,
+        },
+    )
+}
+
+/// A number in `[0, 1)` for jitter. `RandomState` is randomly seeded, which
+/// is all the randomness this needs.
+fn random_fraction() -> f64 {
+    (RandomState::new().hash_one(0u8) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::StatusCode;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[test]
+    fn test_only_timeouts_rate_limits_and_server_errors_are_retried() {
+        assert_eq!(
+            retry_for_status(StatusCode::SERVICE_UNAVAILABLE, None),
+            Some(Retry::Backoff)
+        );
+        assert_eq!(
+            retry_for_status(StatusCode::TOO_MANY_REQUESTS, Some("7")),
+            Some(Retry::After(Duration::from_secs(7)))
+        );
+        for status in [
+            StatusCode::OK,
+            StatusCode::BAD_REQUEST,
+            StatusCode::UNAUTHORIZED,
+            StatusCode::NOT_FOUND,
+        ] {
+            assert_eq!(retry_for_status(status, Some("7")), None, "{status}");
+        }
+    }
+
+    #[test]
+    fn test_delay_doubles_up_to_the_cap_and_stops_after_max_attempts() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            base_delay_ms: 1_000,
+            max_delay_ms: 5_000,
+            jitter: 0.0,
+        };
+        let delays: Vec<_> = (1..=6)
+            .map(|attempt| policy.delay(Retry::Backoff, attempt, 0.9))
+            .collect();
+        assert_eq!(
+            delays,
+            [1, 2, 4, 5, 5]
+                .map(|secs| Some(Duration::from_secs(secs)))
+                .into_iter()
+                .chain([None])
+                .collect::<Vec<_>>()
+        );
+        let never = RetryPolicy {
+            max_attempts: 0,
+            ..policy
+        };
+        assert_eq!(never.delay(Retry::Backoff, 1, 0.0), None);
+    }
+
+    #[test]
+    fn test_jitter_shortens_the_wait_by_at_most_its_fraction() {
+        let policy = RetryPolicy {
+            jitter: 0.5,
+            ..RetryPolicy::default()
+        };
+        let full = Duration::from_millis(policy.base_delay_ms);
+        assert_eq!(policy.delay(Retry::Backoff, 1, 0.0), Some(full));
+        assert_eq!(
+            policy.delay(Retry::Backoff, 1, 0.999),
+            Some(full.mul_f64(1.0 - 0.4995))
+        );
+        let random = random_fraction();
+        assert!((0.0..1.0).contains(&random));
+    }
+
+    #[test]
+    fn test_retry_after_beyond_the_cap_gives_up_instead_of_waiting() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.delay(Retry::After(Duration::from_secs(2)), 1, 0.5),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.delay(Retry::After(Duration::from_secs(3600)), 1, 0.5),
+            None
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_take_their_defaults() {
+        let policy: RetryPolicy = serde_json::from_str(r#"{"max_attempts": 5}"#).unwrap();
+        assert_eq!(
+            policy,
+            RetryPolicy {
+                max_attempts: 5,
+                ..RetryPolicy::default()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_a_server_error_until_it_succeeds() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0; 1024];
+                let _ = socket.read(&mut request).await.unwrap();
+                let response =
+                    format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let policy = RetryPolicy {
+            base_delay_ms: 1,
+            ..RetryPolicy::default()
+        };
+        let client = reqwest::Client::new();
+        let response = policy.send(|| client.get(&url)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        server.await.unwrap();
+    }
+}
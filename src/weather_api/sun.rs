@@ -0,0 +1,92 @@
+//! # Sunrise/Sunset Calculation
+//!
+//! Computes sunrise and sunset locally from coordinates and a date, for
+//! providers whose APIs don't return them at all (the National Weather
+//! Service's gridpoint forecasts) -- rather than leaving `Sys::sunrise`/
+//! `Sys::sunset` at `0`, which the main screen would render as a
+//! confidently wrong "12:00 AM".
+//!
+//! Uses the standard "sunrise equation" (solar mean anomaly -> equation of
+//! center -> ecliptic longitude -> solar transit/declination -> hour
+//! angle), with the usual -0.833° altitude correction for atmospheric
+//! refraction and the solar disc's radius. Accurate to within a couple of
+//! minutes at non-polar latitudes, which is all a display-only "Sunrise
+//! 5:34 AM" chip needs.
+
+/// Julian date of the Unix epoch (1970-01-01T00:00:00Z).
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2_440_587.5;
+/// Julian date of the J2000.0 epoch (2000-01-01T12:00:00 TT).
+const J2000_JULIAN_DATE: f64 = 2_451_545.0;
+/// Earth's axial tilt, in degrees.
+const OBLIQUITY_DEGREES: f64 = 23.4397;
+
+/// Sunrise and sunset for `date` (the location's own local calendar date)
+/// at `lat`/`lon` (degrees, east-positive), as Unix timestamps. `None` on a
+/// polar day or polar night, when the sun never crosses the horizon.
+pub(crate) fn sunrise_sunset(lat: f64, lon: f64, date: jiff::civil::Date) -> Option<(i64, i64)> {
+    let days_since_unix_epoch = date
+        .to_zoned(jiff::tz::TimeZone::UTC)
+        .ok()?
+        .timestamp()
+        .as_second()
+        .div_euclid(86_400) as f64;
+    // Julian date at noon UTC on `date`, counted from J2000.0.
+    let n = days_since_unix_epoch + UNIX_EPOCH_JULIAN_DATE + 0.5 - J2000_JULIAN_DATE + 0.0008;
+    let mean_solar_time = n - lon / 360.0;
+
+    let mean_anomaly = (357.5291 + 0.985_600_28 * mean_solar_time).rem_euclid(360.0);
+    let m = mean_anomaly.to_radians();
+    let center = 1.9148 * m.sin() + 0.0200 * (2.0 * m).sin() + 0.0003 * (3.0 * m).sin();
+    let ecliptic_longitude = (mean_anomaly + center + 180.0 + 102.9372)
+        .rem_euclid(360.0)
+        .to_radians();
+
+    let transit = J2000_JULIAN_DATE + mean_solar_time + 0.0053 * m.sin()
+        - 0.0069 * (2.0 * ecliptic_longitude).sin();
+    let sin_declination = ecliptic_longitude.sin() * OBLIQUITY_DEGREES.to_radians().sin();
+    let cos_declination = sin_declination.asin().cos();
+
+    let phi = lat.to_radians();
+    let cos_hour_angle = ((-0.833_f64).to_radians().sin() - phi.sin() * sin_declination)
+        / (phi.cos() * cos_declination);
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle_days = cos_hour_angle.acos().to_degrees() / 360.0;
+
+    let to_unix = |julian: f64| ((julian - UNIX_EPOCH_JULIAN_DATE) * 86_400.0).round() as i64;
+    Some((
+        to_unix(transit - hour_angle_days),
+        to_unix(transit + hour_angle_days),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Published times for Peoria, IL on 2026-07-04 are 5:35 AM / 8:31 PM
+    /// CDT (10:35 / 01:31 UTC); the equation lands within a few minutes.
+    #[test]
+    fn test_sunrise_sunset_mid_latitude_summer() {
+        let date = jiff::civil::date(2026, 7, 4);
+        let (sunrise, sunset) = sunrise_sunset(40.6936, -89.5890, date).unwrap();
+        let expected_sunrise = "2026-07-04T10:35:00Z"
+            .parse::<jiff::Timestamp>()
+            .unwrap()
+            .as_second();
+        let expected_sunset = "2026-07-05T01:31:00Z"
+            .parse::<jiff::Timestamp>()
+            .unwrap()
+            .as_second();
+        assert!((sunrise - expected_sunrise).abs() < 5 * 60);
+        assert!((sunset - expected_sunset).abs() < 5 * 60);
+    }
+
+    #[test]
+    fn test_polar_night_has_no_sunrise() {
+        // Utqiagvik, AK in late December.
+        let date = jiff::civil::date(2026, 12, 21);
+        assert!(sunrise_sunset(71.29, -156.79, date).is_none());
+    }
+}
@@ -10,13 +10,16 @@
 //!   implement. It guarantees that any provider can fetch weather data in a
 //!   standardized way.
 //! - **`WeatherProviderFactory`**: A factory responsible for creating concrete
-//!   instances of `WeatherProvider` (e.g., `OpenWeatherProvider`, `GoogleWeatherProvider`)
-//!   based on the application's configuration.
+//!   instances of `WeatherProvider` (e.g., `OpenWeatherProvider`, `GoogleWeatherProvider`,
+//!   `OpenMeteoProvider`, `NwsProvider`, `MetNorwayProvider`) based on the
+//!   application's configuration, or a `FallbackProvider` chaining several.
 
 use crate::config::{Language, LocationConfig, WeatherApiProvider};
 use crate::weather_api::alerts::WeatherAlert;
-use crate::weather_api::forecast::ForecastResponse;
+use crate::weather_api::endpoints::Endpoints;
+use crate::weather_api::forecast::{ForecastResponse, HourlyForecast};
 use crate::weather_api::openweather_api::{ApiError, ApiResponse, Location};
+use crate::weather_api::retry::RetryPolicy;
 use async_trait::async_trait;
 
 /// A trait for weather API providers.
@@ -42,13 +45,30 @@ pub trait WeatherProvider {
     /// Returns an `ApiError` if the data cannot be fetched.
     async fn get_forecast(&self, location: &LocationConfig) -> Result<ForecastResponse, ApiError>;
 
-    /// Fetches active weather alerts for a given location.
+    /// Fetches the next `forecast::MAX_FORECAST_HOURS` hours of forecast,
+    /// starting now, for a given location.
+    ///
+    /// # Errors
+    /// Returns an `ApiError` if the data cannot be fetched.
+    async fn get_hourly(&self, location: &LocationConfig) -> Result<HourlyForecast, ApiError>;
+
+    /// Fetches active weather alerts for a given location. Providers that
+    /// don't publish alerts keep this default, which always answers "none"
+    /// -- see `supports_alerts`.
     ///
     /// # Errors
     /// Returns an `ApiError` if the data cannot be fetched.
     async fn get_alerts(&self, _location: &LocationConfig) -> Result<Vec<WeatherAlert>, ApiError> {
         Ok(vec![])
     }
+
+    /// Whether `get_alerts` actually asks the provider, rather than being
+    /// the default's "none" -- so `FallbackProvider` never stands an
+    /// alert-less provider in for one whose alerts failed, which would pass
+    /// the failure off as an all-clear.
+    fn supports_alerts(&self) -> bool {
+        false
+    }
 }
 
 /// A factory for creating weather providers.
@@ -65,29 +85,111 @@ impl WeatherProviderFactory {
     /// * `api_token` - An `Option` containing the API token, if required by the provider.
     /// * `language` - The language to request weather *descriptions* in --
     ///   see `Language`'s docs.
+    /// * `retry` - How to retry a failed request (`AppConfig::retry`). Only
+    ///   the keyed providers take it; the keyless ones already answer
+    ///   `429`/`5xx` by falling back, and have no quota worth protecting.
+    /// * `client` - The shared client every provider sends its requests
+    ///   with (see `http`).
+    /// * `endpoints` - Where each service lives (`AppConfig::endpoints`).
     ///
     /// # Errors
-    /// Returns an error `String` if a required API token is missing for the selected provider.
+    /// Returns an error `String` if a required API token is missing for the selected provider
+    /// (see `WeatherApiProvider::requires_api_token`).
     pub fn create_provider(
         provider_type: &WeatherApiProvider,
         api_token: Option<String>,
         language: Language,
+        retry: RetryPolicy,
+        client: &reqwest::Client,
+        endpoints: &Endpoints,
     ) -> Result<Box<dyn WeatherProvider + Send + Sync>, String> {
+        let client = client.clone();
+        let endpoints = endpoints.clone();
         match provider_type {
             WeatherApiProvider::OpenWeather => {
                 let token = api_token.ok_or("OpenWeather API requires an API token")?;
                 Ok(Box::new(super::openweather_api::OpenWeatherProvider::new(
-                    token, language,
+                    token, language, retry, client, endpoints,
                 )))
             }
             WeatherApiProvider::GoogleWeather => {
                 let token = api_token.ok_or("Google Weather API requires an API token")?;
                 Ok(Box::new(
-                    super::google_weather_api::GoogleWeatherProvider::new(token, language),
+                    super::google_weather_api::GoogleWeatherProvider::new(
+                        token, language, retry, client, endpoints,
+                    ),
                 ))
             }
+            // Keyless -- any token passed in is simply ignored, so callers
+            // can keep handing over whatever the keychain returned without
+            // special-casing this provider.
+            WeatherApiProvider::OpenMeteo => Ok(Box::new(
+                super::open_meteo_api::OpenMeteoProvider::new(client, endpoints),
+            )),
+            WeatherApiProvider::NationalWeatherService => Ok(Box::new(
+                super::nws_api::NwsProvider::new(client, endpoints),
+            )),
+            WeatherApiProvider::MetNorway => Ok(Box::new(
+                super::met_norway_api::MetNorwayProvider::new(client, endpoints),
+            )),
         }
     }
+
+    /// Creates a `FallbackProvider` over `chain` (see
+    /// `AppConfig::provider_chain`), which must list the primary provider
+    /// first. `token_for` looks up each keyed provider's own token (see
+    /// `AppConfig::get_api_token`); it's never called for a keyless one, so
+    /// a chain of keyless providers never touches the keychain. The primary
+    /// fails exactly like `create_provider` without a token. Fallbacks are
+    /// best-effort: a keyed fallback with no token is skipped with a
+    /// warning rather than failing the whole chain.
+    ///
+    /// Always wraps, even a one-provider chain, so responses are stamped
+    /// with their provider either way.
+    pub fn create_chain(
+        chain: &[WeatherApiProvider],
+        token_for: impl Fn(&WeatherApiProvider) -> Option<String>,
+        language: Language,
+        retry: RetryPolicy,
+        client: &reqwest::Client,
+        endpoints: &Endpoints,
+    ) -> Result<Box<dyn WeatherProvider + Send + Sync>, String> {
+        let token_for = |provider: &WeatherApiProvider| {
+            provider
+                .requires_api_token()
+                .then(|| token_for(provider))
+                .flatten()
+                .filter(|token| !token.is_empty())
+        };
+        let (primary, fallbacks) = chain
+            .split_first()
+            .ok_or("No weather provider configured")?;
+
+        let mut providers = vec![(
+            primary.clone(),
+            Self::create_provider(
+                primary,
+                token_for(primary),
+                language,
+                retry,
+                client,
+                endpoints,
+            )?,
+        )];
+        for fallback in fallbacks {
+            let token = token_for(fallback);
+            if fallback.requires_api_token() && token.is_none() {
+                log::warn!("Skipping fallback provider {fallback}: no API token saved for it");
+                continue;
+            }
+            providers.push((
+                fallback.clone(),
+                Self::create_provider(fallback, token, language, retry, client, endpoints)?,
+            ));
+        }
+
+        Ok(Box::new(super::fallback::FallbackProvider::new(providers)))
+    }
 }
 
 /// Converts an application-level `LocationConfig` to an API-level `Location` struct.